@@ -1,7 +1,26 @@
+pub mod elo_filter;
 pub mod errors;
 pub mod fen;
 pub mod game;
+pub mod game_detail;
+pub mod game_filter;
+pub mod game_import_error;
+pub mod game_page;
+pub mod game_search;
+pub mod import_job;
+pub mod import_progress;
+pub mod move_comparison;
 pub mod move_stat;
+pub mod move_stats_scope;
+pub mod move_tree;
 pub mod new_game;
+pub mod opening;
+pub mod opening_report;
 pub mod pgn;
+pub mod pgn_import;
+pub mod player_profile;
 pub mod position;
+pub mod repertoire;
+pub mod store_progress;
+pub mod stored_batch;
+pub mod tracked_player;
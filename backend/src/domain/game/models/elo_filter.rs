@@ -0,0 +1,42 @@
+/// Bounds of the ratings in a game, seen from the side of the player, or of the side to
+/// move for statistics over all games. Games with an unknown rating are excluded as soon
+/// as a bound is set for that side
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EloFilter {
+    min_own_elo: Option<u16>,
+    max_own_elo: Option<u16>,
+    min_opponent_elo: Option<u16>,
+    max_opponent_elo: Option<u16>,
+}
+
+impl EloFilter {
+    pub fn new(
+        min_own_elo: Option<u16>,
+        max_own_elo: Option<u16>,
+        min_opponent_elo: Option<u16>,
+        max_opponent_elo: Option<u16>,
+    ) -> Self {
+        Self {
+            min_own_elo,
+            max_own_elo,
+            min_opponent_elo,
+            max_opponent_elo,
+        }
+    }
+
+    pub fn min_own_elo(&self) -> Option<&u16> {
+        self.min_own_elo.as_ref()
+    }
+
+    pub fn max_own_elo(&self) -> Option<&u16> {
+        self.max_own_elo.as_ref()
+    }
+
+    pub fn min_opponent_elo(&self) -> Option<&u16> {
+        self.min_opponent_elo.as_ref()
+    }
+
+    pub fn max_opponent_elo(&self) -> Option<&u16> {
+        self.max_opponent_elo.as_ref()
+    }
+}
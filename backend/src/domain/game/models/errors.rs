@@ -23,4 +23,18 @@ pub enum StoreGamesError {
     GameRepositoryError(#[from] GameRepositoryError),
     #[error(transparent)]
     PlatformError(#[from] PlatformError),
+    #[error(transparent)]
+    InvalidPgn(#[from] InvalidPgnError),
 }
+
+#[derive(Debug, Error)]
+pub enum SaveRepertoireError {
+    #[error(transparent)]
+    GameRepositoryError(#[from] GameRepositoryError),
+    #[error(transparent)]
+    InvalidPgn(#[from] InvalidPgnError),
+}
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[error("invalid game cursor")]
+pub struct InvalidGameCursorError;
@@ -1,6 +1,6 @@
 use std::fmt::{Display, Formatter};
 
-use crate::domain::game::models::errors::InvalidFenError;
+use crate::domain::game::models::{errors::InvalidFenError, game::Color};
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// A valid Forsyth–Edwards Notation string
@@ -22,6 +22,14 @@ impl Fen {
     pub fn new_unchecked(fen_str: &str) -> Self {
         Self(fen_str.into())
     }
+
+    /// Color to move, read from the active color field
+    pub fn turn(&self) -> Color {
+        match self.0.split_whitespace().nth(1) {
+            Some("b") => Color::Black,
+            _ => Color::White,
+        }
+    }
 }
 
 impl Display for Fen {
@@ -29,3 +37,21 @@ impl Display for Fen {
         f.write_str(&self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turn() {
+        assert_eq!(
+            Fen::new_unchecked("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").turn(),
+            Color::White
+        );
+        assert_eq!(
+            Fen::new_unchecked("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+                .turn(),
+            Color::Black
+        );
+    }
+}
@@ -1,13 +1,102 @@
 use chrono::{DateTime, Utc};
 use strum_macros::{EnumString, IntoStaticStr};
 
-use crate::domain::{game::models::pgn::Pgn, platform::models::PlatformName};
+use crate::domain::{
+    game::models::{opening::Opening, pgn::Pgn},
+    platform::models::PlatformName,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString, IntoStaticStr)]
 pub enum Color {
     White,
     Black,
 }
+
+/// Speed category of a game, as reported by the platform or estimated from the time control
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, IntoStaticStr)]
+pub enum TimeClass {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    /// Correspondence games with days per move
+    Daily,
+}
+
+impl TimeClass {
+    /// Estimates the time class from a PGN `TimeControl` value (e.g. `180+2`, `600`, `1/86400`)
+    /// using the estimated game duration `base + 40 * increment`
+    pub fn from_time_control(time_control: &str) -> Option<Self> {
+        let first_period = time_control.trim().split(':').next()?;
+        if let Some((_, seconds_per_period)) = first_period.split_once('/') {
+            let seconds = seconds_per_period.parse::<u64>().ok()?;
+            return Some(if seconds >= 86400 {
+                TimeClass::Daily
+            } else {
+                TimeClass::Classical
+            });
+        }
+
+        let (base, increment) = first_period.split_once('+').unwrap_or((first_period, "0"));
+        let estimated_seconds = increment
+            .parse::<u64>()
+            .ok()?
+            .checked_mul(40)?
+            .checked_add(base.parse::<u64>().ok()?)?;
+
+        Some(match estimated_seconds {
+            0..180 => TimeClass::Bullet,
+            180..480 => TimeClass::Blitz,
+            480..1500 => TimeClass::Rapid,
+            _ => TimeClass::Classical,
+        })
+    }
+}
+
+/// Rule set a game was played with, only standard games have their positions indexed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, IntoStaticStr)]
+pub enum Variant {
+    /// Standard rules, including games started from a custom position
+    Standard,
+    Chess960,
+    Crazyhouse,
+    Bughouse,
+    ThreeCheck,
+    KingOfTheHill,
+    Atomic,
+    Antichess,
+    Horde,
+    RacingKings,
+    /// Any rule set not known to the platforms
+    Other,
+}
+
+impl Variant {
+    /// Maps the variant names used by Chess.com `rules`, Lichess `variant` and the PGN
+    /// `Variant` tag, e.g. `chess`, `kingOfTheHill` or `From Position`
+    pub fn from_name(name: &str) -> Self {
+        let normalized = name
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        match normalized.as_str() {
+            "" | "chess" | "standard" | "fromposition" | "oddschess" => Variant::Standard,
+            "chess960" | "fischerandom" | "fischerrandom" => Variant::Chess960,
+            "crazyhouse" => Variant::Crazyhouse,
+            "bughouse" => Variant::Bughouse,
+            "threecheck" | "3check" => Variant::ThreeCheck,
+            "kingofthehill" => Variant::KingOfTheHill,
+            "atomic" => Variant::Atomic,
+            "antichess" | "suicide" | "giveaway" => Variant::Antichess,
+            "horde" => Variant::Horde,
+            "racingkings" => Variant::RacingKings,
+            _ => Variant::Other,
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Game {
     id: uuid::Uuid,
@@ -22,9 +111,17 @@ pub struct Game {
     platform_name: PlatformName,
     pgn: Pgn,
     finished_at: DateTime<Utc>,
+    time_class: Option<TimeClass>,
+    /// time control as written in the PGN `TimeControl` tag
+    time_control: Option<String>,
+    rated: bool,
+    variant: Variant,
+    /// opening of the deepest position of the game in the opening book
+    opening: Option<Opening>,
 }
 
 impl Game {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: uuid::Uuid,
         white: String,
@@ -35,17 +132,27 @@ impl Game {
         platform_name: PlatformName,
         pgn: Pgn,
         finished_at: DateTime<Utc>,
+        time_class: Option<TimeClass>,
+        time_control: Option<String>,
+        rated: bool,
+        variant: Variant,
+        opening: Option<Opening>,
     ) -> Self {
         Self {
-            id: id,
-            white: white,
-            white_elo: white_elo,
-            black: black,
-            black_elo: black_elo,
-            winner: winner,
-            platform_name: platform_name,
-            pgn: pgn,
-            finished_at: finished_at,
+            id,
+            white,
+            white_elo,
+            black,
+            black_elo,
+            winner,
+            platform_name,
+            pgn,
+            finished_at,
+            time_class,
+            time_control,
+            rated,
+            variant,
+            opening,
         }
     }
 
@@ -84,4 +191,79 @@ impl Game {
     pub fn winner(&self) -> Option<&Color> {
         self.winner.as_ref()
     }
+
+    pub fn time_class(&self) -> Option<&TimeClass> {
+        self.time_class.as_ref()
+    }
+
+    pub fn time_control(&self) -> Option<&String> {
+        self.time_control.as_ref()
+    }
+
+    pub fn rated(&self) -> &bool {
+        &self.rated
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    pub fn opening(&self) -> Option<&Opening> {
+        self.opening.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_class_from_time_control() {
+        assert_eq!(TimeClass::from_time_control("60"), Some(TimeClass::Bullet));
+        assert_eq!(
+            TimeClass::from_time_control("120+1"),
+            Some(TimeClass::Bullet)
+        );
+        assert_eq!(
+            TimeClass::from_time_control("180+2"),
+            Some(TimeClass::Blitz)
+        );
+        assert_eq!(TimeClass::from_time_control("600"), Some(TimeClass::Rapid));
+        assert_eq!(
+            TimeClass::from_time_control("900+10"),
+            Some(TimeClass::Rapid)
+        );
+        assert_eq!(
+            TimeClass::from_time_control("1800+30"),
+            Some(TimeClass::Classical)
+        );
+        assert_eq!(
+            TimeClass::from_time_control("40/7200:3600"),
+            Some(TimeClass::Classical)
+        );
+        assert_eq!(
+            TimeClass::from_time_control("1/259200"),
+            Some(TimeClass::Daily)
+        );
+        assert_eq!(TimeClass::from_time_control("-"), None);
+        assert_eq!(TimeClass::from_time_control("?"), None);
+    }
+
+    #[test]
+    fn test_time_class_from_overflowing_time_control() {
+        assert_eq!(TimeClass::from_time_control("1+999999999999999999"), None);
+        assert_eq!(TimeClass::from_time_control("18446744073709551615+1"), None);
+    }
+
+    #[test]
+    fn test_variant_from_name() {
+        assert_eq!(Variant::from_name("chess"), Variant::Standard);
+        assert_eq!(Variant::from_name("From Position"), Variant::Standard);
+        assert_eq!(Variant::from_name("Chess960"), Variant::Chess960);
+        assert_eq!(Variant::from_name("kingOfTheHill"), Variant::KingOfTheHill);
+        assert_eq!(Variant::from_name("threecheck"), Variant::ThreeCheck);
+        assert_eq!(Variant::from_name("Three-check"), Variant::ThreeCheck);
+        assert_eq!(Variant::from_name("oddschess"), Variant::Standard);
+        assert_eq!(Variant::from_name("duck"), Variant::Other);
+    }
 }
@@ -0,0 +1,91 @@
+use std::time::Duration;
+
+use crate::domain::game::models::{fen::Fen, game::Game};
+
+/// Half-move of the mainline of a game
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GamePly {
+    /// half-move number, starting at 1
+    ply: u16,
+    san: String,
+    uci: String,
+    /// position after the move
+    fen: Fen,
+    /// remaining time of the player who moved, if the PGN has clock annotations
+    clock: Option<Duration>,
+}
+
+impl GamePly {
+    pub fn new(ply: u16, san: String, uci: String, fen: Fen, clock: Option<Duration>) -> Self {
+        Self {
+            ply,
+            san,
+            uci,
+            fen,
+            clock,
+        }
+    }
+
+    pub fn ply(&self) -> &u16 {
+        &self.ply
+    }
+
+    pub fn san(&self) -> &String {
+        &self.san
+    }
+
+    pub fn uci(&self) -> &String {
+        &self.uci
+    }
+
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    pub fn clock(&self) -> Option<&Duration> {
+        self.clock.as_ref()
+    }
+}
+
+/// Moves of a game as replayed from its PGN, without variations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mainline {
+    /// initial position, which differs from the standard one for games set up from a FEN
+    start_fen: Fen,
+    plies: Vec<GamePly>,
+}
+
+impl Mainline {
+    pub fn new(start_fen: Fen, plies: Vec<GamePly>) -> Self {
+        Self { start_fen, plies }
+    }
+
+    pub fn start_fen(&self) -> &Fen {
+        &self.start_fen
+    }
+
+    pub fn plies(&self) -> &[GamePly] {
+        &self.plies
+    }
+}
+
+/// Stored game together with its moves
+pub struct GameDetail {
+    game: Game,
+    /// `None` if the moves can't be replayed, e.g. for other variants than standard chess
+    mainline: Option<Mainline>,
+}
+
+impl GameDetail {
+    pub fn new(game: Game, mainline: Option<Mainline>) -> Self {
+        Self { game, mainline }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn mainline(&self) -> Option<&Mainline> {
+        self.mainline.as_ref()
+    }
+}
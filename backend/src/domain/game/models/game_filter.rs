@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::game::models::game::TimeClass;
+
+/// Narrows down which games of a player are taken into account
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GameFilter {
+    from_timestamp: Option<DateTime<Utc>>,
+    to_timestamp: Option<DateTime<Utc>>,
+    /// `None` matches games of every time class
+    time_classes: Option<Vec<TimeClass>>,
+    rated_only: bool,
+    /// bounds of the average rating of both players, games with an unknown rating are
+    /// excluded as soon as a bound is set
+    min_rating: Option<u16>,
+    max_rating: Option<u16>,
+}
+
+impl GameFilter {
+    pub fn new(
+        from_timestamp: Option<DateTime<Utc>>,
+        to_timestamp: Option<DateTime<Utc>>,
+        time_classes: Option<Vec<TimeClass>>,
+        rated_only: bool,
+        min_rating: Option<u16>,
+        max_rating: Option<u16>,
+    ) -> Self {
+        Self {
+            from_timestamp,
+            to_timestamp,
+            time_classes,
+            rated_only,
+            min_rating,
+            max_rating,
+        }
+    }
+
+    pub fn from_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.from_timestamp.as_ref()
+    }
+
+    pub fn to_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.to_timestamp.as_ref()
+    }
+
+    pub fn time_classes(&self) -> Option<&Vec<TimeClass>> {
+        self.time_classes.as_ref()
+    }
+
+    pub fn rated_only(&self) -> &bool {
+        &self.rated_only
+    }
+
+    pub fn min_rating(&self) -> Option<&u16> {
+        self.min_rating.as_ref()
+    }
+
+    pub fn max_rating(&self) -> Option<&u16> {
+        self.max_rating.as_ref()
+    }
+}
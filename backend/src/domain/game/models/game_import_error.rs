@@ -0,0 +1,51 @@
+use chrono::{DateTime, Utc};
+
+/// Record of a stored game whose positions could not be indexed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameImportError {
+    game_id: uuid::Uuid,
+    reason: String,
+    /// move that could not be played, if the game failed on a move
+    san: Option<String>,
+    /// half-move number of the offending move, starting at 1
+    ply: Option<u16>,
+    created_at: DateTime<Utc>,
+}
+
+impl GameImportError {
+    pub fn new(
+        game_id: uuid::Uuid,
+        reason: String,
+        san: Option<String>,
+        ply: Option<u16>,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            game_id,
+            reason,
+            san,
+            ply,
+            created_at,
+        }
+    }
+
+    pub fn game_id(&self) -> &uuid::Uuid {
+        &self.game_id
+    }
+
+    pub fn reason(&self) -> &String {
+        &self.reason
+    }
+
+    pub fn san(&self) -> Option<&String> {
+        self.san.as_ref()
+    }
+
+    pub fn ply(&self) -> Option<&u16> {
+        self.ply.as_ref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Utc};
+use std::{fmt, str::FromStr};
+
+use crate::domain::game::models::{errors::InvalidGameCursorError, game::Game};
+
+/// Position of a game in a list of games ordered by the latest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameCursor {
+    finished_at: DateTime<Utc>,
+    /// tie-breaker for games that finished at the same time
+    id: uuid::Uuid,
+}
+
+impl GameCursor {
+    pub fn new(finished_at: DateTime<Utc>, id: uuid::Uuid) -> Self {
+        Self { finished_at, id }
+    }
+
+    pub fn finished_at(&self) -> &DateTime<Utc> {
+        &self.finished_at
+    }
+
+    pub fn id(&self) -> &uuid::Uuid {
+        &self.id
+    }
+}
+
+impl From<&Game> for GameCursor {
+    fn from(value: &Game) -> Self {
+        Self::new(*value.finished_at(), *value.id())
+    }
+}
+
+impl fmt::Display for GameCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}_{}", self.finished_at.timestamp_micros(), self.id)
+    }
+}
+
+impl FromStr for GameCursor {
+    type Err = InvalidGameCursorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (micros, id) = s.split_once('_').ok_or(InvalidGameCursorError)?;
+        let finished_at = micros
+            .parse::<i64>()
+            .ok()
+            .and_then(DateTime::from_timestamp_micros)
+            .ok_or(InvalidGameCursorError)?;
+        let id = uuid::Uuid::parse_str(id).map_err(|_| InvalidGameCursorError)?;
+
+        Ok(Self::new(finished_at, id))
+    }
+}
+
+/// Slice of a list of games, the latest first
+pub struct GamePage {
+    games: Vec<Game>,
+    has_next_page: bool,
+}
+
+impl GamePage {
+    pub fn new(games: Vec<Game>, has_next_page: bool) -> Self {
+        Self {
+            games,
+            has_next_page,
+        }
+    }
+
+    pub fn has_next_page(&self) -> &bool {
+        &self.has_next_page
+    }
+
+    pub fn into_games(self) -> Vec<Game> {
+        self.games
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_game_cursor_round_trip() {
+        let cursor = GameCursor::new(
+            Utc.with_ymd_and_hms(2024, 11, 20, 12, 0, 0).unwrap(),
+            uuid::Uuid::from_u128(42),
+        );
+
+        assert_eq!(cursor.to_string().parse::<GameCursor>(), Ok(cursor));
+    }
+
+    #[test]
+    fn test_invalid_game_cursor() {
+        assert_eq!("".parse::<GameCursor>(), Err(InvalidGameCursorError));
+        assert_eq!(
+            "1732104000000000".parse::<GameCursor>(),
+            Err(InvalidGameCursorError)
+        );
+        assert_eq!(
+            "abc_00000000-0000-0000-0000-00000000002a".parse::<GameCursor>(),
+            Err(InvalidGameCursorError)
+        );
+        assert_eq!(
+            "1732104000000000_not-a-uuid".parse::<GameCursor>(),
+            Err(InvalidGameCursorError)
+        );
+    }
+}
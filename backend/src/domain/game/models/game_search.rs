@@ -0,0 +1,89 @@
+use crate::domain::{
+    game::models::{fen::Fen, game::Color, game_filter::GameFilter},
+    platform::models::PlatformName,
+};
+
+/// Result of a game from the point of view of the searched player
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// Position the games pass through, optionally left with a given move
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionFilter {
+    fen: Fen,
+    next_move_uci: Option<String>,
+}
+
+impl PositionFilter {
+    pub fn new(fen: Fen, next_move_uci: Option<String>) -> Self {
+        Self { fen, next_move_uci }
+    }
+
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    pub fn next_move_uci(&self) -> Option<&String> {
+        self.next_move_uci.as_ref()
+    }
+}
+
+/// Selects the stored games of a player
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameSearch {
+    username: String,
+    platform_name: PlatformName,
+    /// `None` matches the games with both colors
+    play_as: Option<Color>,
+    result: Option<GameResult>,
+    position: Option<PositionFilter>,
+    filter: GameFilter,
+}
+
+impl GameSearch {
+    pub fn new(
+        username: String,
+        platform_name: PlatformName,
+        play_as: Option<Color>,
+        result: Option<GameResult>,
+        position: Option<PositionFilter>,
+        filter: GameFilter,
+    ) -> Self {
+        Self {
+            username,
+            platform_name,
+            play_as,
+            result,
+            position,
+            filter,
+        }
+    }
+
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    pub fn platform_name(&self) -> &PlatformName {
+        &self.platform_name
+    }
+
+    pub fn play_as(&self) -> Option<&Color> {
+        self.play_as.as_ref()
+    }
+
+    pub fn result(&self) -> Option<&GameResult> {
+        self.result.as_ref()
+    }
+
+    pub fn position(&self) -> Option<&PositionFilter> {
+        self.position.as_ref()
+    }
+
+    pub fn filter(&self) -> &GameFilter {
+        &self.filter
+    }
+}
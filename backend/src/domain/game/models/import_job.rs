@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use strum_macros::{EnumString, IntoStaticStr};
+
+use crate::domain::platform::models::{ArchiveMonth, PlatformName};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString, IntoStaticStr)]
+pub enum ImportJobStatus {
+    /// Created, but no worker picked the job up yet
+    Pending,
+    Running,
+    Done,
+    Failed,
+    /// Stopped on request, the archives committed until then are kept
+    Cancelled,
+}
+
+/// Persisted import of the games of a player from a platform
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportJob {
+    id: uuid::Uuid,
+    username: String,
+    platform_name: PlatformName,
+    status: ImportJobStatus,
+    /// downloads the whole history instead of the games since the last synced archive
+    full_resync: bool,
+    /// start of the last archive synced for the player when the job was created
+    from_timestamp: Option<DateTime<Utc>>,
+    /// month of the last archive whose games are committed
+    last_archive: Option<ArchiveMonth>,
+    games_inserted: usize,
+    error: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl ImportJob {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: uuid::Uuid,
+        username: String,
+        platform_name: PlatformName,
+        status: ImportJobStatus,
+        full_resync: bool,
+        from_timestamp: Option<DateTime<Utc>>,
+        last_archive: Option<ArchiveMonth>,
+        games_inserted: usize,
+        error: Option<String>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+        finished_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            id,
+            username,
+            platform_name,
+            status,
+            full_resync,
+            from_timestamp,
+            last_archive,
+            games_inserted,
+            error,
+            created_at,
+            updated_at,
+            finished_at,
+        }
+    }
+
+    pub fn id(&self) -> &uuid::Uuid {
+        &self.id
+    }
+
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    pub fn platform_name(&self) -> &PlatformName {
+        &self.platform_name
+    }
+
+    pub fn status(&self) -> &ImportJobStatus {
+        &self.status
+    }
+
+    pub fn full_resync(&self) -> &bool {
+        &self.full_resync
+    }
+
+    pub fn from_timestamp(&self) -> Option<&DateTime<Utc>> {
+        self.from_timestamp.as_ref()
+    }
+
+    pub fn last_archive(&self) -> Option<&ArchiveMonth> {
+        self.last_archive.as_ref()
+    }
+
+    pub fn games_inserted(&self) -> &usize {
+        &self.games_inserted
+    }
+
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+
+    pub fn finished_at(&self) -> Option<&DateTime<Utc>> {
+        self.finished_at.as_ref()
+    }
+
+    /// Timestamp to download the games from. Imports continue at the start of the last
+    /// archive synced for the player, or of the last archive committed by the job if it was
+    /// interrupted, since the games of these months may be incomplete
+    pub fn resume_timestamp_seconds(&self) -> Option<u64> {
+        let from_month = self
+            .from_timestamp
+            .map(ArchiveMonth::from)
+            .max(self.last_archive)?;
+
+        from_month
+            .start()
+            .map(|timestamp| timestamp.timestamp().max(0) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn job(from_timestamp: Option<DateTime<Utc>>, last_archive: Option<ArchiveMonth>) -> ImportJob {
+        ImportJob::new(
+            uuid::Uuid::nil(),
+            "user".to_string(),
+            PlatformName::ChessCom,
+            ImportJobStatus::Running,
+            false,
+            from_timestamp,
+            last_archive,
+            0,
+            None,
+            DateTime::UNIX_EPOCH,
+            DateTime::UNIX_EPOCH,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_resume_timestamp_seconds() {
+        let from_timestamp = Utc.with_ymd_and_hms(2024, 11, 20, 12, 0, 0).unwrap();
+        let month_start = |year, month| {
+            Some(
+                Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0)
+                    .unwrap()
+                    .timestamp() as u64,
+            )
+        };
+
+        assert_eq!(job(None, None).resume_timestamp_seconds(), None);
+        // The month the job starts at is downloaded again
+        assert_eq!(
+            job(Some(from_timestamp), None).resume_timestamp_seconds(),
+            month_start(2024, 11)
+        );
+        assert_eq!(
+            job(Some(from_timestamp), Some(ArchiveMonth::new(2024, 11))).resume_timestamp_seconds(),
+            month_start(2024, 11)
+        );
+        assert_eq!(
+            job(Some(from_timestamp), Some(ArchiveMonth::new(2025, 2))).resume_timestamp_seconds(),
+            month_start(2025, 2)
+        );
+        assert_eq!(
+            job(None, Some(ArchiveMonth::new(2013, 5))).resume_timestamp_seconds(),
+            month_start(2013, 5)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use crate::domain::{game::models::store_progress::StoreProgress, platform::models::ArchiveMonth};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportPhase {
+    /// Looking up the archives that have to be downloaded
+    ResolvingArchives,
+    /// Waiting for the next archive from the platform
+    Downloading,
+    /// Storing the games of an archive and indexing their positions
+    Indexing,
+    Done,
+    Failed,
+    /// Stopped on request, the games stored until then are kept
+    Cancelled,
+}
+
+/// State of an import of a player's games from a platform
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportProgress {
+    phase: ImportPhase,
+    archives_done: usize,
+    archives_total: usize,
+    games_fetched: usize,
+    games_inserted: usize,
+    /// stored games whose details were refreshed by a full resync
+    games_updated: usize,
+    /// fetched games that were already stored
+    duplicates_skipped: usize,
+    /// inserted games of non-standard variants, which have no positions indexed
+    variants_skipped: usize,
+    /// inserted games whose positions could not be indexed
+    failures: usize,
+    current_archive: Option<ArchiveMonth>,
+    /// reason of the failure once the phase is `Failed`
+    error: Option<String>,
+}
+
+impl Default for ImportProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportProgress {
+    pub fn new() -> Self {
+        Self {
+            phase: ImportPhase::ResolvingArchives,
+            archives_done: 0,
+            archives_total: 0,
+            games_fetched: 0,
+            games_inserted: 0,
+            games_updated: 0,
+            duplicates_skipped: 0,
+            variants_skipped: 0,
+            failures: 0,
+            current_archive: None,
+            error: None,
+        }
+    }
+
+    pub fn start_download(&mut self, archives_total: usize) {
+        self.phase = ImportPhase::Downloading;
+        self.archives_total = archives_total;
+    }
+
+    pub fn apply(&mut self, store_progress: &StoreProgress) {
+        match store_progress {
+            StoreProgress::BatchReceived {
+                archive_month,
+                fetched_amount,
+            } => {
+                self.phase = ImportPhase::Indexing;
+                self.current_archive = *archive_month;
+                self.games_fetched += fetched_amount;
+            }
+            StoreProgress::BatchStored(stored_batch) => {
+                self.phase = ImportPhase::Downloading;
+                self.archives_done += 1;
+                // Platform estimates of the archive count can be too low
+                self.archives_total = self.archives_total.max(self.archives_done);
+                self.games_inserted += stored_batch.inserted_amount();
+                self.games_updated += stored_batch.updated_amount();
+                self.variants_skipped += stored_batch.skipped_amount();
+                self.failures += stored_batch.failed_amount();
+                self.duplicates_skipped =
+                    self.games_fetched - self.games_inserted - self.games_updated;
+            }
+        }
+    }
+
+    pub fn finish(&mut self) {
+        self.phase = ImportPhase::Done;
+        self.archives_done = self.archives_total;
+        self.current_archive = None;
+    }
+
+    pub fn fail(&mut self, error: String) {
+        self.phase = ImportPhase::Failed;
+        self.error = Some(error);
+    }
+
+    pub fn cancel(&mut self) {
+        self.phase = ImportPhase::Cancelled;
+        self.current_archive = None;
+    }
+
+    /// `Done`, `Failed` and `Cancelled` are the last state of an import
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.phase,
+            ImportPhase::Done | ImportPhase::Failed | ImportPhase::Cancelled
+        )
+    }
+
+    /// Fraction of the archives that are stored, between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        match self.phase {
+            ImportPhase::Done => 1.0,
+            _ => self.archives_done as f64 / (self.archives_total as f64).max(1.0),
+        }
+    }
+
+    pub fn phase(&self) -> &ImportPhase {
+        &self.phase
+    }
+
+    pub fn archives_done(&self) -> &usize {
+        &self.archives_done
+    }
+
+    pub fn archives_total(&self) -> &usize {
+        &self.archives_total
+    }
+
+    pub fn games_fetched(&self) -> &usize {
+        &self.games_fetched
+    }
+
+    pub fn games_inserted(&self) -> &usize {
+        &self.games_inserted
+    }
+
+    pub fn games_updated(&self) -> &usize {
+        &self.games_updated
+    }
+
+    pub fn duplicates_skipped(&self) -> &usize {
+        &self.duplicates_skipped
+    }
+
+    pub fn variants_skipped(&self) -> &usize {
+        &self.variants_skipped
+    }
+
+    pub fn failures(&self) -> &usize {
+        &self.failures
+    }
+
+    pub fn current_archive(&self) -> Option<&ArchiveMonth> {
+        self.current_archive.as_ref()
+    }
+
+    pub fn error(&self) -> Option<&String> {
+        self.error.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::game::models::stored_batch::StoredBatch;
+
+    #[test]
+    fn test_apply_store_progress() {
+        let mut progress = ImportProgress::new();
+        progress.start_download(2);
+
+        progress.apply(&StoreProgress::BatchReceived {
+            archive_month: Some(ArchiveMonth::new(2024, 5)),
+            fetched_amount: 10,
+        });
+        assert_eq!(*progress.phase(), ImportPhase::Indexing);
+        assert_eq!(
+            progress.current_archive(),
+            Some(&ArchiveMonth::new(2024, 5))
+        );
+
+        progress.apply(&StoreProgress::BatchStored(StoredBatch::new(7, 1, 1, 2)));
+        assert_eq!(*progress.phase(), ImportPhase::Downloading);
+        assert_eq!(*progress.games_fetched(), 10);
+        assert_eq!(*progress.games_inserted(), 7);
+        assert_eq!(*progress.games_updated(), 1);
+        assert_eq!(*progress.duplicates_skipped(), 2);
+        assert_eq!(*progress.variants_skipped(), 1);
+        assert_eq!(*progress.failures(), 2);
+        assert_eq!(progress.fraction(), 0.5);
+
+        progress.finish();
+        assert!(progress.is_terminal());
+        assert_eq!(progress.fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut progress = ImportProgress::new();
+        progress.start_download(4);
+        progress.apply(&StoreProgress::BatchReceived {
+            archive_month: Some(ArchiveMonth::new(2024, 5)),
+            fetched_amount: 10,
+        });
+        progress.apply(&StoreProgress::BatchStored(StoredBatch::new(10, 0, 0, 0)));
+
+        progress.cancel();
+
+        assert!(progress.is_terminal());
+        assert_eq!(*progress.phase(), ImportPhase::Cancelled);
+        assert_eq!(progress.fraction(), 0.25);
+        assert_eq!(progress.error(), None);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::domain::{
+    game::models::{game::Color, move_stat::MoveStat},
+    platform::models::PlatformName,
+};
+
+/// Player whose moves are compared, with the color the moves are played with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlayerRef {
+    username: String,
+    platform_name: PlatformName,
+    play_as: Color,
+}
+
+impl PlayerRef {
+    pub fn new(username: String, platform_name: PlatformName, play_as: Color) -> Self {
+        Self {
+            username,
+            platform_name,
+            play_as,
+        }
+    }
+
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    pub fn platform_name(&self) -> &PlatformName {
+        &self.platform_name
+    }
+
+    pub fn play_as(&self) -> &Color {
+        &self.play_as
+    }
+}
+
+/// Statistics of one move for each of the compared players
+#[derive(Clone)]
+pub struct MoveComparison {
+    move_uci: String,
+    /// one entry per compared player in the order of the players, `None` if the player
+    /// never played the move
+    stats: Vec<Option<MoveStat>>,
+}
+
+impl MoveComparison {
+    pub fn new(move_uci: String, stats: Vec<Option<MoveStat>>) -> Self {
+        Self { move_uci, stats }
+    }
+
+    /// Arranges the move statistics of each player into one row per move, the move played
+    /// the most by all players together first
+    pub fn from_player_stats(
+        player_count: usize,
+        player_stats: Vec<(usize, MoveStat)>,
+    ) -> Vec<Self> {
+        let mut comparisons: HashMap<String, Self> = HashMap::new();
+        for (player_idx, move_stat) in player_stats {
+            if player_idx >= player_count {
+                continue;
+            }
+            let comparison = comparisons
+                .entry(move_stat.move_uci().to_string())
+                .or_insert_with(|| {
+                    Self::new(move_stat.move_uci().to_string(), vec![None; player_count])
+                });
+            comparison.stats[player_idx] = Some(move_stat);
+        }
+
+        let mut comparisons = comparisons.into_values().collect::<Vec<_>>();
+        comparisons.sort_by(|a, b| {
+            b.total()
+                .cmp(&a.total())
+                .then_with(|| a.move_uci.cmp(&b.move_uci))
+        });
+        comparisons
+    }
+
+    pub fn move_uci(&self) -> &String {
+        &self.move_uci
+    }
+
+    pub fn stats(&self) -> &[Option<MoveStat>] {
+        &self.stats
+    }
+
+    /// Amount of games of all players with the move
+    pub fn total(&self) -> u64 {
+        self.stats
+            .iter()
+            .flatten()
+            .map(|move_stat| *move_stat.total())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn move_stat(move_uci: &str, total: u64) -> MoveStat {
+        MoveStat::new(
+            move_uci.to_string(),
+            total,
+            0,
+            0,
+            total,
+            0.0,
+            1500,
+            1500,
+            1100,
+            DateTime::UNIX_EPOCH,
+            DateTime::UNIX_EPOCH,
+            0,
+            0,
+            1500,
+            1500,
+        )
+    }
+
+    #[test]
+    fn test_from_player_stats() {
+        let comparisons = MoveComparison::from_player_stats(
+            3,
+            vec![
+                (0, move_stat("e2e4", 10)),
+                (2, move_stat("e2e4", 1)),
+                (1, move_stat("d2d4", 20)),
+                (0, move_stat("c2c4", 2)),
+                (2, move_stat("g1f3", 2)),
+            ],
+        );
+
+        let rows = comparisons
+            .iter()
+            .map(|comparison| {
+                (
+                    comparison.move_uci().as_str(),
+                    comparison
+                        .stats()
+                        .iter()
+                        .map(|stat| stat.as_ref().map(|stat| *stat.total()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            rows,
+            vec![
+                ("d2d4", vec![None, Some(20), None]),
+                ("e2e4", vec![Some(10), None, Some(1)]),
+                ("c2c4", vec![Some(2), None, None]),
+                ("g1f3", vec![None, None, Some(2)]),
+            ]
+        );
+    }
+}
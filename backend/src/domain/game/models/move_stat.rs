@@ -1,33 +1,77 @@
 use chrono::{DateTime, Utc};
 
+use crate::domain::game::models::opening::Opening;
+
+#[derive(Clone)]
 pub struct MoveStat {
     move_uci: String,
     total: u64,
     wins: u64,
     draws: u64,
+    losses: u64,
+    /// percentage of the points scored, counting draws as half a point
+    score: f64,
     avg_opponent_elo: u16,
+    avg_own_elo: u16,
+    /// average of the known opponent ratings shifted by 400 for every win and -400 for
+    /// every loss, 0 if no opponent rating is known
+    performance_rating: u16,
+    first_played_at: DateTime<Utc>,
     last_played_at: DateTime<Utc>,
+    white_wins: u64,
+    black_wins: u64,
+    /// average of the known ratings of the players with white, 0 if none is known
+    avg_white_elo: u16,
+    avg_black_elo: u16,
+    /// opening of the position after the move, or of the position the move is played in
+    /// if the move leaves the opening book
+    opening: Option<Opening>,
 }
 
 impl MoveStat {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         move_uci: String,
         total: u64,
         wins: u64,
         draws: u64,
+        losses: u64,
+        score: f64,
         avg_opponent_elo: u16,
+        avg_own_elo: u16,
+        performance_rating: u16,
+        first_played_at: DateTime<Utc>,
         last_played_at: DateTime<Utc>,
+        white_wins: u64,
+        black_wins: u64,
+        avg_white_elo: u16,
+        avg_black_elo: u16,
     ) -> Self {
         Self {
             move_uci,
             total,
             wins,
             draws,
+            losses,
+            score,
             avg_opponent_elo,
+            avg_own_elo,
+            performance_rating,
+            first_played_at,
             last_played_at,
+            white_wins,
+            black_wins,
+            avg_white_elo,
+            avg_black_elo,
+            opening: None,
         }
     }
 
+    pub fn with_opening(mut self, opening: Option<Opening>) -> Self {
+        self.opening = opening;
+        self
+    }
+
     pub fn move_uci(&self) -> &str {
         &self.move_uci
     }
@@ -44,11 +88,51 @@ impl MoveStat {
         &self.draws
     }
 
+    pub fn losses(&self) -> &u64 {
+        &self.losses
+    }
+
+    pub fn score(&self) -> &f64 {
+        &self.score
+    }
+
     pub fn avg_opponent_elo(&self) -> &u16 {
         &self.avg_opponent_elo
     }
 
+    pub fn avg_own_elo(&self) -> &u16 {
+        &self.avg_own_elo
+    }
+
+    pub fn performance_rating(&self) -> &u16 {
+        &self.performance_rating
+    }
+
+    pub fn first_played_at(&self) -> &DateTime<Utc> {
+        &self.first_played_at
+    }
+
     pub fn last_played_at(&self) -> &DateTime<Utc> {
         &self.last_played_at
     }
+
+    pub fn white_wins(&self) -> &u64 {
+        &self.white_wins
+    }
+
+    pub fn black_wins(&self) -> &u64 {
+        &self.black_wins
+    }
+
+    pub fn avg_white_elo(&self) -> &u16 {
+        &self.avg_white_elo
+    }
+
+    pub fn avg_black_elo(&self) -> &u16 {
+        &self.avg_black_elo
+    }
+
+    pub fn opening(&self) -> Option<&Opening> {
+        self.opening.as_ref()
+    }
 }
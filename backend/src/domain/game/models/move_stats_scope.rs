@@ -0,0 +1,11 @@
+use crate::domain::game::models::game::Color;
+
+/// Games the move statistics are computed from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MoveStatsScope {
+    /// Games of one player with the given color. Wins count for the player
+    Player { username: String, play_as: Color },
+    /// Every stored game of the platform, or only the games with at least one of the
+    /// listed players. Wins count for the side to move
+    AllGames { usernames: Option<Vec<String>> },
+}
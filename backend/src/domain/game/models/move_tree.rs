@@ -0,0 +1,49 @@
+use crate::domain::game::models::{fen::Fen, move_stat::MoveStat};
+
+/// Position of an opening tree with the moves played from it, the most played first
+#[derive(Clone)]
+pub struct MoveTree {
+    fen: Fen,
+    branches: Vec<MoveTreeBranch>,
+}
+
+impl MoveTree {
+    pub fn new(fen: Fen, branches: Vec<MoveTreeBranch>) -> Self {
+        Self { fen, branches }
+    }
+
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    pub fn branches(&self) -> &[MoveTreeBranch] {
+        &self.branches
+    }
+}
+
+/// Move of an opening tree with its statistics and the position it leads to
+#[derive(Clone)]
+pub struct MoveTreeBranch {
+    san: String,
+    stat: MoveStat,
+    /// position after the move, without branches at the maximum depth
+    tree: MoveTree,
+}
+
+impl MoveTreeBranch {
+    pub fn new(san: String, stat: MoveStat, tree: MoveTree) -> Self {
+        Self { san, stat, tree }
+    }
+
+    pub fn san(&self) -> &String {
+        &self.san
+    }
+
+    pub fn stat(&self) -> &MoveStat {
+        &self.stat
+    }
+
+    pub fn tree(&self) -> &MoveTree {
+        &self.tree
+    }
+}
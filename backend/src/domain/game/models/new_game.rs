@@ -1,6 +1,9 @@
 use chrono::{DateTime, Utc};
 
-use crate::domain::{game::models::game::Color, platform::models::PlatformName};
+use crate::domain::{
+    game::models::game::{Color, TimeClass, Variant},
+    platform::models::PlatformName,
+};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct NewGame {
@@ -12,9 +15,17 @@ pub struct NewGame {
     platform_name: PlatformName,
     pgn: String,
     finished_at: DateTime<Utc>,
+    time_class: Option<TimeClass>,
+    time_control: Option<String>,
+    rated: bool,
+    variant: Variant,
+    /// tells apart games of the same players that finished at the same time, for sources
+    /// that only record the date of a game
+    source_id: Option<String>,
 }
 
 impl NewGame {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         white: String,
         white_elo: i16,
@@ -24,16 +35,26 @@ impl NewGame {
         platform_name: PlatformName,
         pgn: String,
         finished_at: DateTime<Utc>,
+        time_class: Option<TimeClass>,
+        time_control: Option<String>,
+        rated: bool,
+        variant: Variant,
+        source_id: Option<String>,
     ) -> Self {
         Self {
-            white: white,
-            white_elo: white_elo,
-            black: black,
-            black_elo: black_elo,
-            winner: winner,
-            platform_name: platform_name,
-            pgn: pgn,
-            finished_at: finished_at,
+            white,
+            white_elo,
+            black,
+            black_elo,
+            winner,
+            platform_name,
+            pgn,
+            finished_at,
+            time_class,
+            time_control,
+            rated,
+            variant,
+            source_id,
         }
     }
 
@@ -68,4 +89,24 @@ impl NewGame {
     pub fn finished_at(&self) -> &DateTime<Utc> {
         &self.finished_at
     }
+
+    pub fn time_class(&self) -> Option<&TimeClass> {
+        self.time_class.as_ref()
+    }
+
+    pub fn time_control(&self) -> Option<&String> {
+        self.time_control.as_ref()
+    }
+
+    pub fn rated(&self) -> &bool {
+        &self.rated
+    }
+
+    pub fn variant(&self) -> &Variant {
+        &self.variant
+    }
+
+    pub fn source_id(&self) -> Option<&String> {
+        self.source_id.as_ref()
+    }
 }
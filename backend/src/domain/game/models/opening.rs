@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+/// Lines of the opening book as tab separated `eco`, `name` and `pgn` columns
+const OPENING_BOOK_TSV: &str = include_str!("openings.tsv");
+
+/// Classification of a position by its ECO code and opening name
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opening {
+    /// Encyclopaedia of Chess Openings code, e.g. `B90`
+    eco: String,
+    name: String,
+}
+
+impl Opening {
+    pub fn new(eco: String, name: String) -> Self {
+        Self { eco, name }
+    }
+
+    pub fn eco(&self) -> &String {
+        &self.eco
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+}
+
+/// Computes the key of the position reached by SAN moves from the standard starting
+/// position, `None` if the moves can't be played
+pub trait OpeningLineKeyer: Send + Sync + 'static {
+    fn line_key(&self, san_moves: &[&str]) -> Option<i64>;
+}
+
+/// Openings of the embedded book with the SAN moves leading to them
+pub fn book_lines() -> impl Iterator<Item = (Opening, Vec<&'static str>)> {
+    OPENING_BOOK_TSV.lines().skip(1).filter_map(|line| {
+        let mut columns = line.split('\t');
+        let eco = columns.next()?;
+        let name = columns.next()?;
+        let san_moves = columns
+            .next()?
+            .split_whitespace()
+            .filter(|token| !token.ends_with('.'))
+            .collect();
+
+        Some((Opening::new(eco.to_string(), name.to_string()), san_moves))
+    })
+}
+
+/// Openings of the embedded book by the key of the position they lead to
+pub struct OpeningBook {
+    openings: HashMap<i64, Opening>,
+    /// opening of the shortest line of every ECO code
+    eco_openings: HashMap<String, Opening>,
+}
+
+impl OpeningBook {
+    /// Keys the lines of the embedded book. A position reached by several lines keeps the
+    /// opening of the first one
+    pub fn new(keyer: &impl OpeningLineKeyer) -> Self {
+        let mut openings = HashMap::new();
+        let mut eco_lines: HashMap<String, (usize, Opening)> = HashMap::new();
+        for (opening, san_moves) in book_lines() {
+            if let Some(key) = keyer.line_key(&san_moves) {
+                openings.entry(key).or_insert(opening.clone());
+            }
+            match eco_lines.get(opening.eco()) {
+                Some((plies, _)) if *plies <= san_moves.len() => {}
+                _ => {
+                    eco_lines.insert(opening.eco().clone(), (san_moves.len(), opening));
+                }
+            }
+        }
+
+        Self {
+            openings,
+            eco_openings: eco_lines
+                .into_iter()
+                .map(|(eco, (_, opening))| (eco, opening))
+                .collect(),
+        }
+    }
+
+    pub fn opening(&self, key: &i64) -> Option<&Opening> {
+        self.openings.get(key)
+    }
+
+    /// Most general opening of the book with the ECO code, e.g. of the `ECO` tag of a PGN
+    pub fn opening_by_eco(&self, eco: &str) -> Option<&Opening> {
+        self.eco_openings.get(eco.trim())
+    }
+
+    /// Opening of the deepest position of a game found in the book, given the keys of the
+    /// positions of the game in move order
+    pub fn classify<'a>(&self, keys: impl DoubleEndedIterator<Item = &'a i64>) -> Option<&Opening> {
+        keys.rev().find_map(|key| self.opening(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    /// Keys lines by their moves, so transpositions get different keys
+    struct MovesKeyer;
+
+    impl OpeningLineKeyer for MovesKeyer {
+        fn line_key(&self, san_moves: &[&str]) -> Option<i64> {
+            let mut hasher = DefaultHasher::new();
+            san_moves.hash(&mut hasher);
+            Some(hasher.finish() as i64)
+        }
+    }
+
+    #[test]
+    fn test_book_lines() {
+        let lines = book_lines().collect::<Vec<_>>();
+
+        assert!(!lines.is_empty());
+        for (opening, san_moves) in lines {
+            let mut eco = opening.eco().chars();
+            assert!(matches!(eco.next(), Some('A'..='E')), "{:?}", opening);
+            assert!(eco.all(|c| c.is_ascii_digit()), "{:?}", opening);
+            assert!(!opening.name().is_empty());
+            assert!(!san_moves.is_empty(), "{:?}", opening);
+        }
+    }
+
+    #[test]
+    fn test_book_covers_every_eco_code() {
+        let ecos = book_lines()
+            .map(|(opening, _)| opening.eco().clone())
+            .collect::<std::collections::HashSet<_>>();
+
+        for volume in 'A'..='E' {
+            for code in 0..100 {
+                let eco = format!("{volume}{code:02}");
+                assert!(ecos.contains(&eco), "{eco}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_opening_by_eco() {
+        let book = OpeningBook::new(&MovesKeyer);
+
+        assert_eq!(
+            book.opening_by_eco("B90"),
+            Some(&Opening::new(
+                "B90".to_string(),
+                "Sicilian Defense: Najdorf Variation".to_string()
+            ))
+        );
+        assert_eq!(book.opening_by_eco("F00"), None);
+    }
+
+    #[test]
+    fn test_classify_deepest_position() {
+        let book = OpeningBook::new(&MovesKeyer);
+        let keys = [
+            vec!["e4"],
+            vec!["e4", "c5"],
+            vec!["e4", "c5", "Nf3"],
+            vec!["e4", "c5", "Nf3", "d6"],
+            vec!["e4", "c5", "Nf3", "d6", "h3"],
+        ]
+        .iter()
+        .map(|san_moves| MovesKeyer.line_key(san_moves).unwrap())
+        .collect::<Vec<_>>();
+
+        let opening = book.classify(keys.iter());
+
+        assert_eq!(
+            opening,
+            Some(&Opening::new(
+                "B50".to_string(),
+                "Sicilian Defense: Modern Variations".to_string()
+            ))
+        );
+        assert_eq!(book.classify(keys[..0].iter()), None);
+    }
+}
@@ -0,0 +1,94 @@
+use chrono::{DateTime, Utc};
+use strum_macros::IntoStaticStr;
+
+use crate::domain::game::models::player_profile::ColorRecord;
+
+/// Length of the periods the results of an opening are split into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum TrendPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+/// Results of the games of an opening family in one period
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpeningTrendPoint {
+    period_start: DateTime<Utc>,
+    record: ColorRecord,
+}
+
+impl OpeningTrendPoint {
+    pub fn new(period_start: DateTime<Utc>, record: ColorRecord) -> Self {
+        Self {
+            period_start,
+            record,
+        }
+    }
+
+    pub fn period_start(&self) -> &DateTime<Utc> {
+        &self.period_start
+    }
+
+    pub fn record(&self) -> &ColorRecord {
+        &self.record
+    }
+}
+
+/// Games of a player with one color in the openings of a family, e.g. every variation of
+/// the Sicilian Defense
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpeningFamilyReport {
+    /// opening name without the variation, `None` for the games outside the opening book
+    family: Option<String>,
+    /// ECO codes of the games, sorted
+    ecos: Vec<String>,
+    record: ColorRecord,
+    /// average of the known ratings of the opponents, 0 if none is known
+    avg_opponent_elo: u16,
+    /// results per period, the oldest first
+    trend: Vec<OpeningTrendPoint>,
+}
+
+impl OpeningFamilyReport {
+    pub fn new(
+        family: Option<String>,
+        ecos: Vec<String>,
+        record: ColorRecord,
+        avg_opponent_elo: u16,
+        trend: Vec<OpeningTrendPoint>,
+    ) -> Self {
+        Self {
+            family,
+            ecos,
+            record,
+            avg_opponent_elo,
+            trend,
+        }
+    }
+
+    pub fn family(&self) -> Option<&String> {
+        self.family.as_ref()
+    }
+
+    pub fn ecos(&self) -> &[String] {
+        &self.ecos
+    }
+
+    pub fn record(&self) -> &ColorRecord {
+        &self.record
+    }
+
+    pub fn avg_opponent_elo(&self) -> &u16 {
+        &self.avg_opponent_elo
+    }
+
+    pub fn trend(&self) -> &[OpeningTrendPoint] {
+        &self.trend
+    }
+
+    pub fn push_trend_point(&mut self, trend_point: OpeningTrendPoint) {
+        self.trend.push(trend_point);
+    }
+}
@@ -0,0 +1,42 @@
+pub struct PgnImportSummary {
+    /// amount of games found in the uploaded file
+    parsed_amount: usize,
+    /// amount of games that were not stored before
+    inserted_amount: usize,
+    /// amount of inserted games of non-standard variants, which have no positions indexed
+    skipped_amount: usize,
+    /// amount of inserted games whose positions could not be indexed
+    failed_amount: usize,
+}
+
+impl PgnImportSummary {
+    pub fn new(
+        parsed_amount: usize,
+        inserted_amount: usize,
+        skipped_amount: usize,
+        failed_amount: usize,
+    ) -> Self {
+        Self {
+            parsed_amount,
+            inserted_amount,
+            skipped_amount,
+            failed_amount,
+        }
+    }
+
+    pub fn parsed_amount(&self) -> &usize {
+        &self.parsed_amount
+    }
+
+    pub fn inserted_amount(&self) -> &usize {
+        &self.inserted_amount
+    }
+
+    pub fn skipped_amount(&self) -> &usize {
+        &self.skipped_amount
+    }
+
+    pub fn failed_amount(&self) -> &usize {
+        &self.failed_amount
+    }
+}
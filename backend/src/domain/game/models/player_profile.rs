@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::{game::models::move_stat::MoveStat, platform::models::PlatformName};
+
+/// Results of the games a player played with one color
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ColorRecord {
+    games: u64,
+    wins: u64,
+    draws: u64,
+    losses: u64,
+}
+
+impl ColorRecord {
+    pub fn new(games: u64, wins: u64, draws: u64, losses: u64) -> Self {
+        Self {
+            games,
+            wins,
+            draws,
+            losses,
+        }
+    }
+
+    pub fn games(&self) -> &u64 {
+        &self.games
+    }
+
+    pub fn wins(&self) -> &u64 {
+        &self.wins
+    }
+
+    pub fn draws(&self) -> &u64 {
+        &self.draws
+    }
+
+    pub fn losses(&self) -> &u64 {
+        &self.losses
+    }
+
+    /// Percentage of the points scored, counting draws as half a point, 0 without games
+    pub fn score(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        100.0 * (self.wins as f64 + 0.5 * self.draws as f64) / self.games as f64
+    }
+}
+
+/// Ratings of the player over the stored games. Games without a rating are ignored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RatingSummary {
+    min: u16,
+    max: u16,
+    /// rating in the latest rated game
+    current: u16,
+}
+
+impl RatingSummary {
+    pub fn new(min: u16, max: u16, current: u16) -> Self {
+        Self { min, max, current }
+    }
+
+    pub fn min(&self) -> &u16 {
+        &self.min
+    }
+
+    pub fn max(&self) -> &u16 {
+        &self.max
+    }
+
+    pub fn current(&self) -> &u16 {
+        &self.current
+    }
+}
+
+/// Aggregated statistics of the stored games of a player
+pub struct PlayerProfile {
+    /// username as spelled in the latest game of the player
+    username: String,
+    platform_name: PlatformName,
+    as_white: ColorRecord,
+    as_black: ColorRecord,
+    rating: Option<RatingSummary>,
+    first_game_at: DateTime<Utc>,
+    last_game_at: DateTime<Utc>,
+    /// most played first moves of the player, the most played first
+    first_moves_as_white: Vec<MoveStat>,
+    first_moves_as_black: Vec<MoveStat>,
+}
+
+impl PlayerProfile {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        username: String,
+        platform_name: PlatformName,
+        as_white: ColorRecord,
+        as_black: ColorRecord,
+        rating: Option<RatingSummary>,
+        first_game_at: DateTime<Utc>,
+        last_game_at: DateTime<Utc>,
+        first_moves_as_white: Vec<MoveStat>,
+        first_moves_as_black: Vec<MoveStat>,
+    ) -> Self {
+        Self {
+            username,
+            platform_name,
+            as_white,
+            as_black,
+            rating,
+            first_game_at,
+            last_game_at,
+            first_moves_as_white,
+            first_moves_as_black,
+        }
+    }
+
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    pub fn platform_name(&self) -> &PlatformName {
+        &self.platform_name
+    }
+
+    pub fn total_games(&self) -> u64 {
+        self.as_white.games + self.as_black.games
+    }
+
+    pub fn as_white(&self) -> &ColorRecord {
+        &self.as_white
+    }
+
+    pub fn as_black(&self) -> &ColorRecord {
+        &self.as_black
+    }
+
+    pub fn rating(&self) -> Option<&RatingSummary> {
+        self.rating.as_ref()
+    }
+
+    pub fn first_game_at(&self) -> &DateTime<Utc> {
+        &self.first_game_at
+    }
+
+    pub fn last_game_at(&self) -> &DateTime<Utc> {
+        &self.last_game_at
+    }
+
+    pub fn first_moves_as_white(&self) -> &[MoveStat] {
+        &self.first_moves_as_white
+    }
+
+    pub fn first_moves_as_black(&self) -> &[MoveStat] {
+        &self.first_moves_as_black
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_score() {
+        assert_eq!(ColorRecord::new(4, 2, 1, 1).score(), 62.5);
+        assert_eq!(ColorRecord::default().score(), 0.0);
+    }
+}
@@ -1,22 +1,30 @@
-use crate::domain::game::models::fen::Fen;
-
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Position {
-    id: uuid::Uuid,
-    fen: Fen,
+    id: i32,
+    /// transposition-aware key, shared by all FENs that differ only in move counters
+    key: i64,
 }
 
 impl Position {
-    pub fn new(id: uuid::Uuid, fen: Fen) -> Self {
-        Self { id: id, fen: fen }
+    pub fn new(id: i32, key: i64) -> Self {
+        Self { id, key }
+    }
+
+    pub fn id(&self) -> &i32 {
+        &self.id
+    }
+
+    pub fn key(&self) -> &i64 {
+        &self.key
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::domain::game::models::{errors::InvalidFenError, fen::FenValidator};
-
-    use super::*;
+    use crate::domain::game::models::{
+        errors::InvalidFenError,
+        fen::{Fen, FenValidator},
+    };
 
     struct ShakmatyFenValidator;
 
@@ -30,7 +38,7 @@ mod tests {
     fn test_create_fen_success() {
         let fen_str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
-        let expected = Ok(Fen::new_unchecked(fen_str.into()));
+        let expected = Ok(Fen::new_unchecked(fen_str));
 
         let actual = Fen::new(fen_str, &ShakmatyFenValidator);
 
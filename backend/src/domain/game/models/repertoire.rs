@@ -0,0 +1,211 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::game::models::{fen::Fen, game::Color, player_profile::ColorRecord};
+
+/// Move chosen in a position of a repertoire
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RepertoireMove {
+    fen: Fen,
+    /// transposition-aware key of the position
+    position_key: i64,
+    uci: String,
+    san: String,
+}
+
+impl RepertoireMove {
+    pub fn new(fen: Fen, position_key: i64, uci: String, san: String) -> Self {
+        Self {
+            fen,
+            position_key,
+            uci,
+            san,
+        }
+    }
+
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    pub fn position_key(&self) -> &i64 {
+        &self.position_key
+    }
+
+    pub fn uci(&self) -> &String {
+        &self.uci
+    }
+
+    pub fn san(&self) -> &String {
+        &self.san
+    }
+}
+
+/// Tree of the moves a player prepared with one color. The moves of the player are the
+/// ones they intend to play, the moves of the opponent the replies they prepared for
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Repertoire {
+    id: uuid::Uuid,
+    name: String,
+    color: Color,
+    /// moves in the order of the PGN they were read from, the mainline move of a position
+    /// before its alternatives
+    moves: Vec<RepertoireMove>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl Repertoire {
+    pub fn new(
+        id: uuid::Uuid,
+        name: String,
+        color: Color,
+        moves: Vec<RepertoireMove>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            color,
+            moves,
+            created_at,
+            updated_at,
+        }
+    }
+
+    pub fn id(&self) -> &uuid::Uuid {
+        &self.id
+    }
+
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    pub fn moves(&self) -> &[RepertoireMove] {
+        &self.moves
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn updated_at(&self) -> &DateTime<Utc> {
+        &self.updated_at
+    }
+}
+
+/// Side of a game that played the first move outside of the repertoire
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Deviator {
+    Player,
+    Opponent,
+}
+
+/// Games that left the repertoire with the same move in the same position
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepertoireDeviation {
+    fen: Fen,
+    /// half-move number of the deviating move, the lowest one for transpositions
+    ply: u16,
+    deviator: Deviator,
+    played_uci: String,
+    played_san: String,
+    /// SAN of the moves of the repertoire in the position
+    expected_sans: Vec<String>,
+    /// results of the games from the side of the player
+    record: ColorRecord,
+    last_played_at: DateTime<Utc>,
+}
+
+impl RepertoireDeviation {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        fen: Fen,
+        ply: u16,
+        deviator: Deviator,
+        played_uci: String,
+        played_san: String,
+        expected_sans: Vec<String>,
+        record: ColorRecord,
+        last_played_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            fen,
+            ply,
+            deviator,
+            played_uci,
+            played_san,
+            expected_sans,
+            record,
+            last_played_at,
+        }
+    }
+
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    pub fn ply(&self) -> &u16 {
+        &self.ply
+    }
+
+    pub fn deviator(&self) -> &Deviator {
+        &self.deviator
+    }
+
+    pub fn played_uci(&self) -> &String {
+        &self.played_uci
+    }
+
+    pub fn played_san(&self) -> &String {
+        &self.played_san
+    }
+
+    pub fn expected_sans(&self) -> &[String] {
+        &self.expected_sans
+    }
+
+    pub fn record(&self) -> &ColorRecord {
+        &self.record
+    }
+
+    pub fn last_played_at(&self) -> &DateTime<Utc> {
+        &self.last_played_at
+    }
+}
+
+/// Where the games of a player with the color of a repertoire left it
+#[derive(Clone, Debug, PartialEq)]
+pub struct RepertoireDeviationReport {
+    /// games of the player with the color of the repertoire
+    games: u64,
+    /// games that stayed in the repertoire until it or the game ended
+    followed_games: u64,
+    /// deviations of the other games, the most frequent first
+    deviations: Vec<RepertoireDeviation>,
+}
+
+impl RepertoireDeviationReport {
+    pub fn new(games: u64, followed_games: u64, deviations: Vec<RepertoireDeviation>) -> Self {
+        Self {
+            games,
+            followed_games,
+            deviations,
+        }
+    }
+
+    pub fn games(&self) -> &u64 {
+        &self.games
+    }
+
+    pub fn followed_games(&self) -> &u64 {
+        &self.followed_games
+    }
+
+    pub fn deviations(&self) -> &[RepertoireDeviation] {
+        &self.deviations
+    }
+}
@@ -0,0 +1,12 @@
+use crate::domain::{game::models::stored_batch::StoredBatch, platform::models::ArchiveMonth};
+
+/// Progress reported by the repository while storing the games received from a platform
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StoreProgress {
+    /// A batch was received and its games are being stored and indexed
+    BatchReceived {
+        archive_month: Option<ArchiveMonth>,
+        fetched_amount: usize,
+    },
+    BatchStored(StoredBatch),
+}
@@ -0,0 +1,44 @@
+/// Outcome of storing one batch of games received from a platform
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StoredBatch {
+    /// amount of games that were not stored before
+    inserted_amount: usize,
+    /// amount of stored games whose details were refreshed by a full resync
+    updated_amount: usize,
+    /// amount of inserted games of non-standard variants, which have no positions indexed
+    skipped_amount: usize,
+    /// amount of inserted games whose positions could not be read from the PGN
+    failed_amount: usize,
+}
+
+impl StoredBatch {
+    pub fn new(
+        inserted_amount: usize,
+        updated_amount: usize,
+        skipped_amount: usize,
+        failed_amount: usize,
+    ) -> Self {
+        Self {
+            inserted_amount,
+            updated_amount,
+            skipped_amount,
+            failed_amount,
+        }
+    }
+
+    pub fn inserted_amount(&self) -> &usize {
+        &self.inserted_amount
+    }
+
+    pub fn updated_amount(&self) -> &usize {
+        &self.updated_amount
+    }
+
+    pub fn skipped_amount(&self) -> &usize {
+        &self.skipped_amount
+    }
+
+    pub fn failed_amount(&self) -> &usize {
+        &self.failed_amount
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+
+use crate::domain::platform::models::{ArchiveMonth, PlatformName};
+
+/// Player whose games are imported from a platform, with the sync cursor of their imports
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackedPlayer {
+    platform_name: PlatformName,
+    /// lowercased username
+    username: String,
+    /// month of the latest archive committed by an import of the player
+    last_synced_archive: Option<ArchiveMonth>,
+    /// end of the last import of the player that finished
+    last_synced_at: Option<DateTime<Utc>>,
+    /// amount of stored games the player took part in, as of the last finished import
+    game_count: usize,
+}
+
+impl TrackedPlayer {
+    pub fn new(
+        platform_name: PlatformName,
+        username: String,
+        last_synced_archive: Option<ArchiveMonth>,
+        last_synced_at: Option<DateTime<Utc>>,
+        game_count: usize,
+    ) -> Self {
+        Self {
+            platform_name,
+            username,
+            last_synced_archive,
+            last_synced_at,
+            game_count,
+        }
+    }
+
+    pub fn platform_name(&self) -> &PlatformName {
+        &self.platform_name
+    }
+
+    pub fn username(&self) -> &String {
+        &self.username
+    }
+
+    pub fn last_synced_archive(&self) -> Option<&ArchiveMonth> {
+        self.last_synced_archive.as_ref()
+    }
+
+    pub fn last_synced_at(&self) -> Option<&DateTime<Utc>> {
+        self.last_synced_at.as_ref()
+    }
+
+    pub fn game_count(&self) -> &usize {
+        &self.game_count
+    }
+}
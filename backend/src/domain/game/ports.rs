@@ -3,25 +3,41 @@ use tokio::sync::mpsc::{Receiver, Sender};
 
 use crate::domain::{
     game::models::{
-        errors::{GameRepositoryError, InvalidFenError, StoreGamesError},
+        elo_filter::EloFilter,
+        errors::{GameRepositoryError, InvalidFenError, SaveRepertoireError, StoreGamesError},
         fen::Fen,
         game::Color,
+        game_detail::GameDetail,
+        game_filter::GameFilter,
+        game_import_error::GameImportError,
+        game_page::{GameCursor, GamePage},
+        game_search::GameSearch,
+        import_job::{ImportJob, ImportJobStatus},
+        move_comparison::{MoveComparison, PlayerRef},
         move_stat::MoveStat,
-        new_game::NewGame,
+        move_stats_scope::MoveStatsScope,
+        move_tree::MoveTree,
+        opening_report::{OpeningFamilyReport, TrendPeriod},
+        pgn_import::PgnImportSummary,
+        player_profile::PlayerProfile,
+        repertoire::{Repertoire, RepertoireDeviationReport, RepertoireMove},
+        store_progress::StoreProgress,
+        tracked_player::TrackedPlayer,
     },
-    platform::models::{PlatformError, PlatformName},
+    platform::models::{GameBatch, PlatformError, PlatformName},
 };
 
 #[async_trait]
 pub trait GameRepository: Send + Sync + 'static {
     async fn store_games(
         &self,
-        platform_name: &PlatformName,
-        username: &str,
-        game_receiver: Receiver<Result<Vec<NewGame>, PlatformError>>,
-        progress_sender: Sender<usize>,
+        import_job: Option<&ImportJob>,
+        game_receiver: Receiver<Result<GameBatch, PlatformError>>,
+        progress_sender: Sender<StoreProgress>,
     ) -> Result<(), GameRepositoryError>;
 
+    /// Timestamp to sync the games of the player from, which is the start of the last
+    /// archive synced by the imports of the player
     async fn get_latest_game_timestamp_seconds(
         &self,
         platform_name: &PlatformName,
@@ -31,39 +47,326 @@ pub trait GameRepository: Send + Sync + 'static {
     async fn get_move_stats(
         &self,
         position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+    ) -> Result<Vec<MoveStat>, GameRepositoryError>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn get_move_tree(
+        &self,
+        position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+        depth: u8,
+        min_games: u64,
+    ) -> Result<MoveTree, GameRepositoryError>;
+
+    async fn get_game_import_errors(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<GameImportError>, GameRepositoryError>;
+
+    /// Creates a pending import of the player, or returns the one that is not finished yet.
+    /// A full resync downloads the whole history of the player again
+    async fn create_import_job(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+        full_resync: bool,
+    ) -> Result<ImportJob, GameRepositoryError>;
+
+    async fn update_import_job_status(
+        &self,
+        import_job_id: &uuid::Uuid,
+        status: &ImportJobStatus,
+        error: Option<&str>,
+    ) -> Result<(), GameRepositoryError>;
+
+    /// Lists the pending imports and the imports that were interrupted by a restart
+    async fn get_unfinished_import_jobs(&self) -> Result<Vec<ImportJob>, GameRepositoryError>;
+
+    async fn get_import_jobs(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<ImportJob>, GameRepositoryError>;
+
+    async fn get_import_job(
+        &self,
+        import_job_id: &uuid::Uuid,
+    ) -> Result<Option<ImportJob>, GameRepositoryError>;
+
+    /// Deletes the games of the player and forgets how far the player was synced.
+    /// Returns the number of deleted games
+    async fn delete_player_games(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<u64, GameRepositoryError>;
+
+    async fn get_tracked_players(
+        &self,
+        platform_name: Option<&PlatformName>,
+    ) -> Result<Vec<TrackedPlayer>, GameRepositoryError>;
+
+    /// Statistics over the stored games of the player, none if no game of the player is stored
+    async fn get_player_profile(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Option<PlayerProfile>, GameRepositoryError>;
+
+    /// Results of the games of the player with the color per opening family
+    async fn get_opening_report(
+        &self,
         username: &str,
+        platform_name: &PlatformName,
         play_as: &Color,
+        filter: &GameFilter,
+        trend_period: &TrendPeriod,
+    ) -> Result<Vec<OpeningFamilyReport>, GameRepositoryError>;
+
+    /// Lists at most `limit` games matching the search that come after the cursor, the
+    /// latest first
+    async fn get_games(
+        &self,
+        search: &GameSearch,
+        after: Option<&GameCursor>,
+        limit: usize,
+    ) -> Result<GamePage, GameRepositoryError>;
+
+    async fn get_game(
+        &self,
+        game_id: &uuid::Uuid,
+    ) -> Result<Option<GameDetail>, GameRepositoryError>;
+
+    async fn compare_move_stats(
+        &self,
+        position_fen: &Fen,
+        players: &[PlayerRef],
+        filter: &GameFilter,
+    ) -> Result<Vec<MoveComparison>, GameRepositoryError>;
+
+    async fn create_repertoire(
+        &self,
+        name: &str,
+        color: &Color,
+        moves: &[RepertoireMove],
+    ) -> Result<Repertoire, GameRepositoryError>;
+
+    /// Changes the given fields of the repertoire, none if there is no repertoire with the id
+    async fn update_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+        name: Option<&str>,
+        color: Option<&Color>,
+        moves: Option<&[RepertoireMove]>,
+    ) -> Result<Option<Repertoire>, GameRepositoryError>;
+
+    /// Deletes the repertoire, false if there is no repertoire with the id
+    async fn delete_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+    ) -> Result<bool, GameRepositoryError>;
+
+    async fn get_repertoires(&self) -> Result<Vec<Repertoire>, GameRepositoryError>;
+
+    async fn get_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+    ) -> Result<Option<Repertoire>, GameRepositoryError>;
+
+    /// Walks the positions of the games of the player with the color of the repertoire
+    /// until they leave it
+    async fn get_repertoire_deviations(
+        &self,
+        repertoire: &Repertoire,
+        username: &str,
         platform_name: &PlatformName,
-        from_timestamp: &Option<chrono::DateTime<chrono::Utc>>,
-        to_timestamp: &Option<chrono::DateTime<chrono::Utc>>,
-    ) -> Result<Vec<MoveStat>, GameRepositoryError>;
+        filter: &GameFilter,
+    ) -> Result<RepertoireDeviationReport, GameRepositoryError>;
 }
 
 #[async_trait]
 pub trait GameService: Send + Sync + 'static {
     async fn store_games(
         &self,
-        platform_name: &PlatformName,
-        username: &str,
-        game_receiver: Receiver<Result<Vec<NewGame>, PlatformError>>,
-        progress_sender: Sender<usize>,
+        import_job: Option<&ImportJob>,
+        game_receiver: Receiver<Result<GameBatch, PlatformError>>,
+        progress_sender: Sender<StoreProgress>,
     ) -> Result<(), StoreGamesError>;
 
+    /// Parses every game of the PGN file and stores them
+    async fn import_pgn(&self, pgn: &str) -> Result<PgnImportSummary, StoreGamesError>;
+
     async fn get_latest_game_timestamp_seconds(
         &self,
         platform_name: &PlatformName,
         username: &str,
     ) -> Result<Option<u64>, GameRepositoryError>;
 
+    /// Statistics of the moves played in the position by a player or in all stored games
     async fn get_move_stats(
         &self,
         position_fen: Fen,
+        scope: MoveStatsScope,
+        platform_name: PlatformName,
+        filter: GameFilter,
+        elo_filter: EloFilter,
+    ) -> Result<Vec<MoveStat>, GameRepositoryError>;
+
+    /// Tree of the lines of up to `depth` moves played from the position, leaving out the
+    /// moves played in fewer than `min_games` games
+    #[allow(clippy::too_many_arguments)]
+    async fn get_move_tree(
+        &self,
+        position_fen: Fen,
+        scope: MoveStatsScope,
+        platform_name: PlatformName,
+        filter: GameFilter,
+        elo_filter: EloFilter,
+        depth: u8,
+        min_games: u64,
+    ) -> Result<MoveTree, GameRepositoryError>;
+
+    /// Lists the games of a player whose positions could not be indexed
+    async fn get_game_import_errors(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Vec<GameImportError>, GameRepositoryError>;
+
+    async fn create_import_job(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+        full_resync: bool,
+    ) -> Result<ImportJob, GameRepositoryError>;
+
+    async fn update_import_job_status(
+        &self,
+        import_job_id: &uuid::Uuid,
+        status: &ImportJobStatus,
+        error: Option<&str>,
+    ) -> Result<(), GameRepositoryError>;
+
+    async fn get_unfinished_import_jobs(&self) -> Result<Vec<ImportJob>, GameRepositoryError>;
+
+    /// Lists the imports of a player, the latest first
+    async fn get_import_jobs(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Vec<ImportJob>, GameRepositoryError>;
+
+    async fn get_import_job(
+        &self,
+        import_job_id: uuid::Uuid,
+    ) -> Result<Option<ImportJob>, GameRepositoryError>;
+
+    /// Deletes the stored games of the player, the next import of the player downloads
+    /// the whole history again. Returns the number of deleted games
+    async fn delete_player_games(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<u64, GameRepositoryError>;
+
+    /// Lists the players that were imported, optionally of a single platform
+    async fn get_tracked_players(
+        &self,
+        platform_name: Option<PlatformName>,
+    ) -> Result<Vec<TrackedPlayer>, GameRepositoryError>;
+
+    /// Totals, results per color, ratings and favourite first moves of the player
+    async fn get_player_profile(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Option<PlayerProfile>, GameRepositoryError>;
+
+    /// Results, average opponent rating and trend of the games of the player with the color
+    /// per opening family, the most played family first
+    async fn get_opening_report(
+        &self,
         username: String,
+        platform_name: PlatformName,
         play_as: Color,
+        filter: GameFilter,
+        trend_period: TrendPeriod,
+    ) -> Result<Vec<OpeningFamilyReport>, GameRepositoryError>;
+
+    /// Pages through the games of a player matching the search, the latest first
+    async fn get_games(
+        &self,
+        search: GameSearch,
+        after: Option<GameCursor>,
+        limit: usize,
+    ) -> Result<GamePage, GameRepositoryError>;
+
+    /// Stored game with its moves replayed from the PGN
+    async fn get_game(
+        &self,
+        game_id: uuid::Uuid,
+    ) -> Result<Option<GameDetail>, GameRepositoryError>;
+
+    /// Move statistics of several players in the same position, one row per move
+    async fn compare_move_stats(
+        &self,
+        position_fen: Fen,
+        players: Vec<PlayerRef>,
+        filter: GameFilter,
+    ) -> Result<Vec<MoveComparison>, GameRepositoryError>;
+
+    /// Stores a repertoire with the moves of the mainlines and variations of the PGN
+    async fn create_repertoire(
+        &self,
+        name: String,
+        color: Color,
+        pgn: Option<String>,
+    ) -> Result<Repertoire, SaveRepertoireError>;
+
+    /// Changes the given fields of the repertoire, a PGN replaces all its moves. None if
+    /// there is no repertoire with the id
+    async fn update_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+        name: Option<String>,
+        color: Option<Color>,
+        pgn: Option<String>,
+    ) -> Result<Option<Repertoire>, SaveRepertoireError>;
+
+    /// Deletes the repertoire, false if there is no repertoire with the id
+    async fn delete_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+    ) -> Result<bool, GameRepositoryError>;
+
+    /// Lists the repertoires by name
+    async fn get_repertoires(&self) -> Result<Vec<Repertoire>, GameRepositoryError>;
+
+    async fn get_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+    ) -> Result<Option<Repertoire>, GameRepositoryError>;
+
+    /// Where the games of the player with the color of the repertoire left it, none if
+    /// there is no repertoire with the id
+    async fn get_repertoire_deviations(
+        &self,
+        repertoire_id: uuid::Uuid,
+        username: String,
         platform_name: PlatformName,
-        from_timestamp: Option<chrono::DateTime<chrono::Utc>>,
-        to_timestamp: Option<chrono::DateTime<chrono::Utc>>,
-    ) -> Result<Vec<MoveStat>, GameRepositoryError>;
+        filter: GameFilter,
+    ) -> Result<Option<RepertoireDeviationReport>, GameRepositoryError>;
+
+    /// Writes the repertoire as a PGN game with variations
+    fn export_repertoire_pgn(&self, repertoire: &Repertoire) -> String;
 
     fn parse_fen(&self, fen_str: String) -> Result<Fen, InvalidFenError>;
 }
@@ -1,63 +1,126 @@
 use async_trait::async_trait;
-use tokio::sync::mpsc::{Receiver, Sender};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
 
 use crate::domain::{
     game::{
         models::{
-            errors::{GameRepositoryError, InvalidFenError, StoreGamesError},
+            elo_filter::EloFilter,
+            errors::{GameRepositoryError, InvalidFenError, SaveRepertoireError, StoreGamesError},
             fen::{Fen, FenValidator},
             game::Color,
+            game_detail::GameDetail,
+            game_filter::GameFilter,
+            game_import_error::GameImportError,
+            game_page::{GameCursor, GamePage},
+            game_search::GameSearch,
+            import_job::{ImportJob, ImportJobStatus},
+            move_comparison::{MoveComparison, PlayerRef},
             move_stat::MoveStat,
-            new_game::NewGame,
+            move_stats_scope::MoveStatsScope,
+            move_tree::MoveTree,
+            opening_report::{OpeningFamilyReport, TrendPeriod},
+            pgn::PgnParser,
+            pgn_import::PgnImportSummary,
+            player_profile::PlayerProfile,
+            repertoire::{Repertoire, RepertoireDeviationReport},
+            store_progress::StoreProgress,
+            tracked_player::TrackedPlayer,
         },
         ports::{GameRepository, GameService},
     },
-    platform::models::{PlatformError, PlatformName},
+    platform::models::{GameBatch, PlatformError, PlatformName},
 };
 
 #[derive(Debug, Clone, Copy)]
-pub struct Service<R, V>
+pub struct Service<R, V, P>
 where
     R: GameRepository,
     V: FenValidator,
+    P: PgnParser,
 {
     repo: R,
     fen_validator: V,
+    pgn_parser: P,
 }
 
-impl<R, V> Service<R, V>
+impl<R, V, P> Service<R, V, P>
 where
     R: GameRepository,
     V: FenValidator,
+    P: PgnParser,
 {
-    pub fn new(repo: R, fen_validator: V) -> Self {
+    pub fn new(repo: R, fen_validator: V, pgn_parser: P) -> Self {
         Self {
-            repo: repo,
-            fen_validator: fen_validator,
+            repo,
+            fen_validator,
+            pgn_parser,
         }
     }
 }
 
 #[async_trait]
-impl<R, V> GameService for Service<R, V>
+impl<R, V, P> GameService for Service<R, V, P>
 where
     R: GameRepository,
     V: FenValidator,
+    P: PgnParser,
 {
     async fn store_games(
         &self,
-        platform_name: &PlatformName,
-        username: &str,
-        game_receiver: Receiver<Result<Vec<NewGame>, PlatformError>>,
-        progress_sender: Sender<usize>,
+        import_job: Option<&ImportJob>,
+        game_receiver: Receiver<Result<GameBatch, PlatformError>>,
+        progress_sender: Sender<StoreProgress>,
     ) -> Result<(), StoreGamesError> {
         self.repo
-            .store_games(platform_name, username, game_receiver, progress_sender)
+            .store_games(import_job, game_receiver, progress_sender)
             .await
             .inspect_err(|err| eprintln!("failed to store games: {}", *err))
             .map_err(|err| err.into())
     }
 
+    async fn import_pgn(&self, pgn: &str) -> Result<PgnImportSummary, StoreGamesError> {
+        let new_games = self
+            .pgn_parser
+            .parse_games(pgn)
+            .inspect_err(|err| eprintln!("failed to parse pgn upload: {}", *err))?;
+        let parsed_amount = new_games.len();
+
+        let (game_sender, game_receiver) = channel(1);
+        let (progress_sender, mut progress_receiver) = channel::<StoreProgress>(1000);
+
+        // The whole upload is already in memory, so it is stored as a single batch
+        game_sender
+            .send(Ok(GameBatch::new(None, new_games)))
+            .await
+            .map_err(|err| GameRepositoryError::Unknown(anyhow::anyhow!(err.to_string())))?;
+        drop(game_sender);
+
+        let count_stored = async {
+            let (mut inserted_amount, mut skipped_amount, mut failed_amount) = (0, 0, 0);
+            while let Some(store_progress) = progress_receiver.recv().await {
+                let StoreProgress::BatchStored(stored_batch) = store_progress else {
+                    continue;
+                };
+                inserted_amount += stored_batch.inserted_amount();
+                skipped_amount += stored_batch.skipped_amount();
+                failed_amount += stored_batch.failed_amount();
+            }
+            (inserted_amount, skipped_amount, failed_amount)
+        };
+        let (store_result, (inserted_amount, skipped_amount, failed_amount)) = tokio::join!(
+            self.store_games(None, game_receiver, progress_sender),
+            count_stored
+        );
+        store_result?;
+
+        Ok(PgnImportSummary::new(
+            parsed_amount,
+            inserted_amount,
+            skipped_amount,
+            failed_amount,
+        ))
+    }
+
     async fn get_latest_game_timestamp_seconds(
         &self,
         platform_name: &PlatformName,
@@ -67,30 +130,285 @@ where
             .get_latest_game_timestamp_seconds(platform_name, username)
             .await
             .inspect_err(|err| eprintln!("failed to get latest game timestamp: {}", *err))
-            .map_err(|err| err.into())
     }
 
     async fn get_move_stats(
         &self,
         position_fen: Fen,
-        username: String,
-        play_as: Color,
+        scope: MoveStatsScope,
         platform_name: PlatformName,
-        from_timestamp_seconds: Option<chrono::DateTime<chrono::Utc>>,
-        to_timestamp_seconds: Option<chrono::DateTime<chrono::Utc>>,
+        filter: GameFilter,
+        elo_filter: EloFilter,
     ) -> Result<Vec<MoveStat>, GameRepositoryError> {
         self.repo
-            .get_move_stats(
+            .get_move_stats(&position_fen, &scope, &platform_name, &filter, &elo_filter)
+            .await
+            .inspect_err(|err| eprintln!("failed to get move stats: {}", *err))
+    }
+
+    async fn get_move_tree(
+        &self,
+        position_fen: Fen,
+        scope: MoveStatsScope,
+        platform_name: PlatformName,
+        filter: GameFilter,
+        elo_filter: EloFilter,
+        depth: u8,
+        min_games: u64,
+    ) -> Result<MoveTree, GameRepositoryError> {
+        self.repo
+            .get_move_tree(
                 &position_fen,
-                &username,
-                &play_as,
+                &scope,
                 &platform_name,
-                &from_timestamp_seconds,
-                &to_timestamp_seconds,
+                &filter,
+                &elo_filter,
+                depth,
+                min_games,
             )
             .await
-            .inspect_err(|err| eprintln!("failed to get move stats: {}", *err))
-            .map_err(|err| err.into())
+            .inspect_err(|err| eprintln!("failed to get move tree: {}", *err))
+    }
+
+    async fn get_game_import_errors(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Vec<GameImportError>, GameRepositoryError> {
+        self.repo
+            .get_game_import_errors(&username, &platform_name)
+            .await
+            .inspect_err(|err| eprintln!("failed to get game import errors: {}", *err))
+    }
+
+    async fn create_import_job(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+        full_resync: bool,
+    ) -> Result<ImportJob, GameRepositoryError> {
+        self.repo
+            .create_import_job(platform_name, username, full_resync)
+            .await
+            .inspect_err(|err| eprintln!("failed to create import job: {}", *err))
+    }
+
+    async fn update_import_job_status(
+        &self,
+        import_job_id: &uuid::Uuid,
+        status: &ImportJobStatus,
+        error: Option<&str>,
+    ) -> Result<(), GameRepositoryError> {
+        self.repo
+            .update_import_job_status(import_job_id, status, error)
+            .await
+            .inspect_err(|err| eprintln!("failed to update import job: {}", *err))
+    }
+
+    async fn get_unfinished_import_jobs(&self) -> Result<Vec<ImportJob>, GameRepositoryError> {
+        self.repo
+            .get_unfinished_import_jobs()
+            .await
+            .inspect_err(|err| eprintln!("failed to get unfinished import jobs: {}", *err))
+    }
+
+    async fn get_import_jobs(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Vec<ImportJob>, GameRepositoryError> {
+        self.repo
+            .get_import_jobs(&username, &platform_name)
+            .await
+            .inspect_err(|err| eprintln!("failed to get import jobs: {}", *err))
+    }
+
+    async fn get_import_job(
+        &self,
+        import_job_id: uuid::Uuid,
+    ) -> Result<Option<ImportJob>, GameRepositoryError> {
+        self.repo
+            .get_import_job(&import_job_id)
+            .await
+            .inspect_err(|err| eprintln!("failed to get import job: {}", *err))
+    }
+
+    async fn delete_player_games(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<u64, GameRepositoryError> {
+        self.repo
+            .delete_player_games(&username, &platform_name)
+            .await
+            .inspect_err(|err| eprintln!("failed to delete games of {}: {}", username, *err))
+    }
+
+    async fn get_tracked_players(
+        &self,
+        platform_name: Option<PlatformName>,
+    ) -> Result<Vec<TrackedPlayer>, GameRepositoryError> {
+        self.repo
+            .get_tracked_players(platform_name.as_ref())
+            .await
+            .inspect_err(|err| eprintln!("failed to get tracked players: {}", *err))
+    }
+
+    async fn get_player_profile(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<Option<PlayerProfile>, GameRepositoryError> {
+        self.repo
+            .get_player_profile(&username, &platform_name)
+            .await
+            .inspect_err(|err| eprintln!("failed to get player profile: {}", *err))
+    }
+
+    async fn get_opening_report(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+        play_as: Color,
+        filter: GameFilter,
+        trend_period: TrendPeriod,
+    ) -> Result<Vec<OpeningFamilyReport>, GameRepositoryError> {
+        self.repo
+            .get_opening_report(&username, &platform_name, &play_as, &filter, &trend_period)
+            .await
+            .inspect_err(|err| eprintln!("failed to get opening report: {}", *err))
+    }
+
+    async fn get_games(
+        &self,
+        search: GameSearch,
+        after: Option<GameCursor>,
+        limit: usize,
+    ) -> Result<GamePage, GameRepositoryError> {
+        self.repo
+            .get_games(&search, after.as_ref(), limit)
+            .await
+            .inspect_err(|err| eprintln!("failed to get games: {}", *err))
+    }
+
+    async fn get_game(
+        &self,
+        game_id: uuid::Uuid,
+    ) -> Result<Option<GameDetail>, GameRepositoryError> {
+        self.repo
+            .get_game(&game_id)
+            .await
+            .inspect_err(|err| eprintln!("failed to get game: {}", *err))
+    }
+
+    async fn compare_move_stats(
+        &self,
+        position_fen: Fen,
+        players: Vec<PlayerRef>,
+        filter: GameFilter,
+    ) -> Result<Vec<MoveComparison>, GameRepositoryError> {
+        self.repo
+            .compare_move_stats(&position_fen, &players, &filter)
+            .await
+            .inspect_err(|err| eprintln!("failed to compare move stats: {}", *err))
+    }
+
+    async fn create_repertoire(
+        &self,
+        name: String,
+        color: Color,
+        pgn: Option<String>,
+    ) -> Result<Repertoire, SaveRepertoireError> {
+        let moves = match pgn {
+            Some(pgn) => self
+                .pgn_parser
+                .parse_repertoire_moves(&pgn)
+                .inspect_err(|err| eprintln!("failed to parse repertoire pgn: {}", *err))?,
+            None => vec![],
+        };
+
+        Ok(self
+            .repo
+            .create_repertoire(&name, &color, &moves)
+            .await
+            .inspect_err(|err| eprintln!("failed to create repertoire: {}", *err))?)
+    }
+
+    async fn update_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+        name: Option<String>,
+        color: Option<Color>,
+        pgn: Option<String>,
+    ) -> Result<Option<Repertoire>, SaveRepertoireError> {
+        let moves = match pgn {
+            Some(pgn) => Some(
+                self.pgn_parser
+                    .parse_repertoire_moves(&pgn)
+                    .inspect_err(|err| eprintln!("failed to parse repertoire pgn: {}", *err))?,
+            ),
+            None => None,
+        };
+
+        Ok(self
+            .repo
+            .update_repertoire(
+                &repertoire_id,
+                name.as_deref(),
+                color.as_ref(),
+                moves.as_deref(),
+            )
+            .await
+            .inspect_err(|err| eprintln!("failed to update repertoire: {}", *err))?)
+    }
+
+    async fn delete_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+    ) -> Result<bool, GameRepositoryError> {
+        self.repo
+            .delete_repertoire(&repertoire_id)
+            .await
+            .inspect_err(|err| eprintln!("failed to delete repertoire: {}", *err))
+    }
+
+    async fn get_repertoires(&self) -> Result<Vec<Repertoire>, GameRepositoryError> {
+        self.repo
+            .get_repertoires()
+            .await
+            .inspect_err(|err| eprintln!("failed to get repertoires: {}", *err))
+    }
+
+    async fn get_repertoire(
+        &self,
+        repertoire_id: uuid::Uuid,
+    ) -> Result<Option<Repertoire>, GameRepositoryError> {
+        self.repo
+            .get_repertoire(&repertoire_id)
+            .await
+            .inspect_err(|err| eprintln!("failed to get repertoire: {}", *err))
+    }
+
+    async fn get_repertoire_deviations(
+        &self,
+        repertoire_id: uuid::Uuid,
+        username: String,
+        platform_name: PlatformName,
+        filter: GameFilter,
+    ) -> Result<Option<RepertoireDeviationReport>, GameRepositoryError> {
+        let Some(repertoire) = self.get_repertoire(repertoire_id).await? else {
+            return Ok(None);
+        };
+
+        self.repo
+            .get_repertoire_deviations(&repertoire, &username, &platform_name, &filter)
+            .await
+            .map(Some)
+            .inspect_err(|err| eprintln!("failed to get repertoire deviations: {}", *err))
+    }
+
+    fn export_repertoire_pgn(&self, repertoire: &Repertoire) -> String {
+        self.pgn_parser.write_repertoire(repertoire)
     }
 
     fn parse_fen(&self, fen_str: String) -> Result<Fen, InvalidFenError> {
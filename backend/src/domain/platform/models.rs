@@ -1,8 +1,83 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use std::fmt;
 use strum_macros::{EnumString, IntoStaticStr, VariantNames};
 
+use crate::domain::game::models::new_game::NewGame;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, EnumString, IntoStaticStr, VariantNames)]
 pub enum PlatformName {
     ChessCom,
+    Lichess,
+    /// Games uploaded as PGN files
+    Pgn,
+}
+
+/// Calendar month the games of a platform archive were played in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ArchiveMonth {
+    year: i32,
+    month: u32,
+}
+
+impl ArchiveMonth {
+    pub fn new(year: i32, month: u32) -> Self {
+        Self { year, month }
+    }
+
+    pub fn year(&self) -> &i32 {
+        &self.year
+    }
+
+    pub fn month(&self) -> &u32 {
+        &self.month
+    }
+
+    /// Midnight of the first day of the month
+    pub fn start(&self) -> Option<DateTime<Utc>> {
+        Utc.with_ymd_and_hms(self.year, self.month, 1, 0, 0, 0)
+            .single()
+    }
+}
+
+impl From<DateTime<Utc>> for ArchiveMonth {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::new(value.year(), value.month())
+    }
+}
+
+impl fmt::Display for ArchiveMonth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:02}", self.year, self.month)
+    }
+}
+
+/// Games downloaded from a platform in one request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameBatch {
+    /// `None` if the games were not downloaded from a monthly archive
+    archive_month: Option<ArchiveMonth>,
+    games: Vec<NewGame>,
+}
+
+impl GameBatch {
+    pub fn new(archive_month: Option<ArchiveMonth>, games: Vec<NewGame>) -> Self {
+        Self {
+            archive_month,
+            games,
+        }
+    }
+
+    pub fn archive_month(&self) -> Option<&ArchiveMonth> {
+        self.archive_month.as_ref()
+    }
+
+    pub fn games(&self) -> &Vec<NewGame> {
+        &self.games
+    }
+
+    pub fn into_games(self) -> Vec<NewGame> {
+        self.games
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -1,7 +1,4 @@
-use crate::domain::{
-    game::models::new_game::NewGame,
-    platform::models::{PlatformError, PlatformName},
-};
+use crate::domain::platform::models::{GameBatch, PlatformError, PlatformName};
 use async_trait::async_trait;
 use tokio::sync::mpsc::Receiver;
 
@@ -11,15 +8,18 @@ pub trait PlatformApiClient: Send + Sync + 'static {
         &self,
         user_name: String,
         from_timestamp_seconds: Option<u64>,
-    ) -> Result<(usize, Receiver<Result<Vec<NewGame>, PlatformError>>), PlatformError>;
+    ) -> Result<(usize, Receiver<Result<GameBatch, PlatformError>>), PlatformError>;
 }
 
 #[async_trait]
 pub trait PlatformService: Send + Sync + 'static {
+    /// Whether games of the platform can be downloaded
+    fn supports_platform(&self, platform_name: &PlatformName) -> bool;
+
     async fn fetch_games(
         &self,
         user_name: String,
         from_timestamp_seconds: Option<u64>,
         platform_name: PlatformName,
-    ) -> Result<(usize, Receiver<Result<Vec<NewGame>, PlatformError>>), PlatformError>;
+    ) -> Result<(usize, Receiver<Result<GameBatch, PlatformError>>), PlatformError>;
 }
@@ -1,11 +1,8 @@
 use tokio::sync::mpsc::Receiver;
 
-use crate::domain::{
-    game::models::new_game::NewGame,
-    platform::{
-        models::{PlatformError, PlatformName},
-        ports::{PlatformApiClient, PlatformService},
-    },
+use crate::domain::platform::{
+    models::{GameBatch, PlatformError, PlatformName},
+    ports::{PlatformApiClient, PlatformService},
 };
 
 pub type PlatformApiClientMap = std::collections::HashMap<PlatformName, Box<dyn PlatformApiClient>>;
@@ -22,12 +19,16 @@ impl Service {
 
 #[async_trait::async_trait]
 impl PlatformService for Service {
+    fn supports_platform(&self, platform_name: &PlatformName) -> bool {
+        self.client_map.contains_key(platform_name)
+    }
+
     async fn fetch_games(
         &self,
         user_name: String,
         from_timestamp_seconds: Option<u64>,
         platform_name: PlatformName,
-    ) -> Result<(usize, Receiver<Result<Vec<NewGame>, PlatformError>>), PlatformError> {
+    ) -> Result<(usize, Receiver<Result<GameBatch, PlatformError>>), PlatformError> {
         let client = self
             .client_map
             .get(&platform_name)
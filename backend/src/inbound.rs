@@ -1,2 +1,3 @@
 pub mod graphql;
 pub mod http;
+pub mod import_worker;
@@ -1,41 +1,35 @@
 mod dto;
-pub mod game_update_cache;
+mod mutation;
 mod query;
 mod subscription;
 
 use crate::{
-    domain::{game::ports::GameService, platform::ports::PlatformService},
-    inbound::graphql::{game_update_cache::GameUpdateCache, subscription::Subscription},
+    domain::game::ports::GameService,
+    inbound::{graphql::subscription::Subscription, import_worker::ImportWorker},
 };
-use juniper::{Context, EmptyMutation, RootNode};
+use juniper::{Context, RootNode};
+use mutation::Mutation;
 use query::Query;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 pub struct GraphQLContext {
     game_service: Arc<dyn GameService>,
-    platform_service: Arc<dyn PlatformService>,
-    game_update_cache: Arc<Mutex<GameUpdateCache>>,
+    import_worker: Arc<ImportWorker>,
 }
 
 impl GraphQLContext {
-    pub fn new(
-        game_service: Arc<dyn GameService>,
-        platform_service: Arc<dyn PlatformService>,
-        game_update_cache: Arc<Mutex<GameUpdateCache>>,
-    ) -> Self {
+    pub fn new(game_service: Arc<dyn GameService>, import_worker: Arc<ImportWorker>) -> Self {
         Self {
             game_service,
-            platform_service,
-            game_update_cache,
+            import_worker,
         }
     }
 }
 
 impl Context for GraphQLContext {}
 
-pub type Schema = RootNode<'static, Query, EmptyMutation<GraphQLContext>, Subscription>;
+pub type Schema = RootNode<'static, Query, Mutation, Subscription>;
 
 pub fn schema() -> Schema {
-    Schema::new(Query, EmptyMutation::new(), Subscription)
+    Schema::new(Query, Mutation, Subscription)
 }
@@ -1,10 +1,26 @@
-use juniper::{GraphQLEnum, GraphQLObject};
+use juniper::{GraphQLEnum, GraphQLInputObject, GraphQLObject};
 use uuid::Uuid;
 
 use crate::domain::{
     game::models::{
-        game::{Color, Game},
+        game::{Color, Game, TimeClass, Variant},
+        game_detail::{GameDetail, GamePly, Mainline},
+        game_import_error::GameImportError,
+        game_page::{GameCursor, GamePage},
+        game_search::GameResult,
+        import_job::{ImportJob, ImportJobStatus},
+        import_progress::{ImportPhase, ImportProgress},
+        move_comparison::{MoveComparison, PlayerRef},
         move_stat::MoveStat,
+        move_tree::{MoveTree, MoveTreeBranch},
+        opening::Opening,
+        opening_report::{OpeningFamilyReport, OpeningTrendPoint, TrendPeriod},
+        pgn_import::PgnImportSummary,
+        player_profile::{ColorRecord, PlayerProfile, RatingSummary},
+        repertoire::{
+            Deviator, Repertoire, RepertoireDeviation, RepertoireDeviationReport, RepertoireMove,
+        },
+        tracked_player::TrackedPlayer,
     },
     platform::models::PlatformName,
 };
@@ -13,12 +29,16 @@ use crate::domain::{
 #[graphql(name = "PlatformName")]
 pub enum GraphQLPlatformName {
     ChessCom,
+    Lichess,
+    Pgn,
 }
 
-impl Into<PlatformName> for GraphQLPlatformName {
-    fn into(self) -> PlatformName {
-        match self {
+impl From<GraphQLPlatformName> for PlatformName {
+    fn from(value: GraphQLPlatformName) -> Self {
+        match value {
             GraphQLPlatformName::ChessCom => PlatformName::ChessCom,
+            GraphQLPlatformName::Lichess => PlatformName::Lichess,
+            GraphQLPlatformName::Pgn => PlatformName::Pgn,
         }
     }
 }
@@ -27,11 +47,14 @@ impl From<PlatformName> for GraphQLPlatformName {
     fn from(value: PlatformName) -> Self {
         match value {
             PlatformName::ChessCom => GraphQLPlatformName::ChessCom,
+            PlatformName::Lichess => GraphQLPlatformName::Lichess,
+            PlatformName::Pgn => GraphQLPlatformName::Pgn,
         }
     }
 }
 
 #[derive(Clone, GraphQLObject)]
+#[graphql(name = "Game")]
 pub struct GraphQLGame {
     id: Uuid,
     white: String,
@@ -42,6 +65,12 @@ pub struct GraphQLGame {
     platform_name: GraphQLPlatformName,
     pgn: String,
     finished_at: i32,
+    time_class: Option<GraphQLTimeClass>,
+    time_control: Option<String>,
+    rated: bool,
+    variant: GraphQLVariant,
+    /// opening of the deepest position of the game in the opening book
+    opening: Option<GraphQLOpening>,
 }
 
 impl From<Game> for GraphQLGame {
@@ -56,6 +85,147 @@ impl From<Game> for GraphQLGame {
             platform_name: GraphQLPlatformName::from(*value.platform_name()),
             pgn: value.pgn().to_string(),
             finished_at: value.finished_at().timestamp() as i32,
+            time_class: value.time_class().map(|t| GraphQLTimeClass::from(*t)),
+            time_control: value.time_control().cloned(),
+            rated: *value.rated(),
+            variant: GraphQLVariant::from(*value.variant()),
+            opening: value.opening().map(|opening| opening.clone().into()),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "Opening")]
+pub struct GraphQLOpening {
+    pub eco: String,
+    pub name: String,
+}
+
+impl From<Opening> for GraphQLOpening {
+    fn from(value: Opening) -> Self {
+        GraphQLOpening {
+            eco: value.eco().clone(),
+            name: value.name().clone(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "Ply")]
+pub struct GraphQLPly {
+    /// half-move number, starting at 1
+    pub ply: i32,
+    pub san: String,
+    pub uci: String,
+    /// position after the move
+    pub fen: String,
+    /// remaining time of the player who moved
+    pub clock_seconds: Option<f64>,
+}
+
+impl From<&GamePly> for GraphQLPly {
+    fn from(value: &GamePly) -> Self {
+        GraphQLPly {
+            ply: *value.ply() as i32,
+            san: value.san().clone(),
+            uci: value.uci().clone(),
+            fen: value.fen().to_string(),
+            clock_seconds: value.clock().map(|clock| clock.as_secs_f64()),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "Mainline")]
+pub struct GraphQLMainline {
+    pub start_fen: String,
+    pub plies: Vec<GraphQLPly>,
+}
+
+impl From<&Mainline> for GraphQLMainline {
+    fn from(value: &Mainline) -> Self {
+        GraphQLMainline {
+            start_fen: value.start_fen().to_string(),
+            plies: value.plies().iter().map(|ply| ply.into()).collect(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "GameDetail")]
+pub struct GraphQLGameDetail {
+    pub game: GraphQLGame,
+    /// null if the moves can't be replayed, e.g. for other variants than standard chess
+    pub mainline: Option<GraphQLMainline>,
+}
+
+impl From<GameDetail> for GraphQLGameDetail {
+    fn from(value: GameDetail) -> Self {
+        GraphQLGameDetail {
+            mainline: value.mainline().map(|mainline| mainline.into()),
+            game: value.game().clone().into(),
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "GameResult")]
+pub enum GraphQLGameResult {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl From<GraphQLGameResult> for GameResult {
+    fn from(value: GraphQLGameResult) -> Self {
+        match value {
+            GraphQLGameResult::Win => GameResult::Win,
+            GraphQLGameResult::Draw => GameResult::Draw,
+            GraphQLGameResult::Loss => GameResult::Loss,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "PageInfo")]
+pub struct GraphQLPageInfo {
+    pub has_next_page: bool,
+    /// cursor of the last edge, to pass as `after` for the next page
+    pub end_cursor: Option<String>,
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "GameEdge")]
+pub struct GraphQLGameEdge {
+    pub cursor: String,
+    pub node: GraphQLGame,
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "GameConnection")]
+pub struct GraphQLGameConnection {
+    pub edges: Vec<GraphQLGameEdge>,
+    pub page_info: GraphQLPageInfo,
+}
+
+impl From<GamePage> for GraphQLGameConnection {
+    fn from(value: GamePage) -> Self {
+        let has_next_page = *value.has_next_page();
+        let edges = value
+            .into_games()
+            .into_iter()
+            .map(|game| GraphQLGameEdge {
+                cursor: GameCursor::from(&game).to_string(),
+                node: game.into(),
+            })
+            .collect::<Vec<_>>();
+
+        GraphQLGameConnection {
+            page_info: GraphQLPageInfo {
+                has_next_page,
+                end_cursor: edges.last().map(|edge| edge.cursor.clone()),
+            },
+            edges,
         }
     }
 }
@@ -67,9 +237,9 @@ pub enum GraphQLColor {
     Black,
 }
 
-impl Into<Color> for GraphQLColor {
-    fn into(self) -> Color {
-        match self {
+impl From<GraphQLColor> for Color {
+    fn from(value: GraphQLColor) -> Self {
+        match value {
             GraphQLColor::White => Color::White,
             GraphQLColor::Black => Color::Black,
         }
@@ -85,14 +255,96 @@ impl From<Color> for GraphQLColor {
     }
 }
 
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "TimeClass")]
+pub enum GraphQLTimeClass {
+    Bullet,
+    Blitz,
+    Rapid,
+    Classical,
+    Daily,
+}
+
+impl From<GraphQLTimeClass> for TimeClass {
+    fn from(value: GraphQLTimeClass) -> Self {
+        match value {
+            GraphQLTimeClass::Bullet => TimeClass::Bullet,
+            GraphQLTimeClass::Blitz => TimeClass::Blitz,
+            GraphQLTimeClass::Rapid => TimeClass::Rapid,
+            GraphQLTimeClass::Classical => TimeClass::Classical,
+            GraphQLTimeClass::Daily => TimeClass::Daily,
+        }
+    }
+}
+
+impl From<TimeClass> for GraphQLTimeClass {
+    fn from(value: TimeClass) -> Self {
+        match value {
+            TimeClass::Bullet => GraphQLTimeClass::Bullet,
+            TimeClass::Blitz => GraphQLTimeClass::Blitz,
+            TimeClass::Rapid => GraphQLTimeClass::Rapid,
+            TimeClass::Classical => GraphQLTimeClass::Classical,
+            TimeClass::Daily => GraphQLTimeClass::Daily,
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "Variant")]
+pub enum GraphQLVariant {
+    Standard,
+    Chess960,
+    Crazyhouse,
+    Bughouse,
+    ThreeCheck,
+    KingOfTheHill,
+    Atomic,
+    Antichess,
+    Horde,
+    RacingKings,
+    Other,
+}
+
+impl From<Variant> for GraphQLVariant {
+    fn from(value: Variant) -> Self {
+        match value {
+            Variant::Standard => GraphQLVariant::Standard,
+            Variant::Chess960 => GraphQLVariant::Chess960,
+            Variant::Crazyhouse => GraphQLVariant::Crazyhouse,
+            Variant::Bughouse => GraphQLVariant::Bughouse,
+            Variant::ThreeCheck => GraphQLVariant::ThreeCheck,
+            Variant::KingOfTheHill => GraphQLVariant::KingOfTheHill,
+            Variant::Atomic => GraphQLVariant::Atomic,
+            Variant::Antichess => GraphQLVariant::Antichess,
+            Variant::Horde => GraphQLVariant::Horde,
+            Variant::RacingKings => GraphQLVariant::RacingKings,
+            Variant::Other => GraphQLVariant::Other,
+        }
+    }
+}
+
 #[derive(GraphQLObject, Clone)]
 pub struct GraphQLMoveStat {
     pub move_uci: String,
     pub total: i32,
     pub wins: i32,
     pub draws: i32,
+    pub losses: i32,
+    /// percentage of the points scored, counting draws as half a point
+    pub score: f64,
     pub avg_opponent_elo: i32,
+    pub avg_own_elo: i32,
+    pub performance_rating: i32,
+    pub first_played_at: i32,
     pub last_played_at: i32,
+    pub white_wins: i32,
+    pub black_wins: i32,
+    /// average of the known ratings of the players with white
+    pub avg_white_elo: i32,
+    pub avg_black_elo: i32,
+    /// opening of the position after the move, or of the queried position if the move
+    /// leaves the opening book. Only set by `getMoveStats`
+    pub opening: Option<GraphQLOpening>,
 }
 
 impl From<MoveStat> for GraphQLMoveStat {
@@ -102,8 +354,577 @@ impl From<MoveStat> for GraphQLMoveStat {
             total: *value.total() as i32,
             wins: *value.wins() as i32,
             draws: *value.draws() as i32,
+            losses: *value.losses() as i32,
+            score: *value.score(),
             avg_opponent_elo: *value.avg_opponent_elo() as i32,
+            avg_own_elo: *value.avg_own_elo() as i32,
+            performance_rating: *value.performance_rating() as i32,
+            first_played_at: value.first_played_at().timestamp() as i32,
             last_played_at: value.last_played_at().timestamp() as i32,
+            white_wins: *value.white_wins() as i32,
+            black_wins: *value.black_wins() as i32,
+            avg_white_elo: *value.avg_white_elo() as i32,
+            avg_black_elo: *value.avg_black_elo() as i32,
+            opening: value.opening().map(|opening| opening.clone().into()),
+        }
+    }
+}
+
+#[derive(GraphQLInputObject, Clone)]
+#[graphql(name = "PlayerRef")]
+pub struct GraphQLPlayerRef {
+    pub username: String,
+    pub platform_name: GraphQLPlatformName,
+    pub play_as: GraphQLColor,
+}
+
+impl From<GraphQLPlayerRef> for PlayerRef {
+    fn from(value: GraphQLPlayerRef) -> Self {
+        PlayerRef::new(
+            value.username,
+            value.platform_name.into(),
+            value.play_as.into(),
+        )
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "MoveComparison")]
+pub struct GraphQLMoveComparison {
+    pub move_uci: String,
+    /// one entry per player in the order of the requested players, null if the player
+    /// never played the move
+    pub stats: Vec<Option<GraphQLMoveStat>>,
+}
+
+impl From<MoveComparison> for GraphQLMoveComparison {
+    fn from(value: MoveComparison) -> Self {
+        GraphQLMoveComparison {
+            move_uci: value.move_uci().clone(),
+            stats: value
+                .stats()
+                .iter()
+                .map(|move_stat| move_stat.clone().map(|move_stat| move_stat.into()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "MoveTree")]
+pub struct GraphQLMoveTree {
+    pub fen: String,
+    /// moves played in the position, the most played first
+    pub branches: Vec<GraphQLMoveTreeBranch>,
+}
+
+impl From<&MoveTree> for GraphQLMoveTree {
+    fn from(value: &MoveTree) -> Self {
+        GraphQLMoveTree {
+            fen: value.fen().to_string(),
+            branches: value
+                .branches()
+                .iter()
+                .map(|branch| branch.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "MoveTreeBranch")]
+pub struct GraphQLMoveTreeBranch {
+    pub san: String,
+    pub stat: GraphQLMoveStat,
+    /// position after the move
+    pub tree: GraphQLMoveTree,
+}
+
+impl From<&MoveTreeBranch> for GraphQLMoveTreeBranch {
+    fn from(value: &MoveTreeBranch) -> Self {
+        GraphQLMoveTreeBranch {
+            san: value.san().clone(),
+            stat: value.stat().clone().into(),
+            tree: value.tree().into(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "GameImportError")]
+pub struct GraphQLGameImportError {
+    pub game_id: Uuid,
+    pub reason: String,
+    /// move that could not be played
+    pub san: Option<String>,
+    /// half-move number of the offending move, starting at 1
+    pub ply: Option<i32>,
+    pub created_at: i32,
+}
+
+impl From<GameImportError> for GraphQLGameImportError {
+    fn from(value: GameImportError) -> Self {
+        GraphQLGameImportError {
+            game_id: *value.game_id(),
+            reason: value.reason().clone(),
+            san: value.san().cloned(),
+            ply: value.ply().map(|ply| *ply as i32),
+            created_at: value.created_at().timestamp() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "ImportPhase")]
+pub enum GraphQLImportPhase {
+    ResolvingArchives,
+    Downloading,
+    Indexing,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl From<ImportPhase> for GraphQLImportPhase {
+    fn from(value: ImportPhase) -> Self {
+        match value {
+            ImportPhase::ResolvingArchives => GraphQLImportPhase::ResolvingArchives,
+            ImportPhase::Downloading => GraphQLImportPhase::Downloading,
+            ImportPhase::Indexing => GraphQLImportPhase::Indexing,
+            ImportPhase::Done => GraphQLImportPhase::Done,
+            ImportPhase::Failed => GraphQLImportPhase::Failed,
+            ImportPhase::Cancelled => GraphQLImportPhase::Cancelled,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "GameImportProgress")]
+pub struct GraphQLGameImportProgress {
+    /// `DONE`, `FAILED` and `CANCELLED` are sent once as the last event of an import
+    pub phase: GraphQLImportPhase,
+    /// fraction of the import that is completed, between 0 and 1
+    pub fraction: f64,
+    pub archives_done: i32,
+    pub archives_total: i32,
+    pub games_fetched: i32,
+    pub games_inserted: i32,
+    /// stored games whose details were refreshed by a full resync
+    pub games_updated: i32,
+    /// fetched games that were already stored
+    pub duplicates_skipped: i32,
+    /// inserted games of non-standard variants, which have no positions indexed
+    pub variants_skipped: i32,
+    /// amount of stored games whose positions could not be indexed
+    pub failures: i32,
+    /// month of the archive being stored, e.g. `2024/05`
+    pub current_archive: Option<String>,
+    /// reason of the failure for the `FAILED` phase
+    pub error: Option<String>,
+}
+
+impl From<ImportProgress> for GraphQLGameImportProgress {
+    fn from(value: ImportProgress) -> Self {
+        GraphQLGameImportProgress {
+            phase: GraphQLImportPhase::from(*value.phase()),
+            fraction: value.fraction(),
+            archives_done: *value.archives_done() as i32,
+            archives_total: *value.archives_total() as i32,
+            games_fetched: *value.games_fetched() as i32,
+            games_inserted: *value.games_inserted() as i32,
+            games_updated: *value.games_updated() as i32,
+            duplicates_skipped: *value.duplicates_skipped() as i32,
+            variants_skipped: *value.variants_skipped() as i32,
+            failures: *value.failures() as i32,
+            current_archive: value.current_archive().map(|month| month.to_string()),
+            error: value.error().cloned(),
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "ImportJobStatus")]
+pub enum GraphQLImportJobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl From<ImportJobStatus> for GraphQLImportJobStatus {
+    fn from(value: ImportJobStatus) -> Self {
+        match value {
+            ImportJobStatus::Pending => GraphQLImportJobStatus::Pending,
+            ImportJobStatus::Running => GraphQLImportJobStatus::Running,
+            ImportJobStatus::Done => GraphQLImportJobStatus::Done,
+            ImportJobStatus::Failed => GraphQLImportJobStatus::Failed,
+            ImportJobStatus::Cancelled => GraphQLImportJobStatus::Cancelled,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "ImportJob")]
+pub struct GraphQLImportJob {
+    pub id: Uuid,
+    pub username: String,
+    pub platform_name: GraphQLPlatformName,
+    pub status: GraphQLImportJobStatus,
+    pub full_resync: bool,
+    /// month of the last archive whose games are stored, e.g. `2024/05`
+    pub last_archive: Option<String>,
+    pub games_inserted: i32,
+    pub error: Option<String>,
+    pub created_at: i32,
+    pub updated_at: i32,
+    pub finished_at: Option<i32>,
+}
+
+impl From<ImportJob> for GraphQLImportJob {
+    fn from(value: ImportJob) -> Self {
+        GraphQLImportJob {
+            id: *value.id(),
+            username: value.username().clone(),
+            platform_name: GraphQLPlatformName::from(*value.platform_name()),
+            status: GraphQLImportJobStatus::from(*value.status()),
+            full_resync: *value.full_resync(),
+            last_archive: value.last_archive().map(|month| month.to_string()),
+            games_inserted: *value.games_inserted() as i32,
+            error: value.error().cloned(),
+            created_at: value.created_at().timestamp() as i32,
+            updated_at: value.updated_at().timestamp() as i32,
+            finished_at: value
+                .finished_at()
+                .map(|finished_at| finished_at.timestamp() as i32),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "PgnImportSummary")]
+pub struct GraphQLPgnImportSummary {
+    /// games found in the PGN
+    pub parsed: i32,
+    /// games that were not stored before
+    pub inserted: i32,
+    /// inserted games of non-standard variants, which have no positions indexed
+    pub skipped: i32,
+    /// inserted games whose positions could not be indexed
+    pub failed: i32,
+}
+
+impl From<PgnImportSummary> for GraphQLPgnImportSummary {
+    fn from(value: PgnImportSummary) -> Self {
+        GraphQLPgnImportSummary {
+            parsed: *value.parsed_amount() as i32,
+            inserted: *value.inserted_amount() as i32,
+            skipped: *value.skipped_amount() as i32,
+            failed: *value.failed_amount() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "TrackedPlayer")]
+pub struct GraphQLTrackedPlayer {
+    pub platform_name: GraphQLPlatformName,
+    /// lowercased username
+    pub username: String,
+    /// month of the latest synced archive, e.g. `2024/05`. The next import continues there
+    pub last_synced_archive: Option<String>,
+    pub last_synced_at: Option<i32>,
+    /// stored games of the player as of the last finished import
+    pub game_count: i32,
+}
+
+impl From<TrackedPlayer> for GraphQLTrackedPlayer {
+    fn from(value: TrackedPlayer) -> Self {
+        GraphQLTrackedPlayer {
+            platform_name: GraphQLPlatformName::from(*value.platform_name()),
+            username: value.username().clone(),
+            last_synced_archive: value.last_synced_archive().map(|month| month.to_string()),
+            last_synced_at: value
+                .last_synced_at()
+                .map(|last_synced_at| last_synced_at.timestamp() as i32),
+            game_count: *value.game_count() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "ColorRecord")]
+pub struct GraphQLColorRecord {
+    pub games: i32,
+    pub wins: i32,
+    pub draws: i32,
+    pub losses: i32,
+}
+
+impl From<&ColorRecord> for GraphQLColorRecord {
+    fn from(value: &ColorRecord) -> Self {
+        GraphQLColorRecord {
+            games: *value.games() as i32,
+            wins: *value.wins() as i32,
+            draws: *value.draws() as i32,
+            losses: *value.losses() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "RatingSummary")]
+pub struct GraphQLRatingSummary {
+    pub min: i32,
+    pub max: i32,
+    /// rating in the latest rated game
+    pub current: i32,
+}
+
+impl From<&RatingSummary> for GraphQLRatingSummary {
+    fn from(value: &RatingSummary) -> Self {
+        GraphQLRatingSummary {
+            min: *value.min() as i32,
+            max: *value.max() as i32,
+            current: *value.current() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "PlayerProfile")]
+pub struct GraphQLPlayerProfile {
+    /// username as spelled in the latest game of the player
+    pub username: String,
+    pub platform_name: GraphQLPlatformName,
+    pub total_games: i32,
+    pub as_white: GraphQLColorRecord,
+    pub as_black: GraphQLColorRecord,
+    /// null if no game of the player is rated
+    pub rating: Option<GraphQLRatingSummary>,
+    pub first_game_at: i32,
+    pub last_game_at: i32,
+    /// most played first moves, the most played first
+    pub first_moves_as_white: Vec<GraphQLMoveStat>,
+    pub first_moves_as_black: Vec<GraphQLMoveStat>,
+}
+
+impl From<PlayerProfile> for GraphQLPlayerProfile {
+    fn from(value: PlayerProfile) -> Self {
+        GraphQLPlayerProfile {
+            username: value.username().clone(),
+            platform_name: GraphQLPlatformName::from(*value.platform_name()),
+            total_games: value.total_games() as i32,
+            as_white: value.as_white().into(),
+            as_black: value.as_black().into(),
+            rating: value.rating().map(|rating| rating.into()),
+            first_game_at: value.first_game_at().timestamp() as i32,
+            last_game_at: value.last_game_at().timestamp() as i32,
+            first_moves_as_white: value
+                .first_moves_as_white()
+                .iter()
+                .cloned()
+                .map(|move_stat| move_stat.into())
+                .collect(),
+            first_moves_as_black: value
+                .first_moves_as_black()
+                .iter()
+                .cloned()
+                .map(|move_stat| move_stat.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "TrendPeriod")]
+pub enum GraphQLTrendPeriod {
+    Week,
+    Month,
+    Year,
+}
+
+impl From<GraphQLTrendPeriod> for TrendPeriod {
+    fn from(value: GraphQLTrendPeriod) -> Self {
+        match value {
+            GraphQLTrendPeriod::Week => TrendPeriod::Week,
+            GraphQLTrendPeriod::Month => TrendPeriod::Month,
+            GraphQLTrendPeriod::Year => TrendPeriod::Year,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "OpeningTrendPoint")]
+pub struct GraphQLOpeningTrendPoint {
+    pub period_start: i32,
+    pub record: GraphQLColorRecord,
+    /// percentage of the points scored, counting draws as half a point
+    pub score: f64,
+}
+
+impl From<&OpeningTrendPoint> for GraphQLOpeningTrendPoint {
+    fn from(value: &OpeningTrendPoint) -> Self {
+        GraphQLOpeningTrendPoint {
+            period_start: value.period_start().timestamp() as i32,
+            record: value.record().into(),
+            score: value.record().score(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "OpeningFamilyReport")]
+pub struct GraphQLOpeningFamilyReport {
+    /// opening name without the variation, null for the games outside the opening book
+    pub family: Option<String>,
+    pub ecos: Vec<String>,
+    pub record: GraphQLColorRecord,
+    /// percentage of the points scored, counting draws as half a point
+    pub score: f64,
+    pub avg_opponent_elo: i32,
+    /// results per period, the oldest first
+    pub trend: Vec<GraphQLOpeningTrendPoint>,
+}
+
+impl From<OpeningFamilyReport> for GraphQLOpeningFamilyReport {
+    fn from(value: OpeningFamilyReport) -> Self {
+        GraphQLOpeningFamilyReport {
+            family: value.family().cloned(),
+            ecos: value.ecos().to_vec(),
+            record: value.record().into(),
+            score: value.record().score(),
+            avg_opponent_elo: *value.avg_opponent_elo() as i32,
+            trend: value
+                .trend()
+                .iter()
+                .map(|trend_point| trend_point.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "RepertoireMove")]
+pub struct GraphQLRepertoireMove {
+    pub fen: String,
+    pub uci: String,
+    pub san: String,
+}
+
+impl From<&RepertoireMove> for GraphQLRepertoireMove {
+    fn from(value: &RepertoireMove) -> Self {
+        GraphQLRepertoireMove {
+            fen: value.fen().to_string(),
+            uci: value.uci().clone(),
+            san: value.san().clone(),
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "Repertoire")]
+pub struct GraphQLRepertoire {
+    pub id: Uuid,
+    pub name: String,
+    pub color: GraphQLColor,
+    /// moves in PGN order, the mainline move of a position before its alternatives
+    pub moves: Vec<GraphQLRepertoireMove>,
+    /// the moves as a game with variations
+    pub pgn: String,
+    pub created_at: i32,
+    pub updated_at: i32,
+}
+
+impl GraphQLRepertoire {
+    pub fn new(repertoire: &Repertoire, pgn: String) -> Self {
+        GraphQLRepertoire {
+            id: *repertoire.id(),
+            name: repertoire.name().clone(),
+            color: GraphQLColor::from(*repertoire.color()),
+            moves: repertoire
+                .moves()
+                .iter()
+                .map(|repertoire_move| repertoire_move.into())
+                .collect(),
+            pgn,
+            created_at: repertoire.created_at().timestamp() as i32,
+            updated_at: repertoire.updated_at().timestamp() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLEnum, Clone, Copy)]
+#[graphql(name = "Deviator")]
+pub enum GraphQLDeviator {
+    Player,
+    Opponent,
+}
+
+impl From<Deviator> for GraphQLDeviator {
+    fn from(value: Deviator) -> Self {
+        match value {
+            Deviator::Player => GraphQLDeviator::Player,
+            Deviator::Opponent => GraphQLDeviator::Opponent,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "RepertoireDeviation")]
+pub struct GraphQLRepertoireDeviation {
+    pub fen: String,
+    /// half-move number of the deviating move, the lowest one for transpositions
+    pub ply: i32,
+    pub deviator: GraphQLDeviator,
+    pub played_uci: String,
+    pub played_san: String,
+    /// moves of the repertoire in the position
+    pub expected_sans: Vec<String>,
+    /// results of the games from the side of the player
+    pub record: GraphQLColorRecord,
+    /// percentage of the points scored, counting draws as half a point
+    pub score: f64,
+    pub last_played_at: i32,
+}
+
+impl From<&RepertoireDeviation> for GraphQLRepertoireDeviation {
+    fn from(value: &RepertoireDeviation) -> Self {
+        GraphQLRepertoireDeviation {
+            fen: value.fen().to_string(),
+            ply: *value.ply() as i32,
+            deviator: GraphQLDeviator::from(*value.deviator()),
+            played_uci: value.played_uci().clone(),
+            played_san: value.played_san().clone(),
+            expected_sans: value.expected_sans().to_vec(),
+            record: value.record().into(),
+            score: value.record().score(),
+            last_played_at: value.last_played_at().timestamp() as i32,
+        }
+    }
+}
+
+#[derive(GraphQLObject, Clone)]
+#[graphql(name = "RepertoireDeviationReport")]
+pub struct GraphQLRepertoireDeviationReport {
+    /// games of the player with the color of the repertoire
+    pub games: i32,
+    /// games that stayed in the repertoire until it or the game ended
+    pub followed_games: i32,
+    /// deviations of the other games, the most frequent first
+    pub deviations: Vec<GraphQLRepertoireDeviation>,
+}
+
+impl From<RepertoireDeviationReport> for GraphQLRepertoireDeviationReport {
+    fn from(value: RepertoireDeviationReport) -> Self {
+        GraphQLRepertoireDeviationReport {
+            games: *value.games() as i32,
+            followed_games: *value.followed_games() as i32,
+            deviations: value
+                .deviations()
+                .iter()
+                .map(|deviation| deviation.into())
+                .collect(),
         }
     }
 }
@@ -0,0 +1,279 @@
+use juniper::{FieldResult, graphql_object};
+use uuid::Uuid;
+
+use crate::{
+    domain::game::models::{
+        errors::{GameRepositoryError, InvalidPgnError, SaveRepertoireError, StoreGamesError},
+        import_job::ImportJob,
+        pgn_import::PgnImportSummary,
+        repertoire::Repertoire,
+    },
+    inbound::{
+        graphql::{
+            GraphQLContext,
+            dto::{
+                GraphQLColor, GraphQLImportJob, GraphQLPgnImportSummary, GraphQLPlatformName,
+                GraphQLRepertoire,
+            },
+        },
+        import_worker::{DeletePlayerGamesError, StartImportError},
+    },
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Mutation;
+
+/// The root mutation object of the schema
+#[graphql_object(context = GraphQLContext)]
+impl Mutation {
+    /// Starts an import of the new games of the player, or of all games for a full resync,
+    /// and returns it without waiting for it. Returns the running import if there is one,
+    /// a full resync fails while the new games are imported. `updateUserGames` or
+    /// `importJobs` report its progress
+    async fn start_import(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+        full_resync: Option<bool>,
+    ) -> FieldResult<GraphQLImportJob> {
+        let started: Result<_, StartImportMutationError> = ctx
+            .import_worker
+            .start(
+                &username,
+                platform_name.into(),
+                full_resync.unwrap_or_default(),
+            )
+            .await
+            .map_err(|e| e.into());
+        let (import_job_id, _) = started?;
+
+        let import_job: Result<Option<ImportJob>, StartImportMutationError> = ctx
+            .game_service
+            .get_import_job(import_job_id)
+            .await
+            .map_err(|e| e.into());
+
+        match import_job? {
+            Some(import_job) => Ok(import_job.into()),
+            None => Err(StartImportMutationError::InternalError)?,
+        }
+    }
+
+    /// Stops the running import and returns it once it stopped. The games stored so far are
+    /// kept. Null if the import with the id is not running
+    async fn cancel_import(
+        #[graphql(context)] ctx: &GraphQLContext,
+        job_id: Uuid,
+    ) -> FieldResult<Option<GraphQLImportJob>> {
+        if !ctx.import_worker.cancel(&job_id).await {
+            return Ok(None);
+        }
+
+        let import_job: Result<Option<ImportJob>, CancelImportError> = ctx
+            .game_service
+            .get_import_job(job_id)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(import_job?.map(|import_job| import_job.into()))
+    }
+
+    /// Deletes the stored games of the player and returns how many were deleted. The next
+    /// import of the player downloads the whole history again
+    async fn delete_player_games(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+    ) -> FieldResult<i32> {
+        let deleted: Result<u64, DeletePlayerGamesMutationError> = ctx
+            .import_worker
+            .delete_player_games(username, platform_name.into())
+            .await
+            .map_err(|e| e.into());
+
+        Ok(deleted? as i32)
+    }
+
+    /// Parses every game of the PGN and stores the ones that are not stored yet
+    async fn import_pgn(
+        #[graphql(context)] ctx: &GraphQLContext,
+        pgn: String,
+    ) -> FieldResult<GraphQLPgnImportSummary> {
+        let summary: Result<PgnImportSummary, ImportPgnError> = ctx
+            .game_service
+            .import_pgn(&pgn)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(summary?.into())
+    }
+
+    /// Stores a repertoire of the color. The mainlines and variations of every game of the
+    /// PGN become its moves
+    async fn create_repertoire(
+        #[graphql(context)] ctx: &GraphQLContext,
+        name: String,
+        color: GraphQLColor,
+        pgn: Option<String>,
+    ) -> FieldResult<GraphQLRepertoire> {
+        if name.trim().is_empty() {
+            Err(SaveRepertoireMutationError::EmptyName)?;
+        }
+
+        let repertoire: Result<Repertoire, SaveRepertoireMutationError> = ctx
+            .game_service
+            .create_repertoire(name, color.into(), pgn)
+            .await
+            .map_err(|e| e.into());
+        let repertoire = repertoire?;
+
+        Ok(GraphQLRepertoire::new(
+            &repertoire,
+            ctx.game_service.export_repertoire_pgn(&repertoire),
+        ))
+    }
+
+    /// Changes the given fields of the repertoire, a PGN replaces all its moves. Null if
+    /// there is no repertoire with the id
+    async fn update_repertoire(
+        #[graphql(context)] ctx: &GraphQLContext,
+        id: Uuid,
+        name: Option<String>,
+        color: Option<GraphQLColor>,
+        pgn: Option<String>,
+    ) -> FieldResult<Option<GraphQLRepertoire>> {
+        if name.as_ref().is_some_and(|name| name.trim().is_empty()) {
+            Err(SaveRepertoireMutationError::EmptyName)?;
+        }
+
+        let repertoire: Result<Option<Repertoire>, SaveRepertoireMutationError> = ctx
+            .game_service
+            .update_repertoire(id, name, color.map(|color| color.into()), pgn)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(repertoire?.map(|repertoire| {
+            GraphQLRepertoire::new(
+                &repertoire,
+                ctx.game_service.export_repertoire_pgn(&repertoire),
+            )
+        }))
+    }
+
+    /// Deletes the repertoire, false if there is no repertoire with the id
+    async fn delete_repertoire(
+        #[graphql(context)] ctx: &GraphQLContext,
+        id: Uuid,
+    ) -> FieldResult<bool> {
+        let deleted: Result<bool, DeleteRepertoireError> = ctx
+            .game_service
+            .delete_repertoire(id)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(deleted?)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum StartImportMutationError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("games can't be imported from this platform")]
+    UnsupportedPlatform,
+    #[error("an import of the new games of the player is running, cancel it to resync")]
+    ImportRunning,
+}
+
+impl From<StartImportError> for StartImportMutationError {
+    fn from(value: StartImportError) -> Self {
+        match value {
+            StartImportError::UnsupportedPlatform(_) => Self::UnsupportedPlatform,
+            StartImportError::ImportRunning => Self::ImportRunning,
+            StartImportError::GameRepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+impl From<GameRepositoryError> for StartImportMutationError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CancelImportError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for CancelImportError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DeletePlayerGamesMutationError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("an import of the player is running")]
+    ImportRunning,
+}
+
+impl From<DeletePlayerGamesError> for DeletePlayerGamesMutationError {
+    fn from(value: DeletePlayerGamesError) -> Self {
+        match value {
+            DeletePlayerGamesError::ImportRunning => Self::ImportRunning,
+            DeletePlayerGamesError::GameRepositoryError(_) => Self::InternalError,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum ImportPgnError {
+    #[error("Internal error")]
+    InternalError,
+    #[error(transparent)]
+    InvalidPgn(#[from] InvalidPgnError),
+}
+
+impl From<StoreGamesError> for ImportPgnError {
+    fn from(value: StoreGamesError) -> Self {
+        match value {
+            StoreGamesError::InvalidPgn(err) => Self::InvalidPgn(err),
+            _ => Self::InternalError,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum SaveRepertoireMutationError {
+    #[error("Internal error")]
+    InternalError,
+    #[error(transparent)]
+    InvalidPgn(#[from] InvalidPgnError),
+    #[error("name must not be empty")]
+    EmptyName,
+}
+
+impl From<SaveRepertoireError> for SaveRepertoireMutationError {
+    fn from(value: SaveRepertoireError) -> Self {
+        match value {
+            SaveRepertoireError::GameRepositoryError(_) => Self::InternalError,
+            SaveRepertoireError::InvalidPgn(err) => Self::InvalidPgn(err),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum DeleteRepertoireError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for DeleteRepertoireError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
@@ -1,61 +1,118 @@
-use chrono::DateTime;
+use chrono::{DateTime, Utc};
 use juniper::{FieldResult, graphql_object};
+use uuid::Uuid;
 
 use crate::{
     domain::game::models::{
-        errors::{GameRepositoryError, InvalidFenError},
+        elo_filter::EloFilter,
+        errors::{GameRepositoryError, InvalidFenError, InvalidGameCursorError},
+        game_detail::GameDetail,
+        game_filter::GameFilter,
+        game_import_error::GameImportError,
+        game_page::{GameCursor, GamePage},
+        game_search::{GameSearch, PositionFilter},
+        import_job::ImportJob,
+        move_comparison::MoveComparison,
         move_stat::MoveStat,
+        move_stats_scope::MoveStatsScope,
+        move_tree::MoveTree,
+        opening_report::{OpeningFamilyReport, TrendPeriod},
+        player_profile::PlayerProfile,
+        repertoire::{Repertoire, RepertoireDeviationReport},
+        tracked_player::TrackedPlayer,
     },
     inbound::graphql::{
         GraphQLContext,
-        dto::{GraphQLColor, GraphQLMoveStat, GraphQLPlatformName},
+        dto::{
+            GraphQLColor, GraphQLGameConnection, GraphQLGameDetail, GraphQLGameImportError,
+            GraphQLGameResult, GraphQLImportJob, GraphQLMoveComparison, GraphQLMoveStat,
+            GraphQLMoveTree, GraphQLOpeningFamilyReport, GraphQLPlatformName, GraphQLPlayerProfile,
+            GraphQLPlayerRef, GraphQLRepertoire, GraphQLRepertoireDeviationReport,
+            GraphQLTimeClass, GraphQLTrackedPlayer, GraphQLTrendPeriod,
+        },
     },
 };
 
+/// Games per page if the client doesn't ask for an amount
+const DEFAULT_GAME_PAGE_SIZE: i32 = 20;
+const MAX_GAME_PAGE_SIZE: i32 = 100;
+const MAX_COMPARED_PLAYERS: usize = 10;
+/// Plies of a move tree, as the lines multiply with every ply
+const MAX_MOVE_TREE_DEPTH: u8 = 8;
+
 #[derive(Clone, Copy, Debug)]
 pub struct Query;
 
 /// The root query object of the schema
 #[graphql_object(context = GraphQLContext)]
 impl Query {
+    /// Statistics of the moves played in the position. With a username the games of the
+    /// player with `playAs` are counted, otherwise all stored games of the platform, or only
+    /// those with one of `players`, and wins count for the side to move. The rating bounds
+    /// apply to the average rating of both players, the own and opponent Elo bounds to the
+    /// ratings from the side of the player or of the side to move
+    #[allow(clippy::too_many_arguments)]
     async fn get_move_stats(
         #[graphql(context)] ctx: &GraphQLContext,
         position_fen: String,
-        username: String,
-        play_as: GraphQLColor,
+        username: Option<String>,
+        play_as: Option<GraphQLColor>,
         platform_name: GraphQLPlatformName,
         from_timestamp_seconds: Option<i32>,
         to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+        players: Option<Vec<String>>,
+        min_rating: Option<i32>,
+        max_rating: Option<i32>,
+        min_own_elo: Option<i32>,
+        max_own_elo: Option<i32>,
+        min_opponent_elo: Option<i32>,
+        max_opponent_elo: Option<i32>,
     ) -> FieldResult<Vec<GraphQLMoveStat>> {
+        let scope = match (username, play_as, players) {
+            (Some(username), Some(play_as), None) => MoveStatsScope::Player {
+                username,
+                play_as: play_as.into(),
+            },
+            (Some(_), None, _) => Err(GetMoveStatsError::MissingPlayAs)?,
+            (Some(_), _, Some(_)) => Err(GetMoveStatsError::PlayersWithUsername)?,
+            (None, _, players) => MoveStatsScope::AllGames { usernames: players },
+        };
+
         let move_stats: Result<Vec<MoveStat>, GetMoveStatsError> = ctx
             .game_service
             .get_move_stats(
                 ctx.game_service.parse_fen(position_fen)?,
-                username,
-                play_as.into(),
+                scope,
                 platform_name.into(),
-                match from_timestamp_seconds {
-                    Some(from_timestamp_seconds) => Some(
-                        DateTime::from_timestamp(from_timestamp_seconds as i64, 0).ok_or(
-                            GetMoveStatsError::InvalidTimestamp(
-                                "from_timestamp_seconds".to_string(),
-                            ),
-                        )?,
-                    ),
-                    None => None,
-                },
-                match to_timestamp_seconds {
-                    Some(to_timestamp_seconds) => Some(
-                        DateTime::from_timestamp(to_timestamp_seconds as i64, 0).ok_or(
-                            GetMoveStatsError::InvalidTimestamp("to_timestamp_seconds".to_string()),
-                        )?,
-                    ),
-                    None => None,
-                },
+                game_filter_argument(
+                    from_timestamp_seconds,
+                    to_timestamp_seconds,
+                    time_classes,
+                    rated_only,
+                    rating_argument(min_rating, "min_rating", GetMoveStatsError::InvalidRating)?,
+                    rating_argument(max_rating, "max_rating", GetMoveStatsError::InvalidRating)?,
+                    GetMoveStatsError::InvalidTimestamp,
+                )?,
+                EloFilter::new(
+                    rating_argument(min_own_elo, "min_own_elo", GetMoveStatsError::InvalidRating)?,
+                    rating_argument(max_own_elo, "max_own_elo", GetMoveStatsError::InvalidRating)?,
+                    rating_argument(
+                        min_opponent_elo,
+                        "min_opponent_elo",
+                        GetMoveStatsError::InvalidRating,
+                    )?,
+                    rating_argument(
+                        max_opponent_elo,
+                        "max_opponent_elo",
+                        GetMoveStatsError::InvalidRating,
+                    )?,
+                ),
             )
             .await
             .map_err(|e| {
-                println!("{}", e.to_string());
+                println!("{}", e);
                 e.into()
             });
 
@@ -64,6 +121,387 @@ impl Query {
             .map(|move_stat| move_stat.into())
             .collect::<_>())
     }
+
+    /// Tree of the lines of up to `depth` moves played from the position, each move with
+    /// its statistics and the position it leads to. Games are selected like for
+    /// `getMoveStats`, and only moves played in at least `minGames` games are kept
+    #[allow(clippy::too_many_arguments)]
+    async fn move_tree(
+        #[graphql(context)] ctx: &GraphQLContext,
+        position_fen: String,
+        depth: i32,
+        min_games: Option<i32>,
+        username: Option<String>,
+        play_as: Option<GraphQLColor>,
+        platform_name: GraphQLPlatformName,
+        from_timestamp_seconds: Option<i32>,
+        to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+        players: Option<Vec<String>>,
+        min_rating: Option<i32>,
+        max_rating: Option<i32>,
+        min_own_elo: Option<i32>,
+        max_own_elo: Option<i32>,
+        min_opponent_elo: Option<i32>,
+        max_opponent_elo: Option<i32>,
+    ) -> FieldResult<GraphQLMoveTree> {
+        let depth = match u8::try_from(depth) {
+            Ok(depth) if (1..=MAX_MOVE_TREE_DEPTH).contains(&depth) => depth,
+            _ => Err(GetMoveTreeError::InvalidDepth(MAX_MOVE_TREE_DEPTH))?,
+        };
+        let min_games =
+            u64::try_from(min_games.unwrap_or(1)).map_err(|_| GetMoveTreeError::InvalidMinGames)?;
+
+        let scope = match (username, play_as, players) {
+            (Some(username), Some(play_as), None) => MoveStatsScope::Player {
+                username,
+                play_as: play_as.into(),
+            },
+            (Some(_), None, _) => Err(GetMoveTreeError::MissingPlayAs)?,
+            (Some(_), _, Some(_)) => Err(GetMoveTreeError::PlayersWithUsername)?,
+            (None, _, players) => MoveStatsScope::AllGames { usernames: players },
+        };
+
+        let move_tree: Result<MoveTree, GetMoveTreeError> = ctx
+            .game_service
+            .get_move_tree(
+                ctx.game_service.parse_fen(position_fen)?,
+                scope,
+                platform_name.into(),
+                game_filter_argument(
+                    from_timestamp_seconds,
+                    to_timestamp_seconds,
+                    time_classes,
+                    rated_only,
+                    rating_argument(min_rating, "min_rating", GetMoveTreeError::InvalidRating)?,
+                    rating_argument(max_rating, "max_rating", GetMoveTreeError::InvalidRating)?,
+                    GetMoveTreeError::InvalidTimestamp,
+                )?,
+                EloFilter::new(
+                    rating_argument(min_own_elo, "min_own_elo", GetMoveTreeError::InvalidRating)?,
+                    rating_argument(max_own_elo, "max_own_elo", GetMoveTreeError::InvalidRating)?,
+                    rating_argument(
+                        min_opponent_elo,
+                        "min_opponent_elo",
+                        GetMoveTreeError::InvalidRating,
+                    )?,
+                    rating_argument(
+                        max_opponent_elo,
+                        "max_opponent_elo",
+                        GetMoveTreeError::InvalidRating,
+                    )?,
+                ),
+                depth,
+                min_games,
+            )
+            .await
+            .map_err(|e| e.into());
+
+        Ok((&move_tree?).into())
+    }
+
+    /// Games of the player whose positions could not be indexed, so their moves are
+    /// missing from the statistics
+    async fn get_game_import_errors(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+    ) -> FieldResult<Vec<GraphQLGameImportError>> {
+        let import_errors: Result<Vec<GameImportError>, GetGameImportErrorsError> = ctx
+            .game_service
+            .get_game_import_errors(username, platform_name.into())
+            .await
+            .map_err(|e| e.into());
+
+        Ok(import_errors?
+            .into_iter()
+            .map(|import_error| import_error.into())
+            .collect::<_>())
+    }
+
+    /// Imports of the player, the latest first
+    async fn get_import_jobs(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+    ) -> FieldResult<Vec<GraphQLImportJob>> {
+        let import_jobs: Result<Vec<ImportJob>, GetImportJobsError> = ctx
+            .game_service
+            .get_import_jobs(username, platform_name.into())
+            .await
+            .map_err(|e| e.into());
+
+        Ok(import_jobs?
+            .into_iter()
+            .map(|import_job| import_job.into())
+            .collect::<_>())
+    }
+
+    /// Players whose games were imported, with the archive their next import continues at
+    async fn tracked_players(
+        #[graphql(context)] ctx: &GraphQLContext,
+        platform_name: Option<GraphQLPlatformName>,
+    ) -> FieldResult<Vec<GraphQLTrackedPlayer>> {
+        let tracked_players: Result<Vec<TrackedPlayer>, GetTrackedPlayersError> = ctx
+            .game_service
+            .get_tracked_players(platform_name.map(|platform_name| platform_name.into()))
+            .await
+            .map_err(|e| e.into());
+
+        Ok(tracked_players?
+            .into_iter()
+            .map(|tracked_player| tracked_player.into())
+            .collect::<_>())
+    }
+
+    /// Statistics over the stored games of the player, null if no game of the player is
+    /// stored
+    async fn player(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+    ) -> FieldResult<Option<GraphQLPlayerProfile>> {
+        let player_profile: Result<Option<PlayerProfile>, GetPlayerProfileError> = ctx
+            .game_service
+            .get_player_profile(username, platform_name.into())
+            .await
+            .map_err(|e| e.into());
+
+        Ok(player_profile?.map(|player_profile| player_profile.into()))
+    }
+
+    /// Results of the games of the player with `playAs` per opening family, the most
+    /// played family first, with a trend split into periods of `trendPeriod`
+    #[allow(clippy::too_many_arguments)]
+    async fn opening_report(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+        play_as: GraphQLColor,
+        from_timestamp_seconds: Option<i32>,
+        to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+        trend_period: Option<GraphQLTrendPeriod>,
+    ) -> FieldResult<Vec<GraphQLOpeningFamilyReport>> {
+        let filter = game_filter_argument(
+            from_timestamp_seconds,
+            to_timestamp_seconds,
+            time_classes,
+            rated_only,
+            None,
+            None,
+            GetOpeningReportError::InvalidTimestamp,
+        )?;
+
+        let opening_report: Result<Vec<OpeningFamilyReport>, GetOpeningReportError> = ctx
+            .game_service
+            .get_opening_report(
+                username,
+                platform_name.into(),
+                play_as.into(),
+                filter,
+                trend_period.map_or(TrendPeriod::Month, |trend_period| trend_period.into()),
+            )
+            .await
+            .map_err(|e| e.into());
+
+        Ok(opening_report?
+            .into_iter()
+            .map(|family_report| family_report.into())
+            .collect::<_>())
+    }
+
+    /// Games of the player, the latest first. With a position, only the games that pass
+    /// through it are listed, optionally only those where `nextMoveUci` was played there
+    #[allow(clippy::too_many_arguments)]
+    async fn games(
+        #[graphql(context)] ctx: &GraphQLContext,
+        username: String,
+        platform_name: GraphQLPlatformName,
+        play_as: Option<GraphQLColor>,
+        result: Option<GraphQLGameResult>,
+        position_fen: Option<String>,
+        next_move_uci: Option<String>,
+        from_timestamp_seconds: Option<i32>,
+        to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+        first: Option<i32>,
+        after: Option<String>,
+    ) -> FieldResult<GraphQLGameConnection> {
+        let position = match (position_fen, next_move_uci) {
+            (Some(position_fen), next_move_uci) => Some(PositionFilter::new(
+                ctx.game_service.parse_fen(position_fen)?,
+                next_move_uci,
+            )),
+            (None, Some(_)) => Err(GetGamesError::NextMoveWithoutPosition)?,
+            (None, None) => None,
+        };
+
+        let filter = game_filter_argument(
+            from_timestamp_seconds,
+            to_timestamp_seconds,
+            time_classes,
+            rated_only,
+            None,
+            None,
+            GetGamesError::InvalidTimestamp,
+        )?;
+
+        let after = match after {
+            Some(after) => Some(after.parse::<GameCursor>().map_err(GetGamesError::from)?),
+            None => None,
+        };
+
+        let game_page: Result<GamePage, GetGamesError> = ctx
+            .game_service
+            .get_games(
+                GameSearch::new(
+                    username,
+                    platform_name.into(),
+                    play_as.map(|play_as| play_as.into()),
+                    result.map(|result| result.into()),
+                    position,
+                    filter,
+                ),
+                after,
+                first
+                    .unwrap_or(DEFAULT_GAME_PAGE_SIZE)
+                    .clamp(1, MAX_GAME_PAGE_SIZE) as usize,
+            )
+            .await
+            .map_err(|e| e.into());
+
+        Ok(game_page?.into())
+    }
+
+    /// Stored game with its moves, null if there is no game with the id
+    async fn game(
+        #[graphql(context)] ctx: &GraphQLContext,
+        id: Uuid,
+    ) -> FieldResult<Option<GraphQLGameDetail>> {
+        let game_detail: Result<Option<GameDetail>, GetGameError> =
+            ctx.game_service.get_game(id).await.map_err(|e| e.into());
+
+        Ok(game_detail?.map(|game_detail| game_detail.into()))
+    }
+
+    /// Move statistics of several players in the same position. Every move played by at
+    /// least one of the players gets a row with an entry per player
+    #[allow(clippy::too_many_arguments)]
+    async fn compare_move_stats(
+        #[graphql(context)] ctx: &GraphQLContext,
+        position_fen: String,
+        players: Vec<GraphQLPlayerRef>,
+        from_timestamp_seconds: Option<i32>,
+        to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+    ) -> FieldResult<Vec<GraphQLMoveComparison>> {
+        if players.len() > MAX_COMPARED_PLAYERS {
+            Err(CompareMoveStatsError::TooManyPlayers(MAX_COMPARED_PLAYERS))?;
+        }
+
+        let move_comparisons: Result<Vec<MoveComparison>, CompareMoveStatsError> = ctx
+            .game_service
+            .compare_move_stats(
+                ctx.game_service.parse_fen(position_fen)?,
+                players.into_iter().map(|player| player.into()).collect(),
+                game_filter_argument(
+                    from_timestamp_seconds,
+                    to_timestamp_seconds,
+                    time_classes,
+                    rated_only,
+                    None,
+                    None,
+                    CompareMoveStatsError::InvalidTimestamp,
+                )?,
+            )
+            .await
+            .map_err(|e| e.into());
+
+        Ok(move_comparisons?
+            .into_iter()
+            .map(|move_comparison| move_comparison.into())
+            .collect::<_>())
+    }
+    /// Stored repertoires by name
+    async fn repertoires(
+        #[graphql(context)] ctx: &GraphQLContext,
+    ) -> FieldResult<Vec<GraphQLRepertoire>> {
+        let repertoires: Result<Vec<Repertoire>, GetRepertoireError> = ctx
+            .game_service
+            .get_repertoires()
+            .await
+            .map_err(|e| e.into());
+
+        Ok(repertoires?
+            .iter()
+            .map(|repertoire| {
+                GraphQLRepertoire::new(
+                    repertoire,
+                    ctx.game_service.export_repertoire_pgn(repertoire),
+                )
+            })
+            .collect::<_>())
+    }
+
+    /// Stored repertoire, null if there is no repertoire with the id
+    async fn repertoire(
+        #[graphql(context)] ctx: &GraphQLContext,
+        id: Uuid,
+    ) -> FieldResult<Option<GraphQLRepertoire>> {
+        let repertoire: Result<Option<Repertoire>, GetRepertoireError> = ctx
+            .game_service
+            .get_repertoire(id)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(repertoire?.map(|repertoire| {
+            GraphQLRepertoire::new(
+                &repertoire,
+                ctx.game_service.export_repertoire_pgn(&repertoire),
+            )
+        }))
+    }
+
+    /// Where the games of the player with the color of the repertoire leave it: the first
+    /// move of each game outside of the repertoire, by the player or by the opponent, with
+    /// the games counted per position and move. Null if there is no repertoire with the id
+    #[allow(clippy::too_many_arguments)]
+    async fn repertoire_deviations(
+        #[graphql(context)] ctx: &GraphQLContext,
+        repertoire_id: Uuid,
+        username: String,
+        platform_name: GraphQLPlatformName,
+        from_timestamp_seconds: Option<i32>,
+        to_timestamp_seconds: Option<i32>,
+        time_classes: Option<Vec<GraphQLTimeClass>>,
+        rated_only: Option<bool>,
+    ) -> FieldResult<Option<GraphQLRepertoireDeviationReport>> {
+        let filter = game_filter_argument(
+            from_timestamp_seconds,
+            to_timestamp_seconds,
+            time_classes,
+            rated_only,
+            None,
+            None,
+            GetRepertoireDeviationsError::InvalidTimestamp,
+        )?;
+
+        let deviation_report: Result<
+            Option<RepertoireDeviationReport>,
+            GetRepertoireDeviationsError,
+        > = ctx
+            .game_service
+            .get_repertoire_deviations(repertoire_id, username, platform_name.into(), filter)
+            .await
+            .map_err(|e| e.into());
+
+        Ok(deviation_report?.map(|deviation_report| deviation_report.into()))
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -74,6 +512,12 @@ enum GetMoveStatsError {
     InvalidFen(#[from] InvalidFenError),
     #[error("Invalid timestamp for column {0}")]
     InvalidTimestamp(String),
+    #[error("Invalid rating for column {0}")]
+    InvalidRating(String),
+    #[error("play_as is required with username")]
+    MissingPlayAs,
+    #[error("players can't be combined with username")]
+    PlayersWithUsername,
 }
 
 impl From<GameRepositoryError> for GetMoveStatsError {
@@ -81,3 +525,221 @@ impl From<GameRepositoryError> for GetMoveStatsError {
         Self::InternalError
     }
 }
+
+/// Builds the game filter from the arguments shared by the queries over games
+fn game_filter_argument<E>(
+    from_timestamp_seconds: Option<i32>,
+    to_timestamp_seconds: Option<i32>,
+    time_classes: Option<Vec<GraphQLTimeClass>>,
+    rated_only: Option<bool>,
+    min_rating: Option<u16>,
+    max_rating: Option<u16>,
+    invalid_timestamp: impl Fn(String) -> E,
+) -> Result<GameFilter, E> {
+    Ok(GameFilter::new(
+        timestamp_argument(
+            from_timestamp_seconds,
+            "from_timestamp_seconds",
+            &invalid_timestamp,
+        )?,
+        timestamp_argument(
+            to_timestamp_seconds,
+            "to_timestamp_seconds",
+            &invalid_timestamp,
+        )?,
+        time_classes.map(|time_classes| {
+            time_classes
+                .into_iter()
+                .map(|time_class| time_class.into())
+                .collect()
+        }),
+        rated_only.unwrap_or_default(),
+        min_rating,
+        max_rating,
+    ))
+}
+
+fn timestamp_argument<E>(
+    timestamp_seconds: Option<i32>,
+    column: &str,
+    invalid_timestamp: impl FnOnce(String) -> E,
+) -> Result<Option<DateTime<Utc>>, E> {
+    match timestamp_seconds {
+        Some(timestamp_seconds) => Ok(Some(
+            DateTime::from_timestamp(timestamp_seconds as i64, 0)
+                .ok_or_else(|| invalid_timestamp(column.to_string()))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+fn rating_argument<E>(
+    rating: Option<i32>,
+    column: &str,
+    invalid_rating: impl FnOnce(String) -> E,
+) -> Result<Option<u16>, E> {
+    rating
+        .map(u16::try_from)
+        .transpose()
+        .map_err(|_| invalid_rating(column.to_string()))
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetMoveTreeError {
+    #[error("Internal error")]
+    InternalError,
+    #[error(transparent)]
+    InvalidFen(#[from] InvalidFenError),
+    #[error("Invalid timestamp for column {0}")]
+    InvalidTimestamp(String),
+    #[error("Invalid rating for column {0}")]
+    InvalidRating(String),
+    #[error("depth must be between 1 and {0}")]
+    InvalidDepth(u8),
+    #[error("min_games must not be negative")]
+    InvalidMinGames,
+    #[error("play_as is required with username")]
+    MissingPlayAs,
+    #[error("players can't be combined with username")]
+    PlayersWithUsername,
+}
+
+impl From<GameRepositoryError> for GetMoveTreeError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetGameImportErrorsError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetGameImportErrorsError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetImportJobsError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetImportJobsError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetTrackedPlayersError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetTrackedPlayersError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetPlayerProfileError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetPlayerProfileError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetOpeningReportError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("Invalid timestamp for column {0}")]
+    InvalidTimestamp(String),
+}
+
+impl From<GameRepositoryError> for GetOpeningReportError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetGamesError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("Invalid timestamp for column {0}")]
+    InvalidTimestamp(String),
+    #[error(transparent)]
+    InvalidCursor(#[from] InvalidGameCursorError),
+    #[error("next_move_uci requires position_fen")]
+    NextMoveWithoutPosition,
+}
+
+impl From<GameRepositoryError> for GetGamesError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetGameError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetGameError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CompareMoveStatsError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("Invalid timestamp for column {0}")]
+    InvalidTimestamp(String),
+    #[error("At most {0} players can be compared")]
+    TooManyPlayers(usize),
+}
+
+impl From<GameRepositoryError> for CompareMoveStatsError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetRepertoireError {
+    #[error("Internal error")]
+    InternalError,
+}
+
+impl From<GameRepositoryError> for GetRepertoireError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum GetRepertoireDeviationsError {
+    #[error("Internal error")]
+    InternalError,
+    #[error("Invalid timestamp for column {0}")]
+    InvalidTimestamp(String),
+}
+
+impl From<GameRepositoryError> for GetRepertoireDeviationsError {
+    fn from(_: GameRepositoryError) -> Self {
+        Self::InternalError
+    }
+}
@@ -2,7 +2,10 @@ mod handlers;
 
 use crate::{
     domain::{game::ports::GameService, platform::ports::PlatformService},
-    inbound::graphql::{Schema, game_update_cache::GameUpdateCache, schema},
+    inbound::{
+        graphql::{Schema, schema},
+        import_worker::ImportWorker,
+    },
 };
 use actix_cors::Cors;
 use actix_web::{
@@ -14,22 +17,23 @@ use actix_web::{
 };
 use anyhow::Context;
 use std::{net::SocketAddr, sync::Arc};
-use tokio::sync::Mutex;
+
+const PGN_UPLOAD_LIMIT_BYTES: usize = 64 * 1024 * 1024;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct HttpServerConfig {
     pub addr: SocketAddr,
 }
 
-struct AppData<GS: GameService, PS: PlatformService> {
+struct AppData<GS: GameService> {
     pub schema: Arc<Schema>,
     pub game_service: Arc<GS>,
-    pub platform_service: Arc<PS>,
-    pub game_update_cache: Arc<Mutex<GameUpdateCache>>,
+    pub import_worker: Arc<ImportWorker>,
 }
 
 pub struct HttpServer {
     server: Server,
+    import_worker: Arc<ImportWorker>,
 }
 
 impl HttpServer {
@@ -39,16 +43,18 @@ impl HttpServer {
         platform_service: PS,
     ) -> anyhow::Result<Self> {
         let game_service_arc = Arc::new(game_service);
-        let platform_service_arc = Arc::new(platform_service);
-        let game_update_cache_arc = Arc::new(Mutex::new(GameUpdateCache::new()));
+        let import_worker_arc = Arc::new(ImportWorker::new(
+            game_service_arc.clone(),
+            Arc::new(platform_service),
+        ));
         Ok(Self {
+            import_worker: import_worker_arc.clone(),
             server: actix_web::HttpServer::new(move || {
                 App::new()
                     .app_data(Data::new(AppData {
                         schema: Arc::new(schema()),
                         game_service: game_service_arc.clone(),
-                        platform_service: platform_service_arc.clone(),
-                        game_update_cache: game_update_cache_arc.clone(),
+                        import_worker: import_worker_arc.clone(),
                     }))
                     .wrap(
                         Cors::default()
@@ -63,12 +69,18 @@ impl HttpServer {
                     .wrap(middleware::Logger::default())
                     .service(
                         web::resource("/subscriptions")
-                            .route(web::get().to(handlers::subscriptions::<GS, PS>)),
+                            .route(web::get().to(handlers::subscriptions::<GS>)),
                     )
                     .service(
                         web::resource("/graphql")
-                            .route(web::post().to(handlers::graphql::<GS, PS>))
-                            .route(web::get().to(handlers::graphql::<GS, PS>)),
+                            .route(web::post().to(handlers::graphql::<GS>))
+                            .route(web::get().to(handlers::graphql::<GS>)),
+                    )
+                    .service(
+                        web::resource("/import/pgn")
+                            // PGN files with the whole history of a player easily exceed the default limit
+                            .app_data(web::PayloadConfig::new(PGN_UPLOAD_LIMIT_BYTES))
+                            .route(web::post().to(handlers::import_pgn::<GS>)),
                     )
                     .service(web::resource("/playground").route(
                         web::get().to(|| handlers::playground("/graphql", "/subscriptions")),
@@ -80,6 +92,15 @@ impl HttpServer {
         })
     }
     pub async fn run(self) -> anyhow::Result<()> {
+        let resumed_amount = self
+            .import_worker
+            .resume_jobs()
+            .await
+            .context("failed to resume import jobs")?;
+        if resumed_amount > 0 {
+            println!("resumed {} unfinished import jobs", resumed_amount);
+        }
+
         self.server
             .await
             .context("received error from running server")?;
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
 use crate::{
-    domain::{game::ports::GameService, platform::ports::PlatformService},
+    domain::game::{models::errors::StoreGamesError, ports::GameService},
     inbound::{graphql::GraphQLContext, http::AppData},
 };
 use actix_http::StatusCode;
@@ -18,6 +18,10 @@ use thiserror::Error;
 enum HttpError {
     #[error("bad request")]
     BadRequest,
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("internal server error")]
+    InternalError,
 }
 
 impl ResponseError for HttpError {
@@ -29,7 +33,8 @@ impl ResponseError for HttpError {
 
     fn status_code(&self) -> StatusCode {
         match *self {
-            HttpError::BadRequest => StatusCode::BAD_REQUEST,
+            HttpError::BadRequest | HttpError::InvalidInput(_) => StatusCode::BAD_REQUEST,
+            HttpError::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
@@ -45,20 +50,16 @@ pub async fn playground(
     }
 }
 
-pub async fn graphql<GS: GameService, PS: PlatformService>(
+pub async fn graphql<GS: GameService>(
     req: HttpRequest,
     payload: web::Payload,
-    app_data: Data<AppData<GS, PS>>,
-) -> Result<HttpResponse, Error>
-where
-    GS: GameService,
-{
+    app_data: Data<AppData<GS>>,
+) -> Result<HttpResponse, Error> {
     graphql_handler(
         &app_data.schema,
         &GraphQLContext::new(
             app_data.game_service.clone(),
-            app_data.platform_service.clone(),
-            app_data.game_update_cache.clone(),
+            app_data.import_worker.clone(),
         ),
         req,
         payload,
@@ -66,18 +67,14 @@ where
     .await
 }
 
-pub async fn subscriptions<GS: GameService, PS: PlatformService>(
+pub async fn subscriptions<GS: GameService>(
     req: HttpRequest,
     stream: web::Payload,
-    app_data: Data<AppData<GS, PS>>,
-) -> Result<HttpResponse, Error>
-where
-    GS: GameService,
-{
+    app_data: Data<AppData<GS>>,
+) -> Result<HttpResponse, Error> {
     let context = GraphQLContext::new(
         app_data.game_service.clone(),
-        app_data.platform_service.clone(),
-        app_data.game_update_cache.clone(),
+        app_data.import_worker.clone(),
     );
 
     let schema = app_data.schema.clone();
@@ -88,3 +85,32 @@ where
 
     subscriptions::ws_handler(req, stream, schema, config).await
 }
+
+#[derive(serde::Serialize)]
+struct ImportPgnResponse {
+    parsed: usize,
+    inserted: usize,
+    skipped: usize,
+    failed: usize,
+}
+
+pub async fn import_pgn<GS: GameService>(
+    body: String,
+    app_data: Data<AppData<GS>>,
+) -> Result<HttpResponse, Error> {
+    let summary = app_data
+        .game_service
+        .import_pgn(&body)
+        .await
+        .map_err(|err| match err {
+            StoreGamesError::InvalidPgn(err) => HttpError::InvalidInput(err.to_string()),
+            _ => HttpError::InternalError,
+        })?;
+
+    Ok(HttpResponse::Ok().json(ImportPgnResponse {
+        parsed: *summary.parsed_amount(),
+        inserted: *summary.inserted_amount(),
+        skipped: *summary.skipped_amount(),
+        failed: *summary.failed_amount(),
+    }))
+}
@@ -0,0 +1,291 @@
+mod game_update_cache;
+
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc, watch};
+
+use crate::{
+    domain::{
+        game::{
+            models::{
+                errors::{GameRepositoryError, StoreGamesError},
+                import_job::{ImportJob, ImportJobStatus},
+                import_progress::ImportProgress,
+            },
+            ports::GameService,
+        },
+        platform::{
+            models::{PlatformError, PlatformName},
+            ports::PlatformService,
+        },
+    },
+    inbound::import_worker::game_update_cache::{GameUpdateCache, GameUpdateIdentifier},
+};
+
+/// Runs the persisted import jobs in the background. Every job is registered in the cache
+/// while it runs, so requests for the same player follow the running job
+pub struct ImportWorker {
+    game_service: Arc<dyn GameService>,
+    platform_service: Arc<dyn PlatformService>,
+    game_update_cache: Mutex<GameUpdateCache>,
+}
+
+impl ImportWorker {
+    pub fn new(
+        game_service: Arc<dyn GameService>,
+        platform_service: Arc<dyn PlatformService>,
+    ) -> Self {
+        Self {
+            game_service,
+            platform_service,
+            game_update_cache: Mutex::new(GameUpdateCache::new()),
+        }
+    }
+
+    /// Starts an import of the new games of the player, or of all games for a full resync,
+    /// or follows the import that is already running. A full resync can't follow a running
+    /// import of the new games only. The receiver starts with the last known progress
+    pub async fn import(
+        self: &Arc<Self>,
+        username: &str,
+        platform_name: PlatformName,
+        full_resync: bool,
+    ) -> Result<watch::Receiver<ImportProgress>, StartImportError> {
+        let (_, progress_rx) = self.start(username, platform_name, full_resync).await?;
+
+        Ok(progress_rx)
+    }
+
+    /// Same as `import`, but also returns the id of the import job
+    pub async fn start(
+        self: &Arc<Self>,
+        username: &str,
+        platform_name: PlatformName,
+        full_resync: bool,
+    ) -> Result<(uuid::Uuid, watch::Receiver<ImportProgress>), StartImportError> {
+        if !self.platform_service.supports_platform(&platform_name) {
+            return Err(StartImportError::UnsupportedPlatform(platform_name));
+        }
+        let request_key = GameUpdateIdentifier::new(username, platform_name);
+
+        let mut cache = self.game_update_cache.lock().await;
+        if let Some(existing) = cache.subscribe(&request_key) {
+            if full_resync && !cache.is_full_resync(&request_key) {
+                return Err(StartImportError::ImportRunning);
+            }
+            return Ok(existing);
+        }
+
+        let import_job = self
+            .game_service
+            .create_import_job(&platform_name, username, full_resync)
+            .await?;
+        let import_job_id = *import_job.id();
+        let (progress_tx, progress_rx, cancel_rx) =
+            cache.start(request_key, import_job_id, full_resync);
+        self.spawn(import_job, progress_tx, cancel_rx);
+
+        Ok((import_job_id, progress_rx))
+    }
+
+    /// Stops the running import with the job id and waits until it is finished. Returns
+    /// false if no import with the id is running
+    pub async fn cancel(&self, import_job_id: &uuid::Uuid) -> bool {
+        let Some(mut progress_rx) = self.game_update_cache.lock().await.cancel(import_job_id)
+        else {
+            return false;
+        };
+
+        // The sender is dropped once the job is finished, which ends the wait as well
+        let _ = progress_rx.wait_for(ImportProgress::is_terminal).await;
+        true
+    }
+
+    /// Deletes the stored games of the player unless an import of the player is running.
+    /// The cache stays locked meanwhile, so no import of the player starts before the
+    /// games are deleted
+    pub async fn delete_player_games(
+        &self,
+        username: String,
+        platform_name: PlatformName,
+    ) -> Result<u64, DeletePlayerGamesError> {
+        let cache = self.game_update_cache.lock().await;
+        if cache
+            .subscribe(&GameUpdateIdentifier::new(&username, platform_name))
+            .is_some()
+        {
+            return Err(DeletePlayerGamesError::ImportRunning);
+        }
+
+        let deleted = self
+            .game_service
+            .delete_player_games(username, platform_name)
+            .await?;
+        drop(cache);
+
+        Ok(deleted)
+    }
+
+    /// Picks up the jobs that are pending or were interrupted by a restart.
+    /// Returns the amount of resumed jobs
+    pub async fn resume_jobs(self: &Arc<Self>) -> Result<usize, GameRepositoryError> {
+        let import_jobs = self.game_service.get_unfinished_import_jobs().await?;
+        let resumed_amount = import_jobs.len();
+
+        let mut cache = self.game_update_cache.lock().await;
+        for import_job in import_jobs {
+            let (progress_tx, _, cancel_rx) = cache.start(
+                GameUpdateIdentifier::new(import_job.username(), *import_job.platform_name()),
+                *import_job.id(),
+                *import_job.full_resync(),
+            );
+            self.spawn(import_job, progress_tx, cancel_rx);
+        }
+
+        Ok(resumed_amount)
+    }
+
+    fn spawn(
+        self: &Arc<Self>,
+        import_job: ImportJob,
+        progress_tx: watch::Sender<ImportProgress>,
+        cancel_rx: watch::Receiver<bool>,
+    ) {
+        let worker = self.clone();
+        tokio::spawn(async move { worker.run(import_job, progress_tx, cancel_rx).await });
+    }
+
+    async fn run(
+        &self,
+        import_job: ImportJob,
+        progress_tx: watch::Sender<ImportProgress>,
+        mut cancel_rx: watch::Receiver<bool>,
+    ) {
+        let mut progress = ImportProgress::new();
+
+        // Cancelling drops the import between two database writes, so the archives that
+        // were committed before are kept
+        let import_result = tokio::select! {
+            import_result = self.import_games(&import_job, &mut progress, &progress_tx) => {
+                Some(import_result)
+            }
+            Ok(_) = cancel_rx.wait_for(|cancelled| *cancelled) => None,
+        };
+        let status = match import_result {
+            None => {
+                progress.cancel();
+                ImportJobStatus::Cancelled
+            }
+            Some(Ok(())) => {
+                progress.finish();
+                ImportJobStatus::Done
+            }
+            Some(Err(err)) => {
+                eprintln!(
+                    "failed to import games of {}: {}",
+                    import_job.username(),
+                    err
+                );
+                progress.fail(err.to_string());
+                ImportJobStatus::Failed
+            }
+        };
+
+        // A job that can't be marked as finished stays unfinished and runs again on restart
+        let _ = self
+            .game_service
+            .update_import_job_status(
+                import_job.id(),
+                &status,
+                progress.error().map(String::as_str),
+            )
+            .await;
+
+        // Terminal event, the streams end once the sender is dropped. The job is removed
+        // while holding the lock so that no request subscribes to it in between
+        let mut cache = self.game_update_cache.lock().await;
+        progress_tx.send_replace(progress);
+        cache.finish(&GameUpdateIdentifier::new(
+            import_job.username(),
+            *import_job.platform_name(),
+        ));
+    }
+
+    /// Fetches the games played since the job started or the last committed archive and
+    /// stores them, publishing every change of the progress
+    async fn import_games(
+        &self,
+        import_job: &ImportJob,
+        progress: &mut ImportProgress,
+        progress_tx: &watch::Sender<ImportProgress>,
+    ) -> Result<(), ImportGamesError> {
+        self.game_service
+            .update_import_job_status(import_job.id(), &ImportJobStatus::Running, None)
+            .await?;
+
+        // Resolve the archives to download from the platform
+        let (archive_count, game_stream) = self
+            .platform_service
+            .fetch_games(
+                import_job.username().clone(),
+                import_job.resume_timestamp_seconds(),
+                *import_job.platform_name(),
+            )
+            .await?;
+        progress.start_download(archive_count);
+        progress_tx.send_replace(progress.clone());
+
+        // Store games while reporting progress
+        let (step_tx, mut step_rx) = mpsc::channel(1000);
+        let report_progress = async {
+            while let Some(store_progress) = step_rx.recv().await {
+                progress.apply(&store_progress);
+                progress_tx.send_replace(progress.clone());
+            }
+        };
+        let (store_result, ()) = tokio::join!(
+            self.game_service
+                .store_games(Some(import_job), game_stream, step_tx),
+            report_progress
+        );
+
+        Ok(store_result?)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StartImportError {
+    #[error("Games can't be imported from platform {0:?}")]
+    UnsupportedPlatform(PlatformName),
+    #[error("An import of the new games of the player is running, cancel it to resync")]
+    ImportRunning,
+    #[error("Internal database error")]
+    GameRepositoryError(#[from] GameRepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeletePlayerGamesError {
+    #[error("An import of the player is running")]
+    ImportRunning,
+    #[error("Internal database error")]
+    GameRepositoryError(#[from] GameRepositoryError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImportGamesError {
+    #[error("Failed to load games from platform: {0}")]
+    PlatformError(#[from] PlatformError),
+    #[error("Internal database error")]
+    GameRepositoryError(#[from] GameRepositoryError),
+    #[error("Unknown error")]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<StoreGamesError> for ImportGamesError {
+    fn from(value: StoreGamesError) -> Self {
+        match value {
+            StoreGamesError::PlatformError(err) => Self::PlatformError(err),
+            StoreGamesError::GameRepositoryError(err) => Self::GameRepositoryError(err),
+            err => Self::Unknown(anyhow::anyhow!(err)),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use tokio::sync::watch::{self, Receiver, Sender};
+
+use crate::domain::{
+    game::models::import_progress::ImportProgress, platform::models::PlatformName,
+};
+
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub struct GameUpdateIdentifier {
+    username: String,
+    platform_name: PlatformName,
+}
+
+impl GameUpdateIdentifier {
+    /// Usernames are case-insensitive on every platform
+    pub fn new(username: &str, platform_name: PlatformName) -> Self {
+        Self {
+            username: username.to_lowercase(),
+            platform_name,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RunningImport {
+    import_job_id: uuid::Uuid,
+    full_resync: bool,
+    progress_rx: Receiver<ImportProgress>,
+    /// set to true to ask the import to stop
+    cancel_tx: Sender<bool>,
+}
+
+/// Registry of the running game imports, so that identical requests share one import
+/// instead of fetching the same games twice
+#[derive(Debug, Default)]
+pub struct GameUpdateCache {
+    jobs: HashMap<GameUpdateIdentifier, RunningImport>,
+}
+
+impl GameUpdateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the job id and a receiver of the running import of the player, which starts
+    /// with the last known progress. Finished imports are never returned, so the next request
+    /// starts a new import
+    pub fn subscribe(
+        &self,
+        id: &GameUpdateIdentifier,
+    ) -> Option<(uuid::Uuid, Receiver<ImportProgress>)> {
+        self.jobs
+            .get(id)
+            .filter(|running_import| !running_import.progress_rx.borrow().is_terminal())
+            .map(|running_import| {
+                (
+                    running_import.import_job_id,
+                    running_import.progress_rx.clone(),
+                )
+            })
+    }
+
+    /// Whether the running import of the player downloads every game again
+    pub fn is_full_resync(&self, id: &GameUpdateIdentifier) -> bool {
+        self.jobs
+            .get(id)
+            .is_some_and(|running_import| running_import.full_resync)
+    }
+
+    /// Registers a new import of the player, replacing a finished one. The import stops once
+    /// the cancel receiver turns true
+    pub fn start(
+        &mut self,
+        id: GameUpdateIdentifier,
+        import_job_id: uuid::Uuid,
+        full_resync: bool,
+    ) -> (
+        Sender<ImportProgress>,
+        Receiver<ImportProgress>,
+        Receiver<bool>,
+    ) {
+        let (progress_tx, progress_rx) = watch::channel(ImportProgress::new());
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        self.jobs.insert(
+            id,
+            RunningImport {
+                import_job_id,
+                full_resync,
+                progress_rx: progress_rx.clone(),
+                cancel_tx,
+            },
+        );
+        (progress_tx, progress_rx, cancel_rx)
+    }
+
+    /// Asks the running import with the job id to stop and returns a receiver of its
+    /// progress, none if no import with the id is running
+    pub fn cancel(&self, import_job_id: &uuid::Uuid) -> Option<Receiver<ImportProgress>> {
+        let running_import = self.jobs.values().find(|running_import| {
+            running_import.import_job_id == *import_job_id
+                && !running_import.progress_rx.borrow().is_terminal()
+        })?;
+        running_import.cancel_tx.send_replace(true);
+
+        Some(running_import.progress_rx.clone())
+    }
+
+    /// Removes the import of the player once it sent its terminal progress
+    pub fn finish(&mut self, id: &GameUpdateIdentifier) {
+        self.jobs.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finished_jobs_are_not_shared() {
+        let mut cache = GameUpdateCache::new();
+        let id = GameUpdateIdentifier::new("User", PlatformName::Lichess);
+        assert_eq!(id, GameUpdateIdentifier::new("user", PlatformName::Lichess));
+        assert!(cache.subscribe(&id).is_none());
+
+        let (progress_tx, _, _) = cache.start(id.clone(), uuid::Uuid::nil(), true);
+        assert!(cache.is_full_resync(&id));
+        let mut progress = ImportProgress::new();
+        progress.start_download(3);
+        progress_tx.send_replace(progress.clone());
+        let (import_job_id, progress_rx) = cache.subscribe(&id).unwrap();
+        assert_eq!(import_job_id, uuid::Uuid::nil());
+        assert_eq!(progress_rx.borrow().archives_total(), &3);
+
+        progress.finish();
+        progress_tx.send_replace(progress);
+        assert!(cache.subscribe(&id).is_none());
+
+        cache.finish(&id);
+        assert!(cache.subscribe(&id).is_none());
+    }
+
+    #[test]
+    fn test_cancel_running_job() {
+        let mut cache = GameUpdateCache::new();
+        let id = GameUpdateIdentifier::new("user", PlatformName::ChessCom);
+        let import_job_id = uuid::Uuid::from_u128(1);
+        let (progress_tx, _, cancel_rx) = cache.start(id.clone(), import_job_id, false);
+        assert!(!cache.is_full_resync(&id));
+
+        assert!(cache.cancel(&uuid::Uuid::from_u128(2)).is_none());
+        assert!(!*cancel_rx.borrow());
+        assert!(cache.cancel(&import_job_id).is_some());
+        assert!(*cancel_rx.borrow());
+
+        let mut progress = ImportProgress::new();
+        progress.cancel();
+        progress_tx.send_replace(progress);
+        assert!(cache.cancel(&import_job_id).is_none());
+    }
+}
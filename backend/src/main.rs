@@ -4,11 +4,15 @@ pub mod outbound;
 
 use crate::{
     domain::{
-        game,
+        game::{self, models::opening::OpeningBook},
         platform::{self, models::PlatformName, service::PlatformApiClientMap},
     },
     inbound::http::{HttpServer, HttpServerConfig},
-    outbound::{fen_validator, platforms::chesscom::ChessComClient, postgres::Postgres},
+    outbound::{
+        fen_validator, opening_line_keyer, pgn_parser,
+        platforms::{chesscom::ChessComClient, lichess::LichessClient},
+        postgres::Postgres,
+    },
 };
 use std::{env, net::SocketAddr, str::FromStr};
 
@@ -25,9 +29,10 @@ async fn main() -> anyhow::Result<()> {
 
     // Prepare the Game Service
     let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set");
-    let postgres = Postgres::new(database_url).await?;
+    let opening_book = OpeningBook::new(&opening_line_keyer::Keyer);
+    let postgres = Postgres::new(database_url, opening_book).await?;
     let fen_validator = fen_validator::Validator;
-    let game_service = game::service::Service::new(postgres, fen_validator);
+    let game_service = game::service::Service::new(postgres, fen_validator, pgn_parser::Parser);
 
     // Prepare the Platform Service
     let platform_api_client_map = construct_platform_api_client_map();
@@ -42,6 +47,7 @@ fn construct_platform_api_client_map() -> PlatformApiClientMap {
     let mut client_map = PlatformApiClientMap::new();
 
     client_map.insert(PlatformName::ChessCom, Box::new(ChessComClient::new()));
+    client_map.insert(PlatformName::Lichess, Box::new(LichessClient::new()));
 
     client_map
 }
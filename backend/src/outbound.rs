@@ -1,5 +1,10 @@
 pub mod fen_validator;
 pub mod join_set_limited;
+pub mod move_tree;
+pub mod opening_line_keyer;
+pub mod pgn_parser;
 pub mod platforms;
+pub mod position_key;
 pub mod position_visitor;
 pub mod postgres;
+pub mod repertoire_pgn;
@@ -1,11 +1,10 @@
-use std::str::FromStr;
-
-use crate::domain::game::models::fen::FenValidator;
+use crate::{domain::game::models::fen::FenValidator, outbound::position_key};
 
 pub struct Validator;
 
 impl FenValidator for Validator {
     fn is_valid_fen(&self, fen: &str) -> bool {
-        shakmaty::fen::Fen::from_str(fen).is_ok()
+        // Positions are looked up by their key, so the FEN has to describe a legal position
+        position_key::from_fen(fen).is_ok()
     }
 }
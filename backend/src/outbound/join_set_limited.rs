@@ -40,6 +40,6 @@ impl<T: Send + 'static, U: Future<Output = T> + Send + 'static, V: Iterator<Item
             self.join_set.spawn(next_task);
         }
 
-        return next;
+        next
     }
 }
@@ -0,0 +1,148 @@
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position as _, san::SanPlus, uci::UciMove};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::domain::game::models::{
+    errors::InvalidFenError,
+    fen::Fen,
+    move_stat::MoveStat,
+    move_tree::{MoveTree, MoveTreeBranch},
+};
+
+/// Statistics of the last move of a line of UCI moves played from the root position
+pub struct MoveTreeLine {
+    pub moves: Vec<String>,
+    pub stat: MoveStat,
+}
+
+/// Builds the tree of the lines, replaying them from the root to get the SAN of the moves
+/// and the positions they lead to. Lines continuing a move without statistics of its own
+/// are left out
+pub fn build(root_fen: &Fen, lines: Vec<MoveTreeLine>) -> Result<MoveTree, InvalidFenError> {
+    let chess: Chess = shakmaty::fen::Fen::from_str(&root_fen.to_string())
+        .map_err(|_| InvalidFenError)?
+        .into_position(CastlingMode::Standard)
+        .map_err(|_| InvalidFenError)?;
+
+    Ok(build_node(chess, root_fen.clone(), lines))
+}
+
+fn build_node(chess: Chess, fen: Fen, lines: Vec<MoveTreeLine>) -> MoveTree {
+    let (mut moves, continuations): (Vec<_>, Vec<_>) =
+        lines.into_iter().partition(|line| line.moves.len() == 1);
+    moves.sort_by(|a, b| b.stat.total().cmp(a.stat.total()));
+
+    let mut continuations_by_move: HashMap<String, Vec<MoveTreeLine>> = HashMap::new();
+    for mut line in continuations {
+        let first_move = line.moves.remove(0);
+        continuations_by_move
+            .entry(first_move)
+            .or_default()
+            .push(line);
+    }
+
+    let branches = moves
+        .into_iter()
+        .filter_map(|line| {
+            let uci = &line.moves[0];
+            // Moves of games of other variants can't be replayed with standard rules
+            let mv = UciMove::from_str(uci).ok()?.to_move(&chess).ok()?;
+            let san = SanPlus::from_move(chess.clone(), mv);
+            let next_chess = chess.clone().play(mv).ok()?;
+            let next_fen = shakmaty::fen::Fen::from_position(&next_chess, EnPassantMode::Always);
+            let continuations = continuations_by_move.remove(uci).unwrap_or_default();
+
+            Some(MoveTreeBranch::new(
+                san.to_string(),
+                line.stat,
+                build_node(next_chess, next_fen.into(), continuations),
+            ))
+        })
+        .collect();
+
+    MoveTree::new(fen, branches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn line(moves: &[&str], total: u64) -> MoveTreeLine {
+        MoveTreeLine {
+            moves: moves.iter().map(|uci| uci.to_string()).collect(),
+            stat: MoveStat::new(
+                moves.last().unwrap().to_string(),
+                total,
+                0,
+                0,
+                total,
+                0.0,
+                1500,
+                1500,
+                1100,
+                DateTime::UNIX_EPOCH,
+                DateTime::UNIX_EPOCH,
+                0,
+                0,
+                1500,
+                1500,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_build() {
+        let root_fen =
+            Fen::new_unchecked("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        let tree = build(
+            &root_fen,
+            vec![
+                line(&["e2e4", "c7c5"], 2),
+                line(&["d2d4"], 1),
+                line(&["e2e4"], 3),
+                line(&["e2e4", "e7e5"], 1),
+                line(&["d2d4", "g8f6"], 1),
+                line(&["c2c4", "e7e5"], 1),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(tree.fen(), &root_fen);
+        let moves: Vec<(&str, u64)> = tree
+            .branches()
+            .iter()
+            .map(|branch| (branch.san().as_str(), *branch.stat().total()))
+            .collect();
+        assert_eq!(moves, vec![("e4", 3), ("d4", 1)]);
+
+        let e4 = &tree.branches()[0];
+        assert_eq!(
+            e4.tree().fen().to_string(),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+        let replies: Vec<&str> = e4
+            .tree()
+            .branches()
+            .iter()
+            .map(|branch| branch.san().as_str())
+            .collect();
+        assert_eq!(replies, vec!["c5", "e5"]);
+        assert!(e4.tree().branches()[0].tree().branches().is_empty());
+    }
+
+    #[test]
+    fn test_build_skips_illegal_moves() {
+        let root_fen =
+            Fen::new_unchecked("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        let tree = build(&root_fen, vec![line(&["e2e5"], 1), line(&["g1f3"], 1)]).unwrap();
+
+        let moves: Vec<&str> = tree
+            .branches()
+            .iter()
+            .map(|branch| branch.san().as_str())
+            .collect();
+        assert_eq!(moves, vec!["Nf3"]);
+    }
+}
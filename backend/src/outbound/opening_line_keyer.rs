@@ -0,0 +1,42 @@
+use shakmaty::{Chess, Position as _, san::SanPlus};
+use std::str::FromStr;
+
+use crate::{domain::game::models::opening::OpeningLineKeyer, outbound::position_key};
+
+pub struct Keyer;
+
+impl OpeningLineKeyer for Keyer {
+    fn line_key(&self, san_moves: &[&str]) -> Option<i64> {
+        let mut chess = Chess::new();
+        for san_move in san_moves {
+            let mv = SanPlus::from_str(san_move).ok()?.san.to_move(&chess).ok()?;
+            chess = chess.play(mv).ok()?;
+        }
+
+        Some(position_key::from_chess(&chess))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::game::models::opening::{OpeningBook, book_lines};
+
+    #[test]
+    fn test_book_lines_can_be_played() {
+        for (opening, san_moves) in book_lines() {
+            assert!(Keyer.line_key(&san_moves).is_some(), "{:?}", opening);
+        }
+    }
+
+    #[test]
+    fn test_book_classifies_transpositions() {
+        let book = OpeningBook::new(&Keyer);
+        let key = Keyer.line_key(&["Nf3", "Nf6", "d4", "e6", "c4"]).unwrap();
+
+        let opening = book.opening(&key).unwrap();
+
+        assert_eq!(opening.eco(), "E10");
+        assert_eq!(opening.name(), "Indian Defense: Anti-Nimzo-Indian");
+    }
+}
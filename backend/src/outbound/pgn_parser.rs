@@ -0,0 +1,381 @@
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use md5::{Digest, Md5};
+use pgn_reader::{Outcome, RawComment, RawTag, Reader, SanPlus, Skip, Visitor};
+use std::{io, ops::ControlFlow};
+
+use crate::{
+    domain::{
+        game::models::{
+            errors::InvalidPgnError,
+            game::{Color, TimeClass, Variant},
+            new_game::NewGame,
+            pgn::PgnParser,
+            repertoire::{Repertoire, RepertoireMove},
+        },
+        platform::models::PlatformName,
+    },
+    outbound::repertoire_pgn,
+};
+
+pub struct Parser;
+
+impl PgnParser for Parser {
+    fn parse_games(&self, pgn: &str) -> Result<Vec<NewGame>, InvalidPgnError> {
+        let mut reader = Reader::new(io::Cursor::new(pgn));
+
+        let games = reader
+            .read_games(&mut GameVisitor)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| InvalidPgnError(e.to_string()))?
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        if games.is_empty() {
+            return Err(InvalidPgnError("no games found".to_string()));
+        }
+
+        Ok(games)
+    }
+
+    fn parse_repertoire_moves(&self, pgn: &str) -> Result<Vec<RepertoireMove>, InvalidPgnError> {
+        repertoire_pgn::read_moves(pgn)
+    }
+
+    fn write_repertoire(&self, repertoire: &Repertoire) -> String {
+        repertoire_pgn::write(repertoire)
+    }
+}
+
+/// Collects the tags and the mainline of a single game, so that every game of a
+/// multi-game file can be stored as a standalone PGN
+struct GameVisitor;
+
+struct GameMovetext {
+    tags: Vec<(String, String)>,
+    movetext: String,
+    ply: usize,
+    outcome: Option<Outcome>,
+}
+
+impl GameMovetext {
+    fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(tag_name, _)| tag_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn push_token(&mut self, token: &str) {
+        if !self.movetext.is_empty() {
+            self.movetext.push(' ');
+        }
+        self.movetext.push_str(token);
+    }
+
+    fn winner(&self) -> Option<Color> {
+        match self.tag("Result") {
+            Some("1-0") => Some(Color::White),
+            Some("0-1") => Some(Color::Black),
+            Some(_) => None,
+            None => self
+                .outcome
+                .and_then(|outcome| outcome.winner())
+                .map(|winner| match winner {
+                    shakmaty::Color::White => Color::White,
+                    shakmaty::Color::Black => Color::Black,
+                }),
+        }
+    }
+
+    fn elo(&self, name: &str) -> i16 {
+        self.tag(name)
+            .and_then(|elo| elo.trim().parse::<i16>().ok())
+            .unwrap_or_default()
+    }
+
+    /// Combines `UTCDate`/`Date` with `UTCTime`/`EndTime`, falling back to midnight
+    fn finished_at(&self) -> DateTime<Utc> {
+        let date = self
+            .tag("UTCDate")
+            .or(self.tag("Date"))
+            .and_then(|date| NaiveDate::parse_from_str(date.trim(), "%Y.%m.%d").ok());
+        let time = self
+            .tag("UTCTime")
+            .or(self.tag("EndTime"))
+            .and_then(|time| time.split_whitespace().next())
+            .and_then(|time| NaiveTime::parse_from_str(time, "%H:%M:%S").ok())
+            .unwrap_or_default();
+
+        match date {
+            Some(date) => date.and_time(time).and_utc(),
+            None => DateTime::UNIX_EPOCH,
+        }
+    }
+
+    fn time_control(&self) -> Option<String> {
+        self.tag("TimeControl")
+            .map(str::trim)
+            .filter(|time_control| {
+                !time_control.is_empty() && *time_control != "-" && *time_control != "?"
+            })
+            .map(str::to_string)
+    }
+
+    /// Platform exports name rated games in the `Event` tag, other games count as rated
+    /// when both players have a rating
+    fn rated(&self) -> bool {
+        match self.tag("Event") {
+            Some(event) if event.starts_with("Rated") => true,
+            Some(event) if event.starts_with("Casual") => false,
+            _ => self.elo("WhiteElo") > 0 && self.elo("BlackElo") > 0,
+        }
+    }
+
+    fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+        for (name, value) in &self.tags {
+            pgn.push_str(&format!(
+                "[{} \"{}\"]\n",
+                name,
+                value.replace('\\', "\\\\").replace('"', "\\\"")
+            ));
+        }
+        pgn.push('\n');
+        pgn.push_str(&self.movetext);
+        pgn.push(' ');
+        pgn.push_str(
+            self.tag("Result")
+                .unwrap_or(self.outcome.map(|outcome| outcome.as_str()).unwrap_or("*")),
+        );
+        pgn
+    }
+}
+
+impl Visitor for GameVisitor {
+    type Tags = Vec<(String, String)>;
+    type Movetext = GameMovetext;
+    /// `None` for games without any moves
+    type Output = Option<NewGame>;
+
+    fn begin_tags(&mut self) -> ControlFlow<Self::Output, Self::Tags> {
+        ControlFlow::Continue(Vec::new())
+    }
+
+    fn tag(
+        &mut self,
+        tags: &mut Self::Tags,
+        name: &[u8],
+        value: RawTag<'_>,
+    ) -> ControlFlow<Self::Output> {
+        tags.push((
+            String::from_utf8_lossy(name).to_string(),
+            value.decode_utf8_lossy().to_string(),
+        ));
+        ControlFlow::Continue(())
+    }
+
+    fn begin_movetext(&mut self, tags: Self::Tags) -> ControlFlow<Self::Output, Self::Movetext> {
+        // Games set up from a position may start with black to move
+        let ply = tags
+            .iter()
+            .find(|(name, _)| name == "FEN")
+            .and_then(|(_, fen)| {
+                let mut fields = fen.split_whitespace().skip(1);
+                let turn = fields.next()?;
+                let fullmoves = fields.nth(3)?.parse::<usize>().ok()?;
+                Some((fullmoves.max(1) - 1) * 2 + usize::from(turn == "b"))
+            })
+            .unwrap_or_default();
+
+        ControlFlow::Continue(GameMovetext {
+            tags,
+            movetext: String::new(),
+            ply,
+            outcome: None,
+        })
+    }
+
+    fn san(
+        &mut self,
+        movetext: &mut Self::Movetext,
+        san_plus: SanPlus,
+    ) -> ControlFlow<Self::Output> {
+        let move_number = movetext.ply / 2 + 1;
+        if movetext.ply % 2 == 0 {
+            movetext.push_token(&format!("{}.", move_number));
+        } else if movetext.movetext.is_empty() || movetext.movetext.ends_with('}') {
+            movetext.push_token(&format!("{}...", move_number));
+        }
+        movetext.push_token(&san_plus.to_string());
+        movetext.ply += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn comment(
+        &mut self,
+        movetext: &mut Self::Movetext,
+        comment: RawComment<'_>,
+    ) -> ControlFlow<Self::Output> {
+        movetext.push_token(&format!(
+            "{{{}}}",
+            String::from_utf8_lossy(comment.as_bytes())
+        ));
+        ControlFlow::Continue(())
+    }
+
+    fn begin_variation(
+        &mut self,
+        _movetext: &mut Self::Movetext,
+    ) -> ControlFlow<Self::Output, Skip> {
+        ControlFlow::Continue(Skip(true)) // stay in the mainline
+    }
+
+    fn outcome(
+        &mut self,
+        movetext: &mut Self::Movetext,
+        outcome: Outcome,
+    ) -> ControlFlow<Self::Output> {
+        movetext.outcome = Some(outcome);
+        ControlFlow::Continue(())
+    }
+
+    fn end_game(&mut self, movetext: Self::Movetext) -> Self::Output {
+        if movetext.movetext.is_empty() {
+            return None;
+        }
+
+        // PGN files often only record the date of a game, so games are told apart by
+        // their content
+        let pgn = movetext.to_pgn();
+        let source_id = format!("{:x}", Md5::digest(pgn.as_bytes()));
+
+        Some(NewGame::new(
+            movetext.tag("White").unwrap_or("?").to_string(),
+            movetext.elo("WhiteElo"),
+            movetext.tag("Black").unwrap_or("?").to_string(),
+            movetext.elo("BlackElo"),
+            movetext.winner(),
+            PlatformName::Pgn,
+            pgn,
+            movetext.finished_at(),
+            movetext
+                .time_control()
+                .and_then(|time_control| TimeClass::from_time_control(&time_control)),
+            movetext.time_control(),
+            movetext.rated(),
+            Variant::from_name(movetext.tag("Variant").unwrap_or_default()),
+            Some(source_id),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_multiple_games() {
+        let pgn = r#"
+            [Event "Club championship"]
+            [Date "2019.03.02"]
+            [White "Smith, John"]
+            [Black "Doe, Jane"]
+            [Result "0-1"]
+            [WhiteElo "1850"]
+            [BlackElo "1920"]
+
+            1. e4 {Best by test} c5 (1... e5 2. Nf3) 2. Nf3 d6 0-1
+
+            [Event "Club championship"]
+            [Date "2019.03.09"]
+            [EndTime "21:15:03 GMT+0000"]
+            [TimeControl "600+5"]
+            [White "Doe, Jane"]
+            [Black "Smith, John"]
+            [Result "1/2-1/2"]
+
+            1. d4 d5 2. c4 1/2-1/2
+            "#;
+
+        let games = Parser.parse_games(pgn).unwrap();
+
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].white(), "Smith, John");
+        assert_eq!(*games[0].white_elo(), 1850);
+        assert_eq!(games[0].black(), "Doe, Jane");
+        assert_eq!(*games[0].black_elo(), 1920);
+        assert_eq!(games[0].winner(), Some(&Color::Black));
+        assert_eq!(*games[0].platform_name(), PlatformName::Pgn);
+        assert_eq!(
+            games[0].finished_at().to_rfc3339(),
+            "2019-03-02T00:00:00+00:00"
+        );
+        assert_eq!(
+            games[0].pgn(),
+            "[Event \"Club championship\"]\n[Date \"2019.03.02\"]\n[White \"Smith, John\"]\n[Black \"Doe, Jane\"]\n[Result \"0-1\"]\n[WhiteElo \"1850\"]\n[BlackElo \"1920\"]\n\n1. e4 {Best by test} 1... c5 2. Nf3 d6 0-1"
+        );
+
+        assert!(*games[0].rated());
+        assert_eq!(games[0].time_class(), None);
+
+        assert_eq!(games[1].winner(), None);
+        assert_eq!(*games[1].white_elo(), 0);
+        assert!(!*games[1].rated());
+        assert_eq!(games[1].time_class(), Some(&TimeClass::Rapid));
+        assert_eq!(games[1].time_control().map(String::as_str), Some("600+5"));
+        assert_eq!(
+            games[1].finished_at().to_rfc3339(),
+            "2019-03-09T21:15:03+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_game_from_position() {
+        let pgn = r#"
+            [White "A"]
+            [Black "B"]
+            [SetUp "1"]
+            [FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12"]
+
+            12... Kd7 13. e4 *
+            "#;
+
+        let games = Parser.parse_games(pgn).unwrap();
+
+        assert_eq!(games.len(), 1);
+        assert!(games[0].pgn().ends_with("\n\n12... Kd7 13. e4 *"));
+        assert_eq!(*games[0].finished_at(), DateTime::UNIX_EPOCH);
+        assert_eq!(*games[0].variant(), Variant::Standard);
+    }
+
+    #[test]
+    fn test_parse_games_on_same_date() {
+        let game = |moves: &str| {
+            format!(
+                "[Date \"2019.03.02\"]\n[White \"A\"]\n[Black \"B\"]\n[Result \"*\"]\n\n{} *\n\n",
+                moves
+            )
+        };
+        let pgn = [game("1. e4 e5"), game("1. d4 d5"), game("1. e4 e5")].concat();
+
+        let games = Parser.parse_games(&pgn).unwrap();
+
+        assert_eq!(games.len(), 3);
+        assert_eq!(games[0].finished_at(), games[1].finished_at());
+        assert_ne!(games[0].source_id(), games[1].source_id());
+        assert_eq!(games[0].source_id(), games[2].source_id());
+        assert_eq!(
+            games[0].source_id().map(String::as_str),
+            Some(format!("{:x}", Md5::digest(games[0].pgn().as_bytes()))).as_deref()
+        );
+    }
+
+    #[test]
+    fn test_parse_without_games() {
+        let actual = Parser.parse_games("not a pgn");
+
+        assert_eq!(actual, Err(InvalidPgnError("no games found".to_string())));
+    }
+}
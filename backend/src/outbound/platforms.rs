@@ -1 +1,2 @@
 pub mod chesscom;
+pub mod lichess;
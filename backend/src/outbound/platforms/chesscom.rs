@@ -1,23 +1,37 @@
 use crate::domain::{
-    game::models::game::Color,
+    game::models::game::{Color, TimeClass, Variant},
     game::models::new_game::NewGame,
     platform::{
-        models::{PlatformError, PlatformName},
+        models::{ArchiveMonth, GameBatch, PlatformError, PlatformName},
         ports::PlatformApiClient,
     },
 };
-use chrono::{DateTime, Datelike};
+use chrono::DateTime;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
 use std::time::Duration;
 use tokio::sync::mpsc::{Receiver, channel};
 
+const CHESSCOM_BASE_URL: &str = "https://api.chess.com";
+
 pub struct ChessComClient {
     client: ClientWithMiddleware,
+    base_url: String,
+}
+
+impl Default for ChessComClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ChessComClient {
     pub fn new() -> Self {
+        Self::with_base_url(CHESSCOM_BASE_URL)
+    }
+
+    /// Used to point the client to a different Chess.com API, e.g. a mock server
+    pub fn with_base_url(base_url: &str) -> Self {
         let mut headers = reqwest::header::HeaderMap::new();
         // Set user agent to avoid 403 Forbidden errors
         headers.insert(
@@ -36,17 +50,17 @@ impl ChessComClient {
         // Retry failed requests.
         .with(RetryTransientMiddleware::new_with_policy(retry_policy))
         .build();
-        Self { client }
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
     }
 
     async fn fetch_player_archives(
         &self,
         username: String,
     ) -> Result<ChessComPlayerArchivesResponse, PlatformError> {
-        let url = format!(
-            "https://api.chess.com/pub/player/{}/games/archives",
-            username
-        );
+        let url = format!("{}/pub/player/{}/games/archives", self.base_url, username);
 
         let response = self
             .client
@@ -66,12 +80,13 @@ impl ChessComClient {
     fn fetch_games_by_archives(
         &self,
         archives: Vec<String>,
-    ) -> Receiver<Result<Vec<NewGame>, PlatformError>> {
+    ) -> Receiver<Result<GameBatch, PlatformError>> {
         let (sender, receiver) = channel(1000);
 
         let client = self.client.clone();
         tokio::spawn(async move {
             for archive_url in archives {
+                let archive_month = Self::archive_month(&archive_url);
                 let response_result = client.get(&archive_url).send().await;
                 let response = match response_result {
                     Ok(response) => response,
@@ -89,11 +104,9 @@ impl ChessComClient {
                             .json()
                             .await
                             .map_err(|e| PlatformError::ParseError(e.to_string())),
-                        Err(_) => {
-                            // Ignore if Chess.com fails to resolve request
-                            let _ = sender.send(Ok(Vec::new())).await;
-                            continue;
-                        }
+                        // The import stops at the archive, so that the next sync downloads it
+                        // again instead of continuing after it
+                        Err(e) => Err(PlatformError::ApiError(e.to_string())),
                     };
 
                 match archive_result {
@@ -103,12 +116,10 @@ impl ChessComClient {
                             .into_iter()
                             .map(|game| game.into())
                             .collect::<Vec<NewGame>>();
-                        let send_result = sender.send(Ok(games)).await;
-                        match send_result {
-                            Err(_) => {
-                                return;
-                            }
-                            _ => {}
+                        let send_result =
+                            sender.send(Ok(GameBatch::new(archive_month, games))).await;
+                        if send_result.is_err() {
+                            return;
                         }
                     }
                     Err(err) => {
@@ -122,24 +133,31 @@ impl ChessComClient {
         receiver
     }
 
+    /// Archive urls end with the year and month, e.g. `.../games/2024/05`
+    fn archive_month(archive_url: &str) -> Option<ArchiveMonth> {
+        let mut segments = archive_url.rsplit('/');
+        let month = segments.next()?.parse::<u32>().ok()?;
+        let year = segments.next()?.parse::<i32>().ok()?;
+        Some(ArchiveMonth::new(year, month))
+    }
+
+    /// Keeps the archives from the month of the timestamp on. That month is downloaded
+    /// again, since games may have been played in it after the timestamp
     fn filter_archives_by_timestamp(
         &self,
         archives: Vec<String>,
         from_timestamp: u64,
     ) -> Vec<String> {
-        match chrono::DateTime::from_timestamp_millis((from_timestamp * 1000) as i64) {
-            Some(from_date_time) => archives
-                .into_iter()
-                .filter(|archive| {
-                    let data_arr = archive.split('/').collect::<Vec<&str>>();
-                    let month_str = *data_arr.get(data_arr.len() - 1).unwrap_or(&"");
-                    let year_str = *data_arr.get(data_arr.len() - 2).unwrap_or(&"");
-                    let archive_month = month_str.parse::<u64>().unwrap_or(0);
-                    let archive_year = year_str.parse::<u64>().unwrap_or(0);
-                    archive_month >= from_date_time.month() as u64
-                        && archive_year >= from_date_time.year() as u64
-                })
-                .collect(),
+        match chrono::DateTime::from_timestamp(from_timestamp as i64, 0) {
+            Some(from_date_time) => {
+                let from_month = ArchiveMonth::from(from_date_time);
+                archives
+                    .into_iter()
+                    .filter(|archive| {
+                        Self::archive_month(archive).is_none_or(|month| month >= from_month)
+                    })
+                    .collect()
+            }
             None => archives,
         }
     }
@@ -163,21 +181,36 @@ struct ChessComGameResponse {
     pub end_time: u64,
     pub white: ChessComPlayerReponse,
     pub black: ChessComPlayerReponse,
+    pub time_class: String,
+    pub time_control: String,
+    pub rated: bool,
+    pub rules: String,
 }
 
-impl Into<NewGame> for ChessComGameResponse {
-    fn into(self) -> NewGame {
+impl From<ChessComGameResponse> for NewGame {
+    fn from(value: ChessComGameResponse) -> Self {
         NewGame::new(
-            self.white.username,
-            self.white.rating as i16,
-            self.black.username,
-            self.black.rating as i16,
-            (self.white.result.to_lowercase() == "win")
+            value.white.username,
+            value.white.rating as i16,
+            value.black.username,
+            value.black.rating as i16,
+            (value.white.result.to_lowercase() == "win")
                 .then_some(Color::White)
-                .or_else(|| (self.black.result.to_lowercase() == "win").then_some(Color::Black)),
+                .or_else(|| (value.black.result.to_lowercase() == "win").then_some(Color::Black)),
             PlatformName::ChessCom,
-            self.pgn.unwrap_or_default(),
-            DateTime::from_timestamp(self.end_time as i64, 0).unwrap_or(DateTime::UNIX_EPOCH),
+            value.pgn.unwrap_or_default(),
+            DateTime::from_timestamp(value.end_time as i64, 0).unwrap_or(DateTime::UNIX_EPOCH),
+            match value.time_class.as_str() {
+                "bullet" => Some(TimeClass::Bullet),
+                "blitz" => Some(TimeClass::Blitz),
+                "rapid" => Some(TimeClass::Rapid),
+                "daily" => Some(TimeClass::Daily),
+                _ => TimeClass::from_time_control(&value.time_control),
+            },
+            Some(value.time_control),
+            value.rated,
+            Variant::from_name(&value.rules),
+            None,
         )
     }
 }
@@ -193,7 +226,7 @@ impl PlatformApiClient for ChessComClient {
         &self,
         user_name: String,
         from_timestamp: Option<u64>,
-    ) -> Result<(usize, Receiver<Result<Vec<NewGame>, PlatformError>>), PlatformError> {
+    ) -> Result<(usize, Receiver<Result<GameBatch, PlatformError>>), PlatformError> {
         let archives_response = self.fetch_player_archives(user_name.clone()).await?;
 
         // Filter archives based on the from_timestamp
@@ -210,6 +243,10 @@ impl PlatformApiClient for ChessComClient {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path},
+    };
 
     #[test]
     fn test_filter_archives_by_timestamp_filters_correctly() {
@@ -239,4 +276,109 @@ mod tests {
         );
         assert_eq!(filtered.len(), 2);
     }
+
+    #[test]
+    fn test_filter_archives_by_timestamp_crosses_years() {
+        let client = ChessComClient::new();
+        let archives = vec![
+            "https://api.chess.com/pub/player/test/games/2023/12".to_string(),
+            "https://api.chess.com/pub/player/test/games/2024/10".to_string(),
+            "https://api.chess.com/pub/player/test/games/2024/11".to_string(),
+            "https://api.chess.com/pub/player/test/games/2024/12".to_string(),
+            "https://api.chess.com/pub/player/test/games/2025/01".to_string(),
+            "https://api.chess.com/pub/player/test/games/2025/11".to_string(),
+        ];
+        // Timestamp for 2024-11-20
+        let from_timestamp = chrono::NaiveDate::from_ymd_opt(2024, 11, 20)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp() as u64;
+
+        let filtered = client.filter_archives_by_timestamp(archives, from_timestamp);
+
+        assert_eq!(
+            filtered,
+            vec![
+                "https://api.chess.com/pub/player/test/games/2024/11".to_string(),
+                "https://api.chess.com/pub/player/test/games/2024/12".to_string(),
+                "https://api.chess.com/pub/player/test/games/2025/01".to_string(),
+                "https://api.chess.com/pub/player/test/games/2025/11".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_game_response_keeps_time_control() {
+        let response: ChessComGameResponse = serde_json::from_str(
+            r#"{
+                "url": "https://www.chess.com/game/daily/123",
+                "pgn": "1. e4 e5 1-0",
+                "time_control": "1/259200",
+                "end_time": 1714575600,
+                "rated": false,
+                "time_class": "daily",
+                "rules": "chess",
+                "white": {"rating": 1200, "result": "win", "username": "alice"},
+                "black": {"rating": 1180, "result": "resigned", "username": "bob"}
+            }"#,
+        )
+        .unwrap();
+
+        let game = NewGame::from(response);
+
+        assert_eq!(game.winner(), Some(&Color::White));
+        assert_eq!(game.time_class(), Some(&TimeClass::Daily));
+        assert_eq!(game.time_control().map(String::as_str), Some("1/259200"));
+        assert!(!*game.rated());
+        assert_eq!(*game.variant(), Variant::Standard);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_games_stops_at_failing_archive() {
+        let server = MockServer::start().await;
+        let archives = ["2024/04", "2024/05", "2024/06"]
+            .map(|month| format!("{}/pub/player/alice/games/{}", server.uri(), month));
+        Mock::given(method("GET"))
+            .and(path("/pub/player/alice/games/archives"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({ "archives": archives })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pub/player/alice/games/2024/04"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"games":[]}"#))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pub/player/alice/games/2024/05"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pub/player/alice/games/2024/06"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"games":[]}"#))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = ChessComClient::with_base_url(&server.uri());
+        let (archive_count, mut receiver) =
+            client.fetch_games("alice".to_string(), None).await.unwrap();
+        let mut batches = Vec::new();
+        while let Some(batch) = receiver.recv().await {
+            batches.push(batch);
+        }
+
+        // The cursor only advances with the archives before the failing one
+        assert_eq!(archive_count, 3);
+        assert!(matches!(
+            batches.as_slice(),
+            [Ok(batch), Err(PlatformError::ApiError(_))]
+                if batch.archive_month() == Some(&ArchiveMonth::new(2024, 4))
+        ));
+    }
 }
@@ -0,0 +1,433 @@
+use crate::domain::{
+    game::models::{
+        game::{Color, TimeClass, Variant},
+        new_game::NewGame,
+    },
+    platform::{
+        models::{ArchiveMonth, GameBatch, PlatformError, PlatformName},
+        ports::PlatformApiClient,
+    },
+};
+use chrono::DateTime;
+use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
+use reqwest_retry::{RetryTransientMiddleware, policies::ExponentialBackoff};
+use std::time::Duration;
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+
+const LICHESS_BASE_URL: &str = "https://lichess.org";
+/// Amount of games sent through the channel at once
+const GAMES_PER_BATCH: usize = 100;
+
+pub struct LichessClient {
+    client: ClientWithMiddleware,
+    base_url: String,
+}
+
+impl Default for LichessClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LichessClient {
+    pub fn new() -> Self {
+        Self::with_base_url(LICHESS_BASE_URL)
+    }
+
+    /// Used to point the client to a different Lichess instance, e.g. a mock server
+    pub fn with_base_url(base_url: &str) -> Self {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            reqwest::header::USER_AGENT,
+            reqwest::header::HeaderValue::from_static("neochess/0.1"),
+        );
+
+        let retry_policy = ExponentialBackoff::builder().build_with_max_retries(3);
+        let client = ClientBuilder::new(
+            reqwest::Client::builder()
+                .default_headers(headers)
+                // The export is streamed, so only limit the time between chunks
+                .read_timeout(Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        )
+        // Retry failed requests.
+        .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+        .build();
+        Self {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    async fn fetch_user(&self, username: &str) -> Result<LichessUserResponse, PlatformError> {
+        let url = format!("{}/api/user/{}", self.base_url, username);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| PlatformError::NetworkError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| PlatformError::ApiError(e.to_string()))?;
+
+        response
+            .json::<LichessUserResponse>()
+            .await
+            .map_err(|e| PlatformError::ParseError(format!("Failed to parse user: {}", e)))
+    }
+
+    async fn export_games(
+        &self,
+        username: &str,
+        from_timestamp_seconds: Option<u64>,
+    ) -> Result<reqwest::Response, PlatformError> {
+        let mut query = vec![
+            ("pgnInJson", "true".to_string()),
+            ("clocks", "true".to_string()),
+            // Oldest games first, so an interrupted import has stored every game before
+            // the month it stopped at
+            ("sort", "dateAsc".to_string()),
+        ];
+        if let Some(timestamp) = from_timestamp_seconds {
+            query.push(("since", (timestamp * 1000).to_string()));
+        }
+
+        self.client
+            .get(format!("{}/api/games/user/{}", self.base_url, username))
+            .query(&query)
+            .header(reqwest::header::ACCEPT, "application/x-ndjson")
+            .send()
+            .await
+            .map_err(|e| PlatformError::NetworkError(e.to_string()))?
+            .error_for_status()
+            .map_err(|e| PlatformError::ApiError(e.to_string()))
+    }
+
+    /// Reads the NDJSON export line by line and sends parsed games in batches
+    fn stream_games(response: reqwest::Response) -> Receiver<Result<GameBatch, PlatformError>> {
+        let (sender, receiver) = channel(1000);
+
+        tokio::spawn(async move {
+            if let Err(err) = Self::read_export(response, &sender).await {
+                let _ = sender.send(Err(err)).await;
+            }
+        });
+
+        receiver
+    }
+
+    async fn read_export(
+        mut response: reqwest::Response,
+        sender: &Sender<Result<GameBatch, PlatformError>>,
+    ) -> Result<(), PlatformError> {
+        let mut buffer: Vec<u8> = Vec::new();
+        let mut games: Vec<NewGame> = Vec::with_capacity(GAMES_PER_BATCH);
+
+        loop {
+            let chunk = response
+                .chunk()
+                .await
+                .map_err(|e| PlatformError::NetworkError(e.to_string()))?;
+            let is_last_chunk = chunk.is_none();
+            if let Some(chunk) = chunk {
+                buffer.extend_from_slice(&chunk);
+            } else if !buffer.is_empty() {
+                // The last line is not necessarily terminated by a newline
+                buffer.push(b'\n');
+            }
+
+            while let Some(newline_idx) = buffer.iter().position(|byte| *byte == b'\n') {
+                let line = buffer.drain(..=newline_idx).collect::<Vec<u8>>();
+                if let Some(game) = Self::parse_line(&line)? {
+                    games.push(game);
+                }
+
+                if games.len() >= GAMES_PER_BATCH {
+                    let batch = std::mem::replace(&mut games, Vec::with_capacity(GAMES_PER_BATCH));
+                    if sender.send(Ok(Self::to_batch(batch))).await.is_err() {
+                        return Ok(());
+                    }
+                }
+            }
+
+            if is_last_chunk {
+                break;
+            }
+        }
+
+        if !games.is_empty() {
+            let _ = sender.send(Ok(Self::to_batch(games))).await;
+        }
+
+        Ok(())
+    }
+
+    /// The export runs from the oldest game to the newest, so the month of the newest game
+    /// in the batch is the month currently being downloaded
+    fn to_batch(games: Vec<NewGame>) -> GameBatch {
+        let archive_month = games
+            .iter()
+            .map(|game| *game.finished_at())
+            .max()
+            .map(ArchiveMonth::from);
+
+        GameBatch::new(archive_month, games)
+    }
+
+    fn parse_line(line: &[u8]) -> Result<Option<NewGame>, PlatformError> {
+        if line.iter().all(|byte| byte.is_ascii_whitespace()) {
+            return Ok(None);
+        }
+
+        serde_json::from_slice::<LichessGameResponse>(line)
+            .map(|game| Some(game.into()))
+            .map_err(|e| PlatformError::ParseError(format!("Failed to parse game: {}", e)))
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct LichessUserResponse {
+    #[serde(default)]
+    pub count: LichessUserCountResponse,
+}
+
+#[derive(serde::Deserialize, Default)]
+struct LichessUserCountResponse {
+    #[serde(default)]
+    pub all: usize,
+}
+
+#[derive(serde::Deserialize)]
+struct LichessUserReference {
+    pub name: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LichessPlayerResponse {
+    pub user: Option<LichessUserReference>,
+    pub rating: Option<u32>,
+    pub ai_level: Option<u8>,
+}
+
+impl LichessPlayerResponse {
+    fn username(&self) -> String {
+        match (&self.user, self.ai_level) {
+            (Some(user), _) => user.name.clone(),
+            (None, Some(level)) => format!("Stockfish level {}", level),
+            (None, None) => "Anonymous".to_string(),
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct LichessPlayersResponse {
+    pub white: LichessPlayerResponse,
+    pub black: LichessPlayerResponse,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LichessGameResponse {
+    pub pgn: Option<String>,
+    pub last_move_at: i64,
+    pub players: LichessPlayersResponse,
+    pub winner: Option<String>,
+    pub speed: String,
+    pub rated: bool,
+    pub clock: Option<LichessClockResponse>,
+    pub days_per_turn: Option<u32>,
+    pub variant: String,
+}
+
+#[derive(serde::Deserialize)]
+struct LichessClockResponse {
+    pub initial: u32,
+    pub increment: u32,
+}
+
+impl LichessGameResponse {
+    /// Formats the clock the way it appears in the PGN `TimeControl` tag
+    fn time_control(&self) -> Option<String> {
+        match (&self.clock, self.days_per_turn) {
+            (Some(clock), _) => Some(format!("{}+{}", clock.initial, clock.increment)),
+            (None, Some(days)) => Some(format!("1/{}", days * 86400)),
+            (None, None) => None,
+        }
+    }
+}
+
+impl From<LichessGameResponse> for NewGame {
+    fn from(value: LichessGameResponse) -> Self {
+        let time_control = value.time_control();
+
+        NewGame::new(
+            value.players.white.username(),
+            value.players.white.rating.unwrap_or_default() as i16,
+            value.players.black.username(),
+            value.players.black.rating.unwrap_or_default() as i16,
+            match value.winner.as_deref() {
+                Some("white") => Some(Color::White),
+                Some("black") => Some(Color::Black),
+                _ => None,
+            },
+            PlatformName::Lichess,
+            value.pgn.unwrap_or_default(),
+            DateTime::from_timestamp_millis(value.last_move_at).unwrap_or(DateTime::UNIX_EPOCH),
+            match value.speed.as_str() {
+                "ultraBullet" | "bullet" => Some(TimeClass::Bullet),
+                "blitz" => Some(TimeClass::Blitz),
+                "rapid" => Some(TimeClass::Rapid),
+                "classical" => Some(TimeClass::Classical),
+                "correspondence" => Some(TimeClass::Daily),
+                _ => None,
+            },
+            time_control,
+            value.rated,
+            Variant::from_name(&value.variant),
+            None,
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl PlatformApiClient for LichessClient {
+    async fn fetch_games(
+        &self,
+        user_name: String,
+        from_timestamp_seconds: Option<u64>,
+    ) -> Result<(usize, Receiver<Result<GameBatch, PlatformError>>), PlatformError> {
+        // Lichess has no archives, so the amount of batches is estimated from the game count
+        let batch_count = match from_timestamp_seconds {
+            Some(_) => 1,
+            None => self
+                .fetch_user(&user_name)
+                .await?
+                .count
+                .all
+                .div_ceil(GAMES_PER_BATCH),
+        };
+
+        let response = self
+            .export_games(&user_name, from_timestamp_seconds)
+            .await?;
+
+        Ok((batch_count.max(1), Self::stream_games(response)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::{
+        Mock, MockServer, ResponseTemplate,
+        matchers::{method, path, query_param},
+    };
+
+    const EXPORT_NDJSON: &str = r#"{"id":"q7ZvsdUF","rated":true,"variant":"standard","speed":"blitz","clock":{"initial":180,"increment":2,"totalTime":260},"createdAt":1514505150384,"lastMoveAt":1514505592843,"status":"resign","players":{"white":{"user":{"name":"Lance5500","id":"lance5500"},"rating":2389},"black":{"user":{"name":"TryingHard87","id":"tryinghard87"},"rating":2498}},"winner":"white","pgn":"[White \"Lance5500\"]\n[Black \"TryingHard87\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0\n"}
+{"id":"x2kQ8p1z","rated":false,"variant":"chess960","speed":"rapid","createdAt":1514506150384,"lastMoveAt":1514506592843,"status":"draw","players":{"white":{"aiLevel":3},"black":{"user":{"name":"Lance5500","id":"lance5500"},"rating":2393}},"pgn":"[White \"lichess AI level 3\"]\n[Black \"Lance5500\"]\n[Result \"1/2-1/2\"]\n\n1. d4 d5 1/2-1/2\n"}"#;
+
+    async fn receive_all(
+        mut receiver: Receiver<Result<GameBatch, PlatformError>>,
+    ) -> Vec<Result<GameBatch, PlatformError>> {
+        let mut batches = Vec::new();
+        while let Some(batch) = receiver.recv().await {
+            batches.push(batch);
+        }
+        batches
+    }
+
+    #[tokio::test]
+    async fn test_fetch_games_parses_ndjson_export() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/user/lance5500"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(r#"{"count":{"all":2}}"#))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/games/user/lance5500"))
+            .and(query_param("pgnInJson", "true"))
+            .and(query_param("sort", "dateAsc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(EXPORT_NDJSON))
+            .mount(&server)
+            .await;
+
+        let client = LichessClient::with_base_url(&server.uri());
+        let (batch_count, receiver) = client
+            .fetch_games("lance5500".to_string(), None)
+            .await
+            .unwrap();
+        let batches = receive_all(receiver).await;
+
+        assert_eq!(batch_count, 1);
+        assert_eq!(batches.len(), 1);
+        let batch = batches.into_iter().next().unwrap().unwrap();
+        assert_eq!(batch.archive_month(), Some(&ArchiveMonth::new(2017, 12)));
+        let games = batch.into_games();
+        assert_eq!(games.len(), 2);
+
+        assert_eq!(games[0].white(), "Lance5500");
+        assert_eq!(*games[0].white_elo(), 2389);
+        assert_eq!(games[0].black(), "TryingHard87");
+        assert_eq!(games[0].winner(), Some(&Color::White));
+        assert_eq!(*games[0].platform_name(), PlatformName::Lichess);
+        assert_eq!(games[0].finished_at().timestamp_millis(), 1514505592843);
+        assert!(games[0].pgn().contains("4. Qxf7# 1-0"));
+        assert_eq!(games[0].time_class(), Some(&TimeClass::Blitz));
+        assert_eq!(games[0].time_control().map(String::as_str), Some("180+2"));
+        assert!(*games[0].rated());
+
+        assert_eq!(games[1].white(), "Stockfish level 3");
+        assert_eq!(*games[1].white_elo(), 0);
+        assert_eq!(games[1].winner(), None);
+        assert_eq!(games[1].time_class(), Some(&TimeClass::Rapid));
+        assert_eq!(games[1].time_control(), None);
+        assert!(!*games[1].rated());
+        assert_eq!(*games[1].variant(), Variant::Chess960);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_games_passes_since_in_milliseconds() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/games/user/lance5500"))
+            .and(query_param("since", "1514505000000"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(""))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = LichessClient::with_base_url(&server.uri());
+        let (_, receiver) = client
+            .fetch_games("lance5500".to_string(), Some(1514505000))
+            .await
+            .unwrap();
+
+        assert!(receive_all(receiver).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_games_reports_malformed_line() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/games/user/lance5500"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("{not json}\n"))
+            .mount(&server)
+            .await;
+
+        let client = LichessClient::with_base_url(&server.uri());
+        let (_, receiver) = client
+            .fetch_games("lance5500".to_string(), Some(0))
+            .await
+            .unwrap();
+        let batches = receive_all(receiver).await;
+
+        assert!(matches!(
+            batches.as_slice(),
+            [Err(PlatformError::ParseError(_))]
+        ));
+    }
+}
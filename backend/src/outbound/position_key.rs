@@ -0,0 +1,103 @@
+use shakmaty::{
+    CastlingMode, Chess, EnPassantMode, Position as _,
+    uci::UciMove,
+    zobrist::{Zobrist64, ZobristHash},
+};
+use std::str::FromStr;
+
+use crate::domain::game::models::errors::InvalidFenError;
+
+/// Zobrist hash of the position, which ignores the halfmove and fullmove counters and
+/// en passant squares without a legal capture, so transpositions share the same key
+pub fn from_chess(chess: &Chess) -> i64 {
+    chess.zobrist_hash::<Zobrist64>(EnPassantMode::Legal).0 as i64
+}
+
+/// Key of the standard starting position
+pub fn starting() -> i64 {
+    from_chess(&Chess::default())
+}
+
+pub fn from_fen(fen: &str) -> Result<i64, InvalidFenError> {
+    Ok(from_chess(&chess_from_fen(fen)?))
+}
+
+/// Key of the position after playing the UCI move in the position of the FEN
+pub fn after_move(fen: &str, uci: &str) -> Result<Option<i64>, InvalidFenError> {
+    let chess = chess_from_fen(fen)?;
+
+    Ok(UciMove::from_str(uci)
+        .ok()
+        .and_then(|uci| uci.to_move(&chess).ok())
+        .and_then(|mv| chess.play(mv).ok())
+        .map(|chess| from_chess(&chess)))
+}
+
+fn chess_from_fen(fen: &str) -> Result<Chess, InvalidFenError> {
+    shakmaty::fen::Fen::from_str(fen)
+        .map_err(|_| InvalidFenError)?
+        .into_position(CastlingMode::Standard)
+        .map_err(|_| InvalidFenError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starting_key() {
+        let actual = from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        assert_eq!(actual, Ok(starting()));
+    }
+
+    #[test]
+    fn test_key_ignores_move_counters() {
+        let actual = from_fen("r1bqkb1r/pppppppp/2n2n2/8/8/2N2N2/PPPPPPPP/R1BQKB1R w KQkq - 4 3");
+        let expected =
+            from_fen("r1bqkb1r/pppppppp/2n2n2/8/8/2N2N2/PPPPPPPP/R1BQKB1R w KQkq - 0 12");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_key_ignores_en_passant_without_legal_capture() {
+        let actual = from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        let expected = from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_key_distinguishes_castling_rights() {
+        let actual = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let other = from_fen("r3k2r/8/8/8/8/8/8/R3K2R w Kkq - 0 1");
+
+        assert_ne!(actual, other);
+    }
+
+    #[test]
+    fn test_key_after_move() {
+        let actual = after_move(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "e2e4",
+        );
+        let expected = from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+
+        assert_eq!(actual, Ok(Some(expected.unwrap())));
+        assert_eq!(
+            after_move(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "e2e5"
+            ),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn test_key_of_illegal_position() {
+        let actual = from_fen("8/8/8/8/8/8/8/8 w - - 0 1");
+
+        assert_eq!(actual, Err(InvalidFenError));
+    }
+}
@@ -1,23 +1,127 @@
-use pgn_reader::{SanPlus, Skip, Visitor};
-use shakmaty::{Chess, Position as _, uci::UciMove};
-use std::ops::ControlFlow;
+use pgn_reader::{RawComment, RawTag, Reader, SanPlus, Skip, Visitor};
+use shakmaty::{CastlingMode, Chess, EnPassantMode, Position as _, uci::UciMove};
+use std::{io, ops::ControlFlow, time::Duration};
 
-use crate::domain::game::models::{errors::InvalidPgnError, fen::Fen};
+use crate::{
+    domain::game::models::{
+        fen::Fen,
+        game_detail::{GamePly, Mainline},
+    },
+    outbound::position_key,
+};
 
-pub struct PositionVisitor<'a> {
-    pgn: &'a str,
-}
-
-impl<'a> PositionVisitor<'a> {
-    pub fn new(pgn: &'a str) -> Self {
-        Self { pgn: pgn }
-    }
-}
+/// Collects the mainline positions of a standard-rules game
+pub struct PositionVisitor;
 
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub struct PositionMetadata {
     pub fen: Fen,
+    /// transposition-aware key of the position
+    pub key: i64,
     pub next_move_uci: Option<UciMove>,
+    pub next_move_san: Option<String>,
+    /// remaining time of the player who moved into the position, from a `[%clk]` comment
+    pub clock: Option<Duration>,
+}
+
+/// Reason why the positions of a game could not be read, pointing at the offending move
+#[derive(Clone, Debug, thiserror::Error, PartialEq, Eq)]
+#[error("{reason}")]
+pub struct ReadPositionsError {
+    pub reason: String,
+    pub san: Option<String>,
+    /// half-move number of the offending move, starting at 1
+    pub ply: Option<u16>,
+}
+
+impl ReadPositionsError {
+    pub fn new(reason: &str) -> Self {
+        Self {
+            reason: reason.to_string(),
+            san: None,
+            ply: None,
+        }
+    }
+
+    fn at_move(reason: &str, san: &SanPlus, movetext: &PositionMovetext) -> Self {
+        Self {
+            reason: reason.to_string(),
+            san: Some(san.to_string()),
+            ply: Some(movetext.result.len() as u16),
+        }
+    }
+}
+
+/// Reads the mainline positions of the first game of the PGN
+pub fn read_positions(pgn: &str) -> Result<Vec<PositionMetadata>, ReadPositionsError> {
+    let mut reader = Reader::new(io::Cursor::new(pgn));
+
+    match reader.read_game(&mut PositionVisitor) {
+        Ok(Some(result)) => result,
+        Ok(None) => Err(ReadPositionsError::new("no game found in PGN")),
+        Err(err) => Err(ReadPositionsError::new(&err.to_string())),
+    }
+}
+
+/// Value of the `ECO` tag, which platforms like Chess.com add to their exports
+pub fn read_eco_tag(pgn: &str) -> Option<String> {
+    let mut reader = Reader::new(io::Cursor::new(pgn));
+
+    reader
+        .read_game(&mut EcoTagVisitor)
+        .ok()
+        .flatten()
+        .flatten()
+}
+
+struct EcoTagVisitor;
+
+impl Visitor for EcoTagVisitor {
+    type Tags = Option<String>;
+    type Movetext = ();
+    type Output = Option<String>;
+
+    fn begin_tags(&mut self) -> ControlFlow<Self::Output, Self::Tags> {
+        ControlFlow::Continue(None)
+    }
+
+    fn tag(
+        &mut self,
+        tags: &mut Self::Tags,
+        name: &[u8],
+        value: RawTag<'_>,
+    ) -> ControlFlow<Self::Output> {
+        if name == b"ECO" {
+            *tags = Some(value.decode_utf8_lossy().trim().to_string())
+                .filter(|eco| !eco.is_empty() && eco != "?");
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn begin_movetext(&mut self, tags: Self::Tags) -> ControlFlow<Self::Output, Self::Movetext> {
+        // The moves aren't needed
+        ControlFlow::Break(tags)
+    }
+
+    fn end_game(&mut self, _movetext: Self::Movetext) -> Self::Output {
+        None
+    }
+}
+
+/// Parses the clock annotation of a comment, e.g. `[%clk 0:02:59.9]`
+fn parse_clock(comment: &str) -> Option<Duration> {
+    let (_, annotation) = comment.split_once("[%clk")?;
+    let (clock, _) = annotation.split_once(']')?;
+    let mut parts = clock.trim().rsplit(':');
+
+    let seconds = parts.next()?.parse::<f64>().ok()?;
+    let minutes = parts.next().map_or(Ok(0), str::parse::<u64>).ok()?;
+    let hours = parts.next().map_or(Ok(0), str::parse::<u64>).ok()?;
+    if parts.next().is_some() || !seconds.is_finite() || seconds < 0.0 {
+        return None;
+    }
+
+    Some(Duration::from_secs(hours * 3600 + minutes * 60) + Duration::from_secs_f64(seconds))
 }
 
 pub struct PositionMovetext {
@@ -25,22 +129,52 @@ pub struct PositionMovetext {
     pub result: Vec<PositionMetadata>,
 }
 
-impl Visitor for PositionVisitor<'_> {
-    type Tags = ();
+impl Visitor for PositionVisitor {
+    /// Value of the `FEN` tag for games set up from a position
+    type Tags = Option<String>;
     type Movetext = PositionMovetext;
-    type Output = Result<Vec<PositionMetadata>, InvalidPgnError>;
+    type Output = Result<Vec<PositionMetadata>, ReadPositionsError>;
 
     fn begin_tags(&mut self) -> ControlFlow<Self::Output, Self::Tags> {
+        ControlFlow::Continue(None)
+    }
+
+    fn tag(
+        &mut self,
+        tags: &mut Self::Tags,
+        name: &[u8],
+        value: RawTag<'_>,
+    ) -> ControlFlow<Self::Output> {
+        if name == b"FEN" {
+            *tags = Some(value.decode_utf8_lossy().to_string());
+        }
         ControlFlow::Continue(())
     }
 
-    fn begin_movetext(&mut self, _tags: Self::Tags) -> ControlFlow<Self::Output, Self::Movetext> {
+    fn begin_movetext(&mut self, tags: Self::Tags) -> ControlFlow<Self::Output, Self::Movetext> {
+        let chess = match tags {
+            Some(fen) => match fen
+                .parse::<shakmaty::fen::Fen>()
+                .ok()
+                .and_then(|fen| fen.into_position(CastlingMode::Standard).ok())
+            {
+                Some(chess) => chess,
+                None => {
+                    return ControlFlow::Break(Err(ReadPositionsError::new("invalid FEN tag")));
+                }
+            },
+            None => Chess::new(),
+        };
+
         ControlFlow::Continue(PositionMovetext {
-            chess: Chess::new(),
             result: vec![PositionMetadata {
-                fen: shakmaty::fen::Fen::default().into(),
+                fen: shakmaty::fen::Fen::from_position(&chess, EnPassantMode::Always).into(),
+                key: position_key::from_chess(&chess),
                 next_move_uci: None,
+                next_move_san: None,
+                clock: None,
             }],
+            chess,
         })
     }
 
@@ -49,36 +183,65 @@ impl Visitor for PositionVisitor<'_> {
         movetext: &mut Self::Movetext,
         san_plus: SanPlus,
     ) -> ControlFlow<Self::Output> {
-        let san = san_plus.san;
         let pos = movetext.chess.clone();
-        match san.to_move(&pos) {
-            Ok(mv) => {
-                movetext.chess = match pos.play(mv) {
-                    Ok(chess) => chess,
-                    Err(_) => {
-                        return ControlFlow::Break(Err(InvalidPgnError(self.pgn.to_string())));
-                    }
-                };
-                let next_fen = shakmaty::fen::Fen::try_from_setup(
-                    movetext.chess.to_setup(shakmaty::EnPassantMode::Always),
-                );
-                match next_fen {
-                    Ok(fen) => {
-                        movetext.result.last_mut().unwrap().next_move_uci =
-                            Some(UciMove::from_standard(mv));
-                        movetext.result.push(PositionMetadata {
-                            fen: fen.into(),
-                            next_move_uci: None,
-                        })
-                    }
-                    Err(_) => {
-                        return ControlFlow::Break(Err(InvalidPgnError(self.pgn.to_string())));
-                    }
-                };
-                ControlFlow::Continue(())
+        let mv = match san_plus.san.to_move(&pos) {
+            Ok(mv) => mv,
+            Err(_) => {
+                return ControlFlow::Break(Err(ReadPositionsError::at_move(
+                    "illegal move",
+                    &san_plus,
+                    movetext,
+                )));
+            }
+        };
+        movetext.chess = match pos.play(mv) {
+            Ok(chess) => chess,
+            Err(_) => {
+                return ControlFlow::Break(Err(ReadPositionsError::at_move(
+                    "illegal move",
+                    &san_plus,
+                    movetext,
+                )));
             }
-            _ => ControlFlow::Break(Err(InvalidPgnError(self.pgn.to_string()))),
+        };
+        let next_fen =
+            shakmaty::fen::Fen::try_from_setup(movetext.chess.to_setup(EnPassantMode::Always));
+        match next_fen {
+            Ok(fen) => {
+                let last = movetext.result.last_mut().unwrap();
+                last.next_move_uci = Some(UciMove::from_standard(mv));
+                last.next_move_san = Some(san_plus.to_string());
+                movetext.result.push(PositionMetadata {
+                    fen: fen.into(),
+                    key: position_key::from_chess(&movetext.chess),
+                    next_move_uci: None,
+                    next_move_san: None,
+                    clock: None,
+                })
+            }
+            Err(_) => {
+                return ControlFlow::Break(Err(ReadPositionsError::at_move(
+                    "position after move has no valid FEN",
+                    &san_plus,
+                    movetext,
+                )));
+            }
+        };
+        ControlFlow::Continue(())
+    }
+
+    fn comment(
+        &mut self,
+        movetext: &mut Self::Movetext,
+        comment: RawComment<'_>,
+    ) -> ControlFlow<Self::Output> {
+        // Comments before the first move don't belong to a move
+        if movetext.result.len() > 1
+            && let Some(clock) = parse_clock(&String::from_utf8_lossy(comment.as_bytes()))
+        {
+            movetext.result.last_mut().unwrap().clock = Some(clock);
         }
+        ControlFlow::Continue(())
     }
 
     fn begin_variation(
@@ -93,6 +256,35 @@ impl Visitor for PositionVisitor<'_> {
     }
 }
 
+impl From<Vec<PositionMetadata>> for Mainline {
+    fn from(value: Vec<PositionMetadata>) -> Self {
+        let start_fen = value
+            .first()
+            .map(|position| position.fen.clone())
+            .unwrap_or_else(|| {
+                shakmaty::fen::Fen::from_position(&Chess::new(), EnPassantMode::Always).into()
+            });
+        let plies = value
+            .windows(2)
+            .enumerate()
+            .filter_map(|(idx, positions)| {
+                let [before, after] = positions else {
+                    return None;
+                };
+                Some(GamePly::new(
+                    idx as u16 + 1,
+                    before.next_move_san.clone()?,
+                    before.next_move_uci.as_ref()?.to_string(),
+                    after.fen.clone(),
+                    after.clock,
+                ))
+            })
+            .collect();
+
+        Mainline::new(start_fen, plies)
+    }
+}
+
 impl From<shakmaty::fen::Fen> for Fen {
     fn from(value: shakmaty::fen::Fen) -> Self {
         Self::new_unchecked(&value.to_string())
@@ -101,12 +293,30 @@ impl From<shakmaty::fen::Fen> for Fen {
 
 #[cfg(test)]
 mod tests {
-    use std::{io, str::FromStr};
-
-    use pgn_reader::Reader;
+    use std::str::FromStr;
 
     use super::*;
 
+    /// Position left with the given move as `(uci, san)`
+    fn position(fen: &str, next_move: Option<(&str, &str)>) -> PositionMetadata {
+        PositionMetadata {
+            fen: Fen::new_unchecked(fen),
+            key: position_key::from_fen(fen).unwrap(),
+            next_move_uci: next_move.map(|(uci, _)| UciMove::from_str(uci).unwrap()),
+            next_move_san: next_move.map(|(_, san)| san.to_string()),
+            clock: None,
+        }
+    }
+
+    #[test]
+    fn test_read_eco_tag() {
+        let pgn = "[Event \"Live Chess\"]\n[ECO \"B01\"]\n\n1. e4 d5 1-0";
+
+        assert_eq!(read_eco_tag(pgn), Some("B01".to_string()));
+        assert_eq!(read_eco_tag("[ECO \"?\"]\n\n1. e4 *"), None);
+        assert_eq!(read_eco_tag("1. e4 *"), None);
+    }
+
     #[test]
     fn test_pgn_success() {
         let pgn = r#"
@@ -125,73 +335,50 @@ mod tests {
             {The queen retreats, but notice how it still aims at the weak f7-pawn.} 4... Bg7
             {If Black is not careful, White can end the game in an instant.} 5. Qxf7# 1-0"#;
 
-        let expected: Result<Vec<PositionMetadata>, InvalidPgnError> = Ok(vec![
-            PositionMetadata {
-                fen: Fen::new_unchecked("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
-                next_move_uci: Some(UciMove::from_str("e2e4").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
-                ),
-                next_move_uci: Some(UciMove::from_str("e7e5").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
-                ),
-                next_move_uci: Some(UciMove::from_str("f1c4").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "rnbqkbnr/pppp1ppp/8/4p3/2B1P3/8/PPPP1PPP/RNBQK1NR b KQkq - 1 2",
-                ),
-                next_move_uci: Some(UciMove::from_str("b8c6").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/8/PPPP1PPP/RNBQK1NR w KQkq - 2 3",
-                ),
-                next_move_uci: Some(UciMove::from_str("d1h5").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 3 3",
-                ),
-                next_move_uci: Some(UciMove::from_str("g7g6").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqkbnr/pppp1p1p/2n3p1/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 0 4",
-                ),
-                next_move_uci: Some(UciMove::from_str("h5f3").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqkbnr/pppp1p1p/2n3p1/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR b KQkq - 1 4",
-                ),
-                next_move_uci: Some(UciMove::from_str("f8g7").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqk1nr/pppp1pbp/2n3p1/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR w KQkq - 2 5",
-                ),
-                next_move_uci: Some(UciMove::from_str("f3f7").unwrap()),
-            },
-            PositionMetadata {
-                fen: Fen::new_unchecked(
-                    "r1bqk1nr/pppp1Qbp/2n3p1/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 5",
-                ),
-                next_move_uci: None,
-            },
+        let expected: Result<Vec<PositionMetadata>, ReadPositionsError> = Ok(vec![
+            position(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                Some(("e2e4", "e4")),
+            ),
+            position(
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+                Some(("e7e5", "e5")),
+            ),
+            position(
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2",
+                Some(("f1c4", "Bc4")),
+            ),
+            position(
+                "rnbqkbnr/pppp1ppp/8/4p3/2B1P3/8/PPPP1PPP/RNBQK1NR b KQkq - 1 2",
+                Some(("b8c6", "Nc6")),
+            ),
+            position(
+                "r1bqkbnr/pppp1ppp/2n5/4p3/2B1P3/8/PPPP1PPP/RNBQK1NR w KQkq - 2 3",
+                Some(("d1h5", "Qh5")),
+            ),
+            position(
+                "r1bqkbnr/pppp1ppp/2n5/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 3 3",
+                Some(("g7g6", "g6")),
+            ),
+            position(
+                "r1bqkbnr/pppp1p1p/2n3p1/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 0 4",
+                Some(("h5f3", "Qf3")),
+            ),
+            position(
+                "r1bqkbnr/pppp1p1p/2n3p1/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR b KQkq - 1 4",
+                Some(("f8g7", "Bg7")),
+            ),
+            position(
+                "r1bqk1nr/pppp1pbp/2n3p1/4p3/2B1P3/5Q2/PPPP1PPP/RNB1K1NR w KQkq - 2 5",
+                Some(("f3f7", "Qxf7#")),
+            ),
+            position(
+                "r1bqk1nr/pppp1Qbp/2n3p1/4p3/2B1P3/8/PPPP1PPP/RNB1K1NR b KQkq - 0 5",
+                None,
+            ),
         ]);
 
-        let mut reader = Reader::new(io::Cursor::new(pgn));
-
-        let actual = reader
-            .read_game(&mut PositionVisitor::new(pgn.into()))
-            .unwrap_or(Some(Err(InvalidPgnError(pgn.into()))))
-            .unwrap_or(Err(InvalidPgnError(pgn.into())));
+        let actual = read_positions(pgn);
 
         assert_eq!(actual, expected);
     }
@@ -209,13 +396,133 @@ mod tests {
 
             1. e4 e5 2. Bc4 Nc6 3. Qh5 g6 4. Qf3 5. Qxf7# 1-0"#;
 
-        let mut reader = Reader::new(io::Cursor::new(pgn));
+        let actual = read_positions(pgn);
+
+        // Move numbers are ignored, so the white queen move is read as a black move
+        assert_eq!(
+            actual,
+            Err(ReadPositionsError {
+                reason: "illegal move".to_string(),
+                san: Some("Qxf7#".to_string()),
+                ply: Some(8),
+            })
+        );
+    }
+
+    #[test]
+    fn test_transpositions_share_key() {
+        let first = read_positions("1. Nf3 Nf6 2. Nc3 *").unwrap();
+        let second = read_positions("1. Nc3 Nf6 2. Nf3 *").unwrap();
+
+        let first_last = first.last().unwrap();
+        let second_last = second.last().unwrap();
+        assert_eq!(first_last.fen, second_last.fen);
+        assert_eq!(first_last.key, second_last.key);
+
+        let with_counters = read_positions("1. Nf3 Nf6 2. Ng1 Ng8 3. Nc3 Nf6 4. Nf3 *").unwrap();
+        let with_counters_last = with_counters.last().unwrap();
+        assert_ne!(first_last.fen, with_counters_last.fen);
+        assert_eq!(first_last.key, with_counters_last.key);
+    }
+
+    #[test]
+    fn test_starts_from_fen_tag() {
+        let actual = read_positions(
+            r#"
+            [SetUp "1"]
+            [FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 12"]
+
+            12... Kd7 13. e4 *"#,
+        );
+
+        let expected = Ok(vec![
+            position("4k3/8/8/8/8/8/4P3/4K3 b - - 0 12", Some(("e8d7", "Kd7"))),
+            position("8/3k4/8/8/8/8/4P3/4K3 w - - 1 13", Some(("e2e4", "e4"))),
+            position("8/3k4/8/8/4P3/8/8/4K3 b - e3 0 13", None),
+        ]);
 
-        let actual_fens = reader
-            .read_game(&mut PositionVisitor::new(pgn.into()))
-            .unwrap_or(Some(Err(InvalidPgnError(pgn.into()))))
-            .unwrap_or(Err(InvalidPgnError(pgn.into())));
+        assert_eq!(actual, expected);
+    }
 
-        assert!(actual_fens.is_err());
+    #[test]
+    fn test_invalid_fen_tag() {
+        let actual = read_positions(
+            r#"
+            [FEN "not a fen"]
+
+            1. e4 *"#,
+        );
+
+        assert!(actual.is_err());
+    }
+
+    #[test]
+    fn test_reads_clocks() {
+        let actual = read_positions(
+            "1. e4 { [%clk 0:03:00] } 1... e5 {[%clk 0:02:59.5]} 2. Nf3 {a comment} *",
+        )
+        .unwrap();
+
+        let clocks = actual
+            .iter()
+            .map(|position| position.clock)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            clocks,
+            vec![
+                None,
+                Some(Duration::from_secs(180)),
+                Some(Duration::from_millis(179_500)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_clock() {
+        assert_eq!(
+            parse_clock("[%clk 1:30:05]"),
+            Some(Duration::from_secs(5405))
+        );
+        assert_eq!(
+            parse_clock("[%eval 0.3] [%clk 0:00:09.9]"),
+            Some(Duration::from_millis(9900))
+        );
+        assert_eq!(parse_clock("[%clk 1:2:3:4]"), None);
+        assert_eq!(parse_clock("[%clk soon]"), None);
+        assert_eq!(parse_clock("no clock"), None);
+    }
+
+    #[test]
+    fn test_mainline_from_positions() {
+        let mainline = Mainline::from(read_positions("1. e4 {[%clk 0:01:00]} 1... e5 *").unwrap());
+
+        assert_eq!(
+            mainline.start_fen().to_string(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(
+            mainline.plies(),
+            &[
+                GamePly::new(
+                    1,
+                    "e4".to_string(),
+                    "e2e4".to_string(),
+                    Fen::new_unchecked(
+                        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+                    ),
+                    Some(Duration::from_secs(60)),
+                ),
+                GamePly::new(
+                    2,
+                    "e5".to_string(),
+                    "e7e5".to_string(),
+                    Fen::new_unchecked(
+                        "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+                    ),
+                    None,
+                ),
+            ]
+        );
     }
 }
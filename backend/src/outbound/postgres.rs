@@ -1,11 +1,12 @@
 pub mod dto;
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use pgn_reader::Reader;
-use rayon::prelude::*;
-use sqlx::{PgConnection, Pool, Row, postgres::PgRow};
-use std::io;
+use rayon::{iter::Either, prelude::*};
+use sqlx::{Connection, PgConnection, Pool, postgres::PgArguments, query::QueryAs};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, Sender};
 
@@ -13,41 +14,212 @@ use crate::{
     domain::{
         game::{
             models::{
-                errors::{GameRepositoryError, InvalidPgnError},
+                elo_filter::EloFilter,
+                errors::{GameRepositoryError, InvalidFenError, InvalidPgnError},
                 fen::Fen,
-                game::Color,
+                game::{Color, Game, Variant},
+                game_detail::GameDetail,
+                game_filter::GameFilter,
+                game_import_error::GameImportError,
+                game_page::{GameCursor, GamePage},
+                game_search::{GameResult, GameSearch, PositionFilter},
+                import_job::{ImportJob, ImportJobStatus},
+                move_comparison::{MoveComparison, PlayerRef},
                 move_stat::MoveStat,
-                new_game::NewGame,
+                move_stats_scope::MoveStatsScope,
+                move_tree::MoveTree,
+                opening::{Opening, OpeningBook},
+                opening_report::{OpeningFamilyReport, TrendPeriod},
+                player_profile::PlayerProfile,
+                repertoire::{Repertoire, RepertoireDeviationReport, RepertoireMove},
+                store_progress::StoreProgress,
+                stored_batch::StoredBatch,
+                tracked_player::TrackedPlayer,
             },
             ports::GameRepository,
         },
-        platform::models::{PlatformError, PlatformName},
+        platform::models::{ArchiveMonth, GameBatch, PlatformError, PlatformName},
     },
     outbound::{
-        position_visitor::{PositionMetadata, PositionVisitor},
-        postgres::dto::{InsertedGameDto, MoveStatDto, NewGameDto},
+        move_tree, position_key,
+        position_visitor::{PositionMetadata, read_eco_tag, read_positions},
+        postgres::dto::{
+            GameDto, GameImportErrorDto, GamePositionDto, ImportJobDto, InsertedGameDto,
+            MoveStatDto, MoveTreeLineDto, NewGameDto, NewGameImportErrorDto, NewPositionDto,
+            OpeningReportRowDto, PlayerMoveStatDto, PlayerSummaryDto, PositionDto,
+            RepertoireDeviationRowDto, RepertoireDto, RepertoireMoveDto, TrackedPlayerDto,
+            UncheckedGameOpeningDto, UpsertedGameDto,
+        },
     },
 };
+/// Amount of first moves listed per color in a player profile
+const FIRST_MOVE_LIMIT: i64 = 5;
+
+/// Keeps the games matching the game filter bound by `Postgres::bind_game_filter` to `$5`
+/// to `$10`. The rating bounds apply to the average rating of both players
+const GAME_FILTER_CONDITION: &str = "($5::TIMESTAMPTZ IS NULL OR game.finished_at >= $5)
+    AND ($6::TIMESTAMPTZ IS NULL OR game.finished_at <= $6)
+    AND ($7::VARCHAR[] IS NULL OR game.time_class = ANY($7))
+    AND (NOT $8 OR game.rated)
+    AND ($9::INT IS NULL OR (
+        game.white_elo > 0 AND game.black_elo > 0
+        AND (game.white_elo + game.black_elo) / 2 >= $9
+    ))
+    AND ($10::INT IS NULL OR (
+        game.white_elo > 0 AND game.black_elo > 0
+        AND (game.white_elo + game.black_elo) / 2 <= $10
+    ))";
+
 #[derive(Clone)]
 struct PositionRelation {
     pub game_id: uuid::Uuid,
     pub metadata: Vec<PositionMetadata>,
+    pub eco_tag: Option<String>,
 }
 
 #[derive(Clone)]
 pub struct Postgres {
     pool: Pool<sqlx::Postgres>,
+    opening_book: Arc<OpeningBook>,
 }
 
 impl Postgres {
-    pub async fn new(database_url: String) -> anyhow::Result<Self> {
+    pub async fn new(database_url: String, opening_book: OpeningBook) -> anyhow::Result<Self> {
         let pool = Pool::connect(&database_url).await?;
 
         sqlx::migrate!("src/outbound/postgres/migrations")
             .run(&pool)
             .await?;
 
-        Ok(Self { pool })
+        Self::reindex_queued_games(&pool, &opening_book).await?;
+        Self::classify_unchecked_games(&pool, &opening_book).await?;
+
+        Ok(Self {
+            pool,
+            opening_book: Arc::new(opening_book),
+        })
+    }
+
+    /// Positions can't be computed in SQL, so migrations that drop the indexed positions
+    /// queue their games, whose positions are computed again here from the stored PGN
+    async fn reindex_queued_games(
+        pool: &Pool<sqlx::Postgres>,
+        opening_book: &OpeningBook,
+    ) -> Result<(), PostgresError> {
+        loop {
+            let mut tx = pool.begin().await?;
+            let games: Vec<InsertedGameDto> = sqlx::query_as(
+                "SELECT game.id, game.pgn, game.finished_at, game.variant
+                FROM game_reindex
+                    JOIN game ON game.id = game_reindex.game_id
+                LIMIT 1000",
+            )
+            .fetch_all(&mut *tx)
+            .await?;
+
+            if games.is_empty() {
+                return Ok(());
+            }
+
+            // Games of other variants stay without positions but leave the queue as well
+            let game_ids: Vec<uuid::Uuid> = games.iter().map(|game| game.id).collect();
+            let indexed_games = games
+                .into_iter()
+                .filter(|game| game.is_indexed())
+                .collect::<Vec<_>>();
+            Self::copy_positions(indexed_games, opening_book, &mut tx).await?;
+            sqlx::query("DELETE FROM game_reindex WHERE game_id = ANY($1)")
+                .bind(game_ids)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+    }
+
+    /// Games stored before the opening book was added are classified from their indexed
+    /// positions, or else from the ECO tag of their PGN. Every game is only checked once
+    async fn classify_unchecked_games(
+        pool: &Pool<sqlx::Postgres>,
+        opening_book: &OpeningBook,
+    ) -> Result<(), PostgresError> {
+        loop {
+            let mut tx = pool.begin().await?;
+            let game_ids: Vec<uuid::Uuid> =
+                sqlx::query_scalar("SELECT id FROM game WHERE NOT opening_checked LIMIT 1000")
+                    .fetch_all(&mut *tx)
+                    .await?;
+
+            if game_ids.is_empty() {
+                return Ok(());
+            }
+
+            let games: Vec<UncheckedGameOpeningDto> = sqlx::query_as(
+                "SELECT game.id game_id,
+                        game.pgn,
+                        ARRAY_REMOVE(
+                            ARRAY_AGG(position.key ORDER BY game_position.move_idx),
+                            NULL
+                        ) keys
+                    FROM game
+                        LEFT JOIN game_position ON game_position.game_id = game.id
+                        LEFT JOIN position ON position.id = game_position.position_id
+                    WHERE game.id = ANY($1)
+                    AND game.variant = 'Standard'
+                    GROUP BY game.id",
+            )
+            .bind(&game_ids)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            let openings = games
+                .iter()
+                .filter_map(|game| {
+                    opening_book
+                        .classify(game.keys.iter())
+                        .or_else(|| {
+                            read_eco_tag(&game.pgn)
+                                .and_then(|eco| opening_book.opening_by_eco(&eco))
+                        })
+                        .map(|opening| (game.game_id, opening))
+                })
+                .collect::<Vec<_>>();
+            Self::update_game_openings(openings, &mut tx).await?;
+            sqlx::query("UPDATE game SET opening_checked = TRUE WHERE id = ANY($1)")
+                .bind(game_ids)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+        }
+    }
+
+    async fn update_game_openings(
+        openings: Vec<(uuid::Uuid, &Opening)>,
+        conn: &mut PgConnection,
+    ) -> Result<(), PostgresError> {
+        if openings.is_empty() {
+            return Ok(());
+        }
+
+        let (mut game_ids, mut ecos, mut names) = (vec![], vec![], vec![]);
+        for (game_id, opening) in openings {
+            game_ids.push(game_id);
+            ecos.push(opening.eco());
+            names.push(opening.name());
+        }
+
+        sqlx::query(
+            "UPDATE game
+            SET eco = opening.eco, opening_name = opening.name
+            FROM UNNEST($1::UUID[], $2::VARCHAR[], $3::VARCHAR[]) AS opening(game_id, eco, name)
+            WHERE game.id = opening.game_id",
+        )
+        .bind(game_ids)
+        .bind(ecos)
+        .bind(names)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
     }
 
     fn games_to_bytes(new_game_dto_chunk: Vec<NewGameDto>) -> Result<Vec<u8>, PostgresError> {
@@ -56,7 +228,7 @@ impl Postgres {
         encoder.write_header().unwrap();
 
         for game_dto in new_game_dto_chunk {
-            encoder.write_tuple(8)?;
+            encoder.write_tuple(13)?;
             encoder.write_str(&game_dto.white)?;
             encoder.write_smallint(game_dto.white_elo)?;
             encoder.write_str(&game_dto.black)?;
@@ -68,36 +240,45 @@ impl Postgres {
             encoder.write_str(&game_dto.platform_name)?;
             encoder.write_str(&game_dto.pgn)?;
             encoder.write_timestamp_with_time_zone(game_dto.finished_at)?;
+            match game_dto.time_class.as_ref() {
+                Some(time_class) => encoder.write_str(time_class)?,
+                None => encoder.write_null()?,
+            };
+            match game_dto.time_control.as_ref() {
+                Some(time_control) => encoder.write_str(time_control)?,
+                None => encoder.write_null()?,
+            };
+            encoder.write_bool(game_dto.rated)?;
+            encoder.write_str(&game_dto.variant)?;
+            encoder.write_str(&game_dto.source_id)?;
         }
 
         encoder.write_trailer()?;
 
-        return Ok(buf);
+        Ok(buf)
     }
 
-    fn position_relation_vec_to_bytes(
-        position_relation_vec: Vec<PositionRelation>,
+    fn game_positions_to_bytes(
+        game_position_dto_vec: Vec<GamePositionDto>,
     ) -> Result<Vec<u8>, PostgresError> {
         let mut buf: Vec<u8> = vec![];
         let mut encoder = pgcopy::Encoder::new(&mut buf);
         encoder.write_header().unwrap();
 
-        for position_relation in position_relation_vec {
-            for (move_idx, position_meta) in position_relation.metadata.iter().enumerate() {
-                encoder.write_tuple(4)?;
-                encoder.write_uuid(*position_relation.game_id.as_bytes())?;
-                encoder.write_smallint(move_idx as i16)?;
-                encoder.write_str(position_meta.fen.to_string())?;
-                match position_meta.next_move_uci {
-                    Some(uci) => encoder.write_str(uci.to_string())?,
-                    None => encoder.write_null()?,
-                };
-            }
+        for game_position_dto in game_position_dto_vec {
+            encoder.write_tuple(4)?;
+            encoder.write_uuid(*game_position_dto.game_id.as_bytes())?;
+            encoder.write_smallint(game_position_dto.move_idx)?;
+            encoder.write_int(game_position_dto.position_id)?;
+            match game_position_dto.next_move_uci {
+                Some(uci) => encoder.write_str(uci)?,
+                None => encoder.write_null()?,
+            };
         }
 
         encoder.write_trailer()?;
 
-        return Ok(buf);
+        Ok(buf)
     }
 
     async fn copy_games(
@@ -108,7 +289,7 @@ impl Postgres {
         let mut copy_in = conn
             .copy_in_raw(&format!(
                 "COPY \"{}\" 
-        (white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at) 
+        (white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at, time_class, time_control, rated, variant, source_id) 
         FROM STDIN 
         WITH (FORMAT binary);",
                 temp_table_name
@@ -132,27 +313,20 @@ impl Postgres {
 
     async fn save_games_from_receiver(
         &self,
-        username: &str,
-        platform_name: &PlatformName,
-        mut game_receiver: Receiver<Result<Vec<NewGame>, PlatformError>>,
-        progress_sender: Sender<usize>,
+        import_job: Option<&ImportJob>,
+        mut game_receiver: Receiver<Result<GameBatch, PlatformError>>,
+        progress_sender: Sender<StoreProgress>,
     ) -> Result<(), PostgresError> {
-        let mut tx = self.pool.begin().await?;
-        let games_temp_table_name = format!(
-            "temp_game_{}_{}",
-            username,
-            Into::<&'static str>::into(platform_name)
-        );
+        // Temporary tables are private to the connection, so the games of every batch are
+        // staged on the same connection and the table is emptied by each commit
+        let mut conn = self.pool.acquire().await?;
 
-        sqlx::query(&format!(
-            "DROP TABLE IF EXISTS \"{}\";",
-            games_temp_table_name
-        ))
-        .execute(&mut *tx)
-        .await?;
+        sqlx::query("DROP TABLE IF EXISTS temp_game;")
+            .execute(&mut *conn)
+            .await?;
 
-        sqlx::query(&format!(
-            "CREATE TEMPORARY TABLE IF NOT EXISTS \"{}\" (
+        sqlx::query(
+            "CREATE TEMPORARY TABLE temp_game (
             id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
             white VARCHAR NOT NULL,
             white_elo SMALLINT NOT NULL,
@@ -162,87 +336,284 @@ impl Postgres {
             platform_name VARCHAR NOT NULL,
             pgn VARCHAR NOT NULL,
             finished_at TIMESTAMP WITH TIME ZONE NOT NULL,
-            UNIQUE (white, black, finished_at, platform_name)
-        );",
-            games_temp_table_name
-        ))
-        .execute(&mut *tx)
+            time_class VARCHAR,
+            time_control VARCHAR,
+            rated BOOLEAN NOT NULL,
+            variant VARCHAR NOT NULL,
+            source_id VARCHAR NOT NULL,
+            UNIQUE (white, black, finished_at, platform_name, source_id)
+        ) ON COMMIT DELETE ROWS;",
+        )
+        .execute(&mut *conn)
         .await?;
 
-        while let Some(new_games) = game_receiver.recv().await {
+        // Every batch is committed on its own, so the stored games are available while the
+        // import continues and a failure only loses the batch it happened in
+        while let Some(game_batch) = game_receiver.recv().await {
+            let game_batch = game_batch?;
+            let archive_month = game_batch.archive_month().copied();
+            progress_sender
+                .send(StoreProgress::BatchReceived {
+                    archive_month,
+                    fetched_amount: game_batch.games().len(),
+                })
+                .await
+                .map_err(|e| anyhow::anyhow!(e))?;
+
+            let mut tx = conn.begin().await?;
+
             Self::copy_games(
-                new_games?
-                    .into_iter()
-                    .map(|new_game| new_game.into())
-                    .collect::<_>(),
-                &games_temp_table_name,
-                &mut *tx,
+                Self::unique_games(
+                    game_batch
+                        .into_games()
+                        .into_iter()
+                        .map(|new_game| new_game.into())
+                        .collect::<_>(),
+                ),
+                "temp_game",
+                &mut tx,
             )
             .await?;
 
-            let inserted_games: Vec<InsertedGameDto> = sqlx::query_as(&format!(
-                "INSERT INTO game
-        SELECT * FROM \"{}\"
-        ON CONFLICT DO NOTHING
-        RETURNING id, pgn, finished_at",
-                games_temp_table_name
-            ))
-            .fetch_all(&mut *tx)
+            let (inserted_games, updated_amount) = Self::insert_staged_games(
+                import_job.is_some_and(|import_job| *import_job.full_resync()),
+                &mut tx,
+            )
             .await?;
 
             let inserted_amount = inserted_games.len();
+            let indexed_games = inserted_games
+                .into_iter()
+                .filter(|inserted_game| inserted_game.is_indexed())
+                .collect::<Vec<_>>();
+            let skipped_amount = inserted_amount - indexed_games.len();
+
+            let failed_amount =
+                Self::copy_positions(indexed_games, &self.opening_book, &mut tx).await?;
+
+            // The cursor of the job is only committed together with the games it covers
+            if let Some(import_job) = import_job {
+                Self::advance_import_job(import_job.id(), archive_month, inserted_amount, &mut tx)
+                    .await?;
+            }
 
-            Self::copy_positions(inserted_games, &mut *tx).await?;
+            tx.commit().await?;
 
             progress_sender
-                .send(inserted_amount)
+                .send(StoreProgress::BatchStored(StoredBatch::new(
+                    inserted_amount,
+                    updated_amount,
+                    skipped_amount,
+                    failed_amount,
+                )))
                 .await
                 .map_err(|e| anyhow::anyhow!(e))?;
         }
 
-        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Drops the games that are repeated within a batch, which would violate the unique key
+    /// of the staging table, e.g. a game pasted twice into an uploaded PGN file
+    fn unique_games(new_game_dtos: Vec<NewGameDto>) -> Vec<NewGameDto> {
+        let mut keys = HashSet::new();
+        new_game_dtos
+            .into_iter()
+            .filter(|game_dto| {
+                keys.insert((
+                    game_dto.white.clone(),
+                    game_dto.black.clone(),
+                    game_dto.finished_at,
+                    game_dto.platform_name.clone(),
+                    game_dto.source_id.clone(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Moves the staged games into the game table. Games stored by an earlier import are
+    /// skipped, unless the import is a full resync, which refreshes their details instead.
+    /// Returns the inserted games and the amount of refreshed games
+    async fn insert_staged_games(
+        full_resync: bool,
+        conn: &mut PgConnection,
+    ) -> Result<(Vec<InsertedGameDto>, usize), PostgresError> {
+        if !full_resync {
+            let inserted_games: Vec<InsertedGameDto> = sqlx::query_as(
+                "INSERT INTO game (id, white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at, time_class, time_control, rated, variant, source_id)
+                SELECT id, white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at, time_class, time_control, rated, variant, source_id FROM temp_game
+                ON CONFLICT DO NOTHING
+                RETURNING id, pgn, finished_at, variant",
+            )
+            .fetch_all(&mut *conn)
+            .await?;
+
+            return Ok((inserted_games, 0));
+        }
+
+        // The PGN and the variant are kept, since the indexed positions depend on them.
+        // Rows whose xmax is 0 were inserted by this statement
+        let upserted_games: Vec<UpsertedGameDto> = sqlx::query_as(
+            "INSERT INTO game (id, white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at, time_class, time_control, rated, variant, source_id)
+            SELECT id, white, white_elo, black, black_elo, winner, platform_name, pgn, finished_at, time_class, time_control, rated, variant, source_id FROM temp_game
+            ON CONFLICT (white, black, finished_at, platform_name, source_id) DO UPDATE
+            SET white_elo = EXCLUDED.white_elo,
+                black_elo = EXCLUDED.black_elo,
+                winner = EXCLUDED.winner,
+                time_class = EXCLUDED.time_class,
+                time_control = EXCLUDED.time_control,
+                rated = EXCLUDED.rated
+            WHERE (game.white_elo, game.black_elo, game.winner, game.time_class, game.time_control, game.rated)
+                IS DISTINCT FROM
+                (EXCLUDED.white_elo, EXCLUDED.black_elo, EXCLUDED.winner, EXCLUDED.time_class, EXCLUDED.time_control, EXCLUDED.rated)
+            RETURNING id, pgn, finished_at, variant, xmax = 0 AS inserted",
+        )
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let (inserted_games, updated_games): (Vec<_>, Vec<_>) = upserted_games
+            .into_iter()
+            .partition(|upserted_game| upserted_game.inserted);
+
+        Ok((
+            inserted_games
+                .into_iter()
+                .map(|inserted_game| inserted_game.game)
+                .collect(),
+            updated_games.len(),
+        ))
+    }
+
+    async fn advance_import_job(
+        import_job_id: &uuid::Uuid,
+        archive_month: Option<ArchiveMonth>,
+        inserted_amount: usize,
+        conn: &mut PgConnection,
+    ) -> Result<(), PostgresError> {
+        sqlx::query(
+            "UPDATE import_job
+            SET last_archive_year = COALESCE($2, last_archive_year),
+                last_archive_month = COALESCE($3, last_archive_month),
+                games_inserted = games_inserted + $4,
+                updated_at = now()
+            WHERE id = $1",
+        )
+        .bind(import_job_id)
+        .bind(archive_month.map(|month| *month.year() as i16))
+        .bind(archive_month.map(|month| *month.month() as i16))
+        .bind(inserted_amount as i32)
+        .execute(&mut *conn)
+        .await?;
+
+        // A full resync revisits old archives, the cursor of the player only moves forward
+        sqlx::query(
+            "UPDATE tracked_player
+            SET last_synced_year = $2,
+                last_synced_month = $3
+            FROM import_job
+            WHERE import_job.id = $1
+                AND tracked_player.platform_name = import_job.platform_name
+                AND tracked_player.username = LOWER(import_job.username)
+                AND $2::SMALLINT IS NOT NULL
+                AND (
+                    tracked_player.last_synced_year IS NULL
+                    OR ($2::SMALLINT, $3::SMALLINT)
+                        > (tracked_player.last_synced_year, tracked_player.last_synced_month)
+                )",
+        )
+        .bind(import_job_id)
+        .bind(archive_month.map(|month| *month.year() as i16))
+        .bind(archive_month.map(|month| *month.month() as i16))
+        .execute(&mut *conn)
+        .await?;
 
         Ok(())
     }
 
+    /// Indexes the positions of the given games, classifies their openings and records the
+    /// games that can't be read. Returns the amount of games that failed
     async fn copy_positions(
         inserted_games: Vec<InsertedGameDto>,
+        opening_book: &OpeningBook,
         conn: &mut PgConnection,
-    ) -> Result<(), PostgresError> {
-        let position_relation_vec: Vec<PositionRelation> = inserted_games
+    ) -> Result<usize, PostgresError> {
+        let (position_relation_vec, import_errors): (Vec<_>, Vec<_>) = inserted_games
             .par_iter()
-            .filter_map(|inserted_game| {
-                let mut reader = Reader::new(io::Cursor::new(&inserted_game.pgn));
-                let metadata = match reader.read_game(&mut PositionVisitor::new(&inserted_game.pgn))
-                {
-                    Ok(option) => match option {
-                        Some(result) => match result {
-                            Ok(result) => result,
-                            Err(_) => return None,
-                        },
-                        None => return None,
-                    },
-                    Err(_) => return None,
-                };
-
-                return Some(PositionRelation {
+            .map(|inserted_game| match read_positions(&inserted_game.pgn) {
+                Ok(metadata) => Ok(PositionRelation {
                     game_id: inserted_game.id,
                     metadata,
-                });
+                    eco_tag: read_eco_tag(&inserted_game.pgn),
+                }),
+                Err(err) => Err(NewGameImportErrorDto {
+                    game_id: inserted_game.id,
+                    reason: err.reason,
+                    san: err.san,
+                    ply: err.ply.map(|ply| ply as i16),
+                }),
+            })
+            .partition_map(|result| match result {
+                Ok(position_relation) => Either::Left(position_relation),
+                Err(import_error) => Either::Right(import_error),
+            });
+
+        let failed_amount = import_errors.len();
+        Self::insert_import_errors(import_errors, conn).await?;
+
+        let openings = position_relation_vec
+            .iter()
+            .filter_map(|position_relation| {
+                opening_book
+                    .classify(position_relation.metadata.iter().map(|meta| &meta.key))
+                    .or_else(|| {
+                        position_relation
+                            .eco_tag
+                            .as_deref()
+                            .and_then(|eco| opening_book.opening_by_eco(eco))
+                    })
+                    .map(|opening| (position_relation.game_id, opening))
+            })
+            .collect::<Vec<_>>();
+        Self::update_game_openings(openings, conn).await?;
+
+        let new_positions = position_relation_vec
+            .iter()
+            .flat_map(|position_relation| position_relation.metadata.iter())
+            .map(|position_meta| NewPositionDto {
+                key: position_meta.key,
+            })
+            .collect::<Vec<_>>();
+        let position_ids = Self::upsert_positions(new_positions, conn).await?;
+
+        let game_position_dto_vec = position_relation_vec
+            .into_iter()
+            .flat_map(|position_relation| {
+                let game_id = position_relation.game_id;
+                position_relation
+                    .metadata
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(move_idx, position_meta)| (game_id, move_idx, position_meta))
+            })
+            .map(|(game_id, move_idx, position_meta)| GamePositionDto {
+                game_id,
+                position_id: position_ids[&position_meta.key],
+                move_idx: move_idx as i16,
+                next_move_uci: position_meta.next_move_uci.map(|uci| uci.to_string()),
             })
             .collect::<Vec<_>>();
 
         let mut copy_in = conn
             .copy_in_raw(
                 "COPY game_position 
-        (game_id, move_idx, fen, next_move_uci) 
+        (game_id, move_idx, position_id, next_move_uci) 
         FROM STDIN 
         WITH (FORMAT binary);",
             )
             .await?;
 
         let result = copy_in
-            .send(Self::position_relation_vec_to_bytes(position_relation_vec)?)
+            .send(Self::game_positions_to_bytes(game_position_dto_vec)?)
             .await;
         match result {
             Ok(_) => {
@@ -253,152 +624,1499 @@ impl Postgres {
             }
         }
 
+        Ok(failed_amount)
+    }
+
+    async fn insert_import_errors(
+        import_errors: Vec<NewGameImportErrorDto>,
+        conn: &mut PgConnection,
+    ) -> Result<(), PostgresError> {
+        if import_errors.is_empty() {
+            return Ok(());
+        }
+
+        let (mut game_ids, mut reasons, mut sans, mut plies) = (vec![], vec![], vec![], vec![]);
+        for import_error in import_errors {
+            game_ids.push(import_error.game_id);
+            reasons.push(import_error.reason);
+            sans.push(import_error.san);
+            plies.push(import_error.ply);
+        }
+
+        sqlx::query(
+            "INSERT INTO game_import_error (game_id, reason, san, ply)
+            SELECT * FROM UNNEST($1::UUID[], $2::VARCHAR[], $3::VARCHAR[], $4::SMALLINT[])
+            ON CONFLICT (game_id) DO UPDATE
+            SET reason = EXCLUDED.reason, san = EXCLUDED.san, ply = EXCLUDED.ply, created_at = now()",
+        )
+        .bind(game_ids)
+        .bind(reasons)
+        .bind(sans)
+        .bind(plies)
+        .execute(&mut *conn)
+        .await?;
+
         Ok(())
     }
 
-    async fn latest_game_timestamp_seconds_by_username(
+    /// Inserts the positions that are not stored yet and returns ids of all given positions by key
+    async fn upsert_positions(
+        new_positions: Vec<NewPositionDto>,
+        conn: &mut PgConnection,
+    ) -> Result<HashMap<i64, i32>, PostgresError> {
+        let mut keys = new_positions
+            .into_iter()
+            .map(|new_position| new_position.key)
+            .collect::<Vec<_>>();
+        // Sorted keys keep the lock order stable between concurrent imports
+        keys.sort_unstable();
+        keys.dedup();
+
+        sqlx::query(
+            "INSERT INTO position (key)
+            SELECT UNNEST($1::BIGINT[])
+            ON CONFLICT (key) DO NOTHING",
+        )
+        .bind(&keys)
+        .execute(&mut *conn)
+        .await?;
+
+        let positions: Vec<PositionDto> =
+            sqlx::query_as("SELECT id, key FROM position WHERE key = ANY($1)")
+                .bind(&keys)
+                .fetch_all(&mut *conn)
+                .await?;
+
+        Ok(positions
+            .into_iter()
+            .map(|position| (position.key, position.id))
+            .collect())
+    }
+
+    async fn query_tracked_player(
         &self,
         platform_name: &PlatformName,
         username: &str,
-    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, PostgresError> {
-        let latest_timestamp: Option<DateTime<Utc>> = sqlx::query(
-            "SELECT MAX(finished_at) FROM game
-        WHERE platform_name = $1
-        AND (
-            LOWER(white) = LOWER($2)
-            OR LOWER(black) = LOWER($2)
-        )",
+    ) -> Result<Option<TrackedPlayer>, PostgresError> {
+        let tracked_player: Option<TrackedPlayerDto> = sqlx::query_as(
+            "SELECT * FROM tracked_player
+            WHERE platform_name = $1
+                AND username = LOWER($2)",
         )
         .bind(Into::<&'static str>::into(platform_name))
         .bind(username)
-        .map(|row: PgRow| row.get(0))
-        .fetch_one(&self.pool)
+        .fetch_optional(&self.pool)
         .await?;
 
-        return Ok(latest_timestamp);
+        Ok(tracked_player.map(|tracked_player| tracked_player.into()))
     }
 
-    pub async fn query_move_stats(
+    async fn query_tracked_players(
+        &self,
+        platform_name: Option<&PlatformName>,
+    ) -> Result<Vec<TrackedPlayer>, PostgresError> {
+        let tracked_players: Vec<TrackedPlayerDto> = sqlx::query_as(
+            "SELECT * FROM tracked_player
+            WHERE $1::VARCHAR IS NULL OR platform_name = $1
+            ORDER BY platform_name, username",
+        )
+        .bind(platform_name.map(Into::<&'static str>::into))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tracked_players
+            .into_iter()
+            .map(|tracked_player| tracked_player.into())
+            .collect::<_>())
+    }
+
+    async fn query_game_import_errors(
         &self,
-        position_fen: &Fen,
         username: &str,
-        play_as: &Color,
         platform_name: &PlatformName,
-        from_timestamp_seconds: &Option<chrono::DateTime<chrono::Utc>>,
-        to_timestamp_seconds: &Option<chrono::DateTime<chrono::Utc>>,
-    ) -> Result<Vec<MoveStat>, PostgresError> {
-        let move_stats_dto: Vec<MoveStatDto> = sqlx::query_as(&format!(
-            "SELECT game_position.next_move_uci,
-                    COUNT(*) total,
-                    SUM(case when game.winner = $1 then 1 else 0 end) wins,
-                    SUM(case when game.winner is NULL then 1 else 0 end) draws,
-                    AVG({})::INT avg_opponent_elo,
-                    MAX(game.finished_at) last_played_at
-                FROM game_position
-                    JOIN game ON game.id = game_position.game_id
-                WHERE game.platform_name = $2
-                    AND game_position.fen = $3
-                    AND game_position.next_move_uci IS NOT NULL
-                    AND LOWER({}) = LOWER($4)
-                    AND ($5 is NULL OR game.finished_at >= $5)
-                    AND ($6 is NULL OR game.finished_at <= $6)
-                GROUP BY game_position.next_move_uci",
-            match play_as {
-                Color::White => "game.black_elo",
-                Color::Black => "game.white_elo",
-            },
-            match play_as {
-                Color::White => "game.white",
-                Color::Black => "game.black",
-            }
-        ))
-        .bind(Into::<&'static str>::into(play_as))
+    ) -> Result<Vec<GameImportError>, PostgresError> {
+        let import_errors: Vec<GameImportErrorDto> = sqlx::query_as(
+            "SELECT game_import_error.game_id,
+                    game_import_error.reason,
+                    game_import_error.san,
+                    game_import_error.ply,
+                    game_import_error.created_at
+                FROM game_import_error
+                    JOIN game ON game.id = game_import_error.game_id
+                WHERE game.platform_name = $1
+                    AND (
+                        LOWER(game.white) = LOWER($2)
+                        OR LOWER(game.black) = LOWER($2)
+                    )
+                ORDER BY game.finished_at DESC",
+        )
         .bind(Into::<&'static str>::into(platform_name))
-        .bind(position_fen.to_string())
         .bind(username)
-        .bind(from_timestamp_seconds)
-        .bind(to_timestamp_seconds)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(move_stats_dto
+        Ok(import_errors
             .into_iter()
-            .map(|move_stat_dto| move_stat_dto.into())
+            .map(|import_error| import_error.into())
             .collect::<_>())
     }
-}
 
-#[derive(Debug, Error)]
-pub enum PostgresError {
-    #[error(transparent)]
-    DatabaseError(#[from] sqlx::Error),
-    #[error(transparent)]
-    JoinError(#[from] tokio::task::JoinError),
-    #[error(transparent)]
-    InvalidPgn(#[from] InvalidPgnError),
-    #[error(transparent)]
-    SerializationError(#[from] std::io::Error),
-    #[error(transparent)]
-    PlatformError(#[from] PlatformError),
-    #[error(transparent)]
-    Unknown(#[from] anyhow::Error),
-}
+    async fn insert_import_job(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+        full_resync: bool,
+    ) -> Result<ImportJob, PostgresError> {
+        let platform_name: &'static str = platform_name.into();
+        let mut tx = self.pool.begin().await?;
 
-impl From<PostgresError> for GameRepositoryError {
-    fn from(value: PostgresError) -> Self {
-        match value {
-            PostgresError::DatabaseError(err) => {
-                GameRepositoryError::DatabaseError(err.to_string())
+        sqlx::query(
+            "INSERT INTO tracked_player (platform_name, username)
+            VALUES ($1, LOWER($2))
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(platform_name)
+        .bind(username)
+        .execute(&mut *tx)
+        .await?;
+
+        // Incremental imports start at the last archive synced for the player
+        let inserted_job: Option<ImportJobDto> = sqlx::query_as(
+            "INSERT INTO import_job (username, platform_name, full_resync, from_timestamp)
+            SELECT $1, $2, $3, CASE WHEN NOT $3 THEN
+                    make_timestamptz(last_synced_year, last_synced_month, 1, 0, 0, 0, 'UTC')
+                END
+                FROM tracked_player
+                WHERE platform_name = $2
+                    AND username = LOWER($1)
+            ON CONFLICT (LOWER(username), platform_name) WHERE status IN ('Pending', 'Running')
+            DO NOTHING
+            RETURNING *",
+        )
+        .bind(username)
+        .bind(platform_name)
+        .bind(full_resync)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let import_job = match inserted_job {
+            Some(import_job) => import_job,
+            None => {
+                sqlx::query_as(
+                    "SELECT * FROM import_job
+                    WHERE LOWER(username) = LOWER($1)
+                        AND platform_name = $2
+                        AND status IN ('Pending', 'Running')",
+                )
+                .bind(username)
+                .bind(platform_name)
+                .fetch_one(&mut *tx)
+                .await?
             }
-            err => GameRepositoryError::Unknown(anyhow::anyhow!(err)),
-        }
+        };
+
+        tx.commit().await?;
+
+        Ok(import_job.into())
     }
-}
 
-#[async_trait]
-impl GameRepository for Postgres {
-    async fn store_games(
+    async fn set_import_job_status(
         &self,
-        platform_name: &PlatformName,
-        username: &str,
-        game_receiver: Receiver<Result<Vec<NewGame>, PlatformError>>,
-        progress_sender: Sender<usize>,
-    ) -> Result<(), GameRepositoryError> {
-        Ok(self
-            .save_games_from_receiver(username, platform_name, game_receiver, progress_sender)
-            .await?)
+        import_job_id: &uuid::Uuid,
+        status: &ImportJobStatus,
+        error: Option<&str>,
+    ) -> Result<(), PostgresError> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            "UPDATE import_job
+            SET status = $2,
+                error = $3,
+                updated_at = now(),
+                finished_at = CASE WHEN $2 IN ('Done', 'Failed', 'Cancelled') THEN now() END
+            WHERE id = $1",
+        )
+        .bind(import_job_id)
+        .bind(Into::<&'static str>::into(status))
+        .bind(error)
+        .execute(&mut *tx)
+        .await?;
+
+        if *status == ImportJobStatus::Done {
+            sqlx::query(
+                "UPDATE tracked_player
+                SET last_synced_at = import_job.finished_at,
+                    game_count = (
+                        SELECT COUNT(*) FROM game
+                        WHERE game.platform_name = tracked_player.platform_name
+                            AND (
+                                LOWER(game.white) = tracked_player.username
+                                OR LOWER(game.black) = tracked_player.username
+                            )
+                    )
+                FROM import_job
+                WHERE import_job.id = $1
+                    AND tracked_player.platform_name = import_job.platform_name
+                    AND tracked_player.username = LOWER(import_job.username)",
+            )
+            .bind(import_job_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
     }
 
-    async fn get_latest_game_timestamp_seconds(
+    async fn query_unfinished_import_jobs(&self) -> Result<Vec<ImportJob>, PostgresError> {
+        let import_jobs: Vec<ImportJobDto> = sqlx::query_as(
+            "SELECT * FROM import_job
+            WHERE status IN ('Pending', 'Running')
+            ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(import_jobs
+            .into_iter()
+            .map(|import_job| import_job.into())
+            .collect::<_>())
+    }
+
+    async fn query_import_job(
         &self,
-        platform_name: &PlatformName,
-        username: &str,
-    ) -> Result<Option<u64>, GameRepositoryError> {
-        let timestamp = self
-            .latest_game_timestamp_seconds_by_username(platform_name, username)
-            .await?;
+        import_job_id: &uuid::Uuid,
+    ) -> Result<Option<ImportJob>, PostgresError> {
+        let import_job: Option<ImportJobDto> =
+            sqlx::query_as("SELECT * FROM import_job WHERE id = $1")
+                .bind(import_job_id)
+                .fetch_optional(&self.pool)
+                .await?;
 
-        Ok(timestamp.map(|ts| (ts.timestamp_millis() / 1000) as u64))
+        Ok(import_job.map(|import_job| import_job.into()))
     }
 
-    async fn get_move_stats(
+    async fn remove_player_games(
         &self,
-        position_fen: &Fen,
         username: &str,
-        play_as: &Color,
         platform_name: &PlatformName,
-        from_timestamp: &Option<chrono::DateTime<chrono::Utc>>,
-        to_timestamp: &Option<chrono::DateTime<chrono::Utc>>,
-    ) -> Result<Vec<MoveStat>, GameRepositoryError> {
-        Ok(self
-            .query_move_stats(
-                position_fen,
-                username,
-                play_as,
-                platform_name,
-                from_timestamp,
-                to_timestamp,
-            )
-            .await?)
+    ) -> Result<u64, PostgresError> {
+        let mut tx = self.pool.begin().await?;
+
+        // Without the sync state the next import downloads the whole history again
+        sqlx::query(
+            "DELETE FROM tracked_player
+            WHERE platform_name = $2 AND username = LOWER($1)",
+        )
+        .bind(username)
+        .bind(Into::<&'static str>::into(platform_name))
+        .execute(&mut *tx)
+        .await?;
+
+        // Positions and import errors of the games are removed with them. Games against
+        // another tracked player stay, they belong to that player's history as well
+        let result = sqlx::query(
+            "DELETE FROM game
+            WHERE platform_name = $2
+                AND (LOWER(white) = LOWER($1) OR LOWER(black) = LOWER($1))
+                AND NOT EXISTS (
+                    SELECT FROM tracked_player
+                    WHERE tracked_player.platform_name = game.platform_name
+                        AND tracked_player.username IN (LOWER(game.white), LOWER(game.black))
+                )",
+        )
+        .bind(username)
+        .bind(Into::<&'static str>::into(platform_name))
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn query_import_jobs(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<ImportJob>, PostgresError> {
+        let import_jobs: Vec<ImportJobDto> = sqlx::query_as(
+            "SELECT * FROM import_job
+            WHERE LOWER(username) = LOWER($1)
+                AND platform_name = $2
+            ORDER BY created_at DESC",
+        )
+        .bind(username)
+        .bind(Into::<&'static str>::into(platform_name))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(import_jobs
+            .into_iter()
+            .map(|import_job| import_job.into())
+            .collect::<_>())
+    }
+
+    /// Aggregates of the move statistics of `MoveStatDto`, with the SQL expressions of the
+    /// color the results count for and of the Elo ratings on either side. The averages are
+    /// clamped at 0, losses against opponents rated below 400 give a negative performance
+    fn move_stat_aggregates(play_as: &str, own_elo: &str, opponent_elo: &str) -> String {
+        format!(
+            "COUNT(*) total,
+                    SUM(case when game.winner = {play_as} then 1 else 0 end) wins,
+                    SUM(case when game.winner is NULL then 1 else 0 end) draws,
+                    GREATEST(AVG({opponent_elo}), 0)::INT avg_opponent_elo,
+                    MAX(game.finished_at) last_played_at,
+                    SUM(case when game.winner = 'White' then 1 else 0 end) white_wins,
+                    SUM(case when game.winner = 'Black' then 1 else 0 end) black_wins,
+                    GREATEST(AVG(NULLIF(game.white_elo, 0)), 0)::INT avg_white_elo,
+                    GREATEST(AVG(NULLIF(game.black_elo, 0)), 0)::INT avg_black_elo,
+                    SUM(case when game.winner <> {play_as} then 1 else 0 end) losses,
+                    (100.0 * SUM(case when game.winner = {play_as} then 1 when game.winner is NULL then 0.5 else 0 end) / COUNT(*))::FLOAT8 score,
+                    GREATEST(AVG(NULLIF({own_elo}, 0)), 0)::INT avg_own_elo,
+                    GREATEST(AVG(NULLIF({opponent_elo}, 0) + case when game.winner = {play_as} then 400 when game.winner <> {play_as} then -400 else 0 end), 0)::INT performance_rating,
+                    MIN(game.finished_at) first_played_at"
+        )
+    }
+
+    /// Aggregates of the move statistics for a color that differs per row
+    fn move_stat_aggregates_of_column(play_as: &str) -> String {
+        Self::move_stat_aggregates(
+            play_as,
+            &format!("case when {play_as} = 'White' then game.white_elo else game.black_elo end"),
+            &format!("case when {play_as} = 'White' then game.black_elo else game.white_elo end"),
+        )
+    }
+
+    /// Binds the game filter to `$5` to `$10` of `GAME_FILTER_CONDITION`
+    fn bind_game_filter<'q, O>(
+        query: QueryAs<'q, sqlx::Postgres, O, PgArguments>,
+        filter: &'q GameFilter,
+    ) -> QueryAs<'q, sqlx::Postgres, O, PgArguments> {
+        query
+            .bind(filter.from_timestamp())
+            .bind(filter.to_timestamp())
+            .bind(filter.time_classes().map(|time_classes| {
+                time_classes
+                    .iter()
+                    .map(|time_class| Into::<&'static str>::into(*time_class))
+                    .collect::<Vec<_>>()
+            }))
+            .bind(filter.rated_only())
+            .bind(filter.min_rating().map(|rating| *rating as i32))
+            .bind(filter.max_rating().map(|rating| *rating as i32))
+    }
+
+    /// Wins and the opponent count from the side of the player, or of the side to move
+    fn move_stats_play_as(position_fen: &Fen, scope: &MoveStatsScope) -> Color {
+        match scope {
+            MoveStatsScope::Player { play_as, .. } => *play_as,
+            MoveStatsScope::AllGames { .. } => position_fen.turn(),
+        }
+    }
+
+    /// Columns of the own and the opponent Elo rating of the player with the color
+    fn elo_columns(play_as: &Color) -> (&'static str, &'static str) {
+        match play_as {
+            Color::White => ("game.white_elo", "game.black_elo"),
+            Color::Black => ("game.black_elo", "game.white_elo"),
+        }
+    }
+
+    /// Keeps the games of the scope in the position, with the parameters bound by
+    /// `bind_move_stats_parameters`
+    fn move_stats_condition(scope: &MoveStatsScope, play_as: &Color) -> String {
+        let (own_elo, opponent_elo) = Self::elo_columns(play_as);
+        let player_condition = match scope {
+            MoveStatsScope::Player {
+                play_as: Color::White,
+                ..
+            } => "LOWER(game.white) = LOWER($4)",
+            MoveStatsScope::Player {
+                play_as: Color::Black,
+                ..
+            } => "LOWER(game.black) = LOWER($4)",
+            MoveStatsScope::AllGames { .. } => {
+                "($4::VARCHAR[] IS NULL OR LOWER(game.white) = ANY($4) OR LOWER(game.black) = ANY($4))"
+            }
+        };
+
+        format!(
+            "game.platform_name = $2
+                    AND position.key = $3
+                    AND {player_condition}
+                    AND {GAME_FILTER_CONDITION}
+                    AND ($11::INT IS NULL OR ({own_elo} > 0 AND {own_elo} >= $11))
+                    AND ($12::INT IS NULL OR ({own_elo} > 0 AND {own_elo} <= $12))
+                    AND ($13::INT IS NULL OR ({opponent_elo} > 0 AND {opponent_elo} >= $13))
+                    AND ($14::INT IS NULL OR ({opponent_elo} > 0 AND {opponent_elo} <= $14))"
+        )
+    }
+
+    /// Binds `$1` to `$14` of `move_stats_condition`, with the color the results count for
+    /// as `$1`
+    #[allow(clippy::too_many_arguments)]
+    fn bind_move_stats_parameters<'q, O>(
+        query: QueryAs<'q, sqlx::Postgres, O, PgArguments>,
+        play_as: &Color,
+        position_key: i64,
+        scope: &'q MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &'q GameFilter,
+        elo_filter: &EloFilter,
+    ) -> QueryAs<'q, sqlx::Postgres, O, PgArguments> {
+        let query = query
+            .bind(Into::<&'static str>::into(play_as))
+            .bind(Into::<&'static str>::into(platform_name))
+            .bind(position_key);
+        let query = match scope {
+            MoveStatsScope::Player { username, .. } => query.bind(username),
+            MoveStatsScope::AllGames { usernames } => {
+                query.bind(usernames.as_ref().map(|usernames| {
+                    usernames
+                        .iter()
+                        .map(|username| username.to_lowercase())
+                        .collect::<Vec<_>>()
+                }))
+            }
+        };
+        Self::bind_game_filter(query, filter)
+            .bind(elo_filter.min_own_elo().map(|elo| *elo as i32))
+            .bind(elo_filter.max_own_elo().map(|elo| *elo as i32))
+            .bind(elo_filter.min_opponent_elo().map(|elo| *elo as i32))
+            .bind(elo_filter.max_opponent_elo().map(|elo| *elo as i32))
+    }
+
+    pub async fn query_move_stats(
+        &self,
+        position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+    ) -> Result<Vec<MoveStat>, PostgresError> {
+        let play_as = Self::move_stats_play_as(position_fen, scope);
+        let (own_elo, opponent_elo) = Self::elo_columns(&play_as);
+
+        let sql = format!(
+            "SELECT game_position.next_move_uci,
+                    {aggregates}
+                FROM game_position
+                    JOIN game ON game.id = game_position.game_id
+                    JOIN position ON position.id = game_position.position_id
+                WHERE game_position.next_move_uci IS NOT NULL
+                    AND {condition}
+                GROUP BY game_position.next_move_uci",
+            aggregates = Self::move_stat_aggregates("$1", own_elo, opponent_elo),
+            condition = Self::move_stats_condition(scope, &play_as),
+        );
+        let position_key = position_key::from_fen(&position_fen.to_string())?;
+        let move_stats_dto: Vec<MoveStatDto> = Self::bind_move_stats_parameters(
+            sqlx::query_as(&sql),
+            &play_as,
+            position_key,
+            scope,
+            platform_name,
+            filter,
+            elo_filter,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let position_opening = self.opening_book.opening(&position_key).cloned();
+        move_stats_dto
+            .into_iter()
+            .map(|move_stat_dto| {
+                let next_key = position_key::after_move(
+                    &position_fen.to_string(),
+                    &move_stat_dto.next_move_uci,
+                )?;
+                let opening = next_key
+                    .and_then(|next_key| self.opening_book.opening(&next_key).cloned())
+                    .or_else(|| position_opening.clone());
+                Ok(MoveStat::from(move_stat_dto).with_opening(opening))
+            })
+            .collect::<_>()
+    }
+
+    /// Statistics of the lines of up to `depth` moves played from the position, following
+    /// each game from its first occurrence of the position
+    #[allow(clippy::too_many_arguments)]
+    pub async fn query_move_tree(
+        &self,
+        position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+        depth: u8,
+        min_games: u64,
+    ) -> Result<MoveTree, PostgresError> {
+        let play_as = Self::move_stats_play_as(position_fen, scope);
+
+        let sql = format!(
+            "WITH root AS (
+                SELECT DISTINCT ON (game.id) game.id game_id, game_position.move_idx
+                    FROM game_position
+                        JOIN game ON game.id = game_position.game_id
+                        JOIN position ON position.id = game_position.position_id
+                    WHERE {condition}
+                    ORDER BY game.id, game_position.move_idx
+            ),
+            line AS (
+                SELECT game_position.game_id,
+                        ARRAY_AGG(game_position.next_move_uci) OVER (
+                            PARTITION BY game_position.game_id ORDER BY game_position.move_idx
+                        ) moves,
+                        {line_play_as} play_as
+                    FROM root
+                        JOIN game_position ON game_position.game_id = root.game_id
+                            AND game_position.move_idx >= root.move_idx
+                            AND game_position.move_idx < root.move_idx + $15
+                            AND game_position.next_move_uci IS NOT NULL
+            )
+            SELECT line.moves,
+                    line.moves[ARRAY_LENGTH(line.moves, 1)] next_move_uci,
+                    {aggregates}
+                FROM line
+                    JOIN game ON game.id = line.game_id
+                GROUP BY line.moves
+                HAVING COUNT(*) >= $16",
+            condition = Self::move_stats_condition(scope, &play_as),
+            // Wins count for the player throughout the line, or for the side to move
+            line_play_as = match scope {
+                MoveStatsScope::Player { .. } => "$1::VARCHAR",
+                MoveStatsScope::AllGames { .. } => {
+                    "case when (game_position.move_idx - root.move_idx) % 2 = 0 then $1::VARCHAR
+                        when $1::VARCHAR = 'White' then 'Black'
+                        else 'White' end"
+                }
+            },
+            aggregates = Self::move_stat_aggregates_of_column("line.play_as"),
+        );
+        let lines_dto: Vec<MoveTreeLineDto> = Self::bind_move_stats_parameters(
+            sqlx::query_as(&sql),
+            &play_as,
+            position_key::from_fen(&position_fen.to_string())?,
+            scope,
+            platform_name,
+            filter,
+            elo_filter,
+        )
+        .bind(depth as i32)
+        .bind(min_games as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(move_tree::build(
+            position_fen,
+            lines_dto
+                .into_iter()
+                .map(|line_dto| line_dto.into())
+                .collect(),
+        )?)
+    }
+
+    async fn query_compared_move_stats(
+        &self,
+        position_fen: &Fen,
+        players: &[PlayerRef],
+        filter: &GameFilter,
+    ) -> Result<Vec<MoveComparison>, PostgresError> {
+        let sql = format!(
+            "WITH player AS (
+                SELECT * FROM UNNEST($1::VARCHAR[], $2::VARCHAR[], $3::VARCHAR[])
+                    WITH ORDINALITY AS player(platform_name, username, play_as, idx)
+            )
+            SELECT player.idx - 1 player_idx,
+                    game_position.next_move_uci,
+                    {}
+                FROM game_position
+                    JOIN position ON position.id = game_position.position_id
+                    JOIN game ON game.id = game_position.game_id
+                    JOIN player ON player.platform_name = game.platform_name
+                        AND LOWER(player.username) = LOWER(
+                            case when player.play_as = 'White' then game.white else game.black end
+                        )
+                WHERE position.key = $4
+                    AND game_position.next_move_uci IS NOT NULL
+                    AND {}
+                GROUP BY player.idx, game_position.next_move_uci",
+            Self::move_stat_aggregates_of_column("player.play_as"),
+            GAME_FILTER_CONDITION,
+        );
+        let query = sqlx::query_as(&sql)
+            .bind(
+                players
+                    .iter()
+                    .map(|player| Into::<&'static str>::into(player.platform_name()))
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                players
+                    .iter()
+                    .map(|player| player.username().as_str())
+                    .collect::<Vec<_>>(),
+            )
+            .bind(
+                players
+                    .iter()
+                    .map(|player| Into::<&'static str>::into(player.play_as()))
+                    .collect::<Vec<_>>(),
+            )
+            .bind(position_key::from_fen(&position_fen.to_string())?);
+        let player_stats_dto: Vec<PlayerMoveStatDto> = Self::bind_game_filter(query, filter)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(MoveComparison::from_player_stats(
+            players.len(),
+            player_stats_dto
+                .into_iter()
+                .map(|player_stat| {
+                    (
+                        player_stat.player_idx as usize,
+                        player_stat.move_stat.into(),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    async fn query_game(&self, game_id: &uuid::Uuid) -> Result<Option<GameDetail>, PostgresError> {
+        let game: Option<Game> = sqlx::query_as("SELECT * FROM game WHERE id = $1")
+            .bind(game_id)
+            .fetch_optional(&self.pool)
+            .await?
+            .map(|game: GameDto| game.into());
+
+        Ok(game.map(|game| {
+            // Positions are read with standard rules, like when the game was indexed
+            let mainline = match game.variant() {
+                Variant::Standard => read_positions(&game.pgn().to_string())
+                    .ok()
+                    .map(|positions| positions.into()),
+                _ => None,
+            };
+            GameDetail::new(game, mainline)
+        }))
+    }
+
+    async fn query_games(
+        &self,
+        search: &GameSearch,
+        after: Option<&GameCursor>,
+        limit: usize,
+    ) -> Result<GamePage, PostgresError> {
+        let sql = format!(
+            "SELECT game.* FROM game
+                WHERE game.platform_name = $1
+                    AND {}
+                    AND {}
+                    AND ($3::BIGINT IS NULL OR game.id IN (
+                        SELECT game_position.game_id FROM game_position
+                            JOIN position ON position.id = game_position.position_id
+                            WHERE position.key = $3
+                                AND ($4::TEXT IS NULL OR game_position.next_move_uci = $4)
+                    ))
+                    AND {}
+                    AND ($11::TIMESTAMPTZ IS NULL OR (game.finished_at, game.id) < ($11, $12))
+                ORDER BY game.finished_at DESC, game.id DESC
+                LIMIT $13",
+            match search.play_as() {
+                Some(Color::White) => "LOWER(game.white) = LOWER($2)",
+                Some(Color::Black) => "LOWER(game.black) = LOWER($2)",
+                None => "(LOWER(game.white) = LOWER($2) OR LOWER(game.black) = LOWER($2))",
+            },
+            match search.result() {
+                Some(GameResult::Win) => {
+                    "game.winner = CASE WHEN LOWER(game.white) = LOWER($2) THEN 'White' ELSE 'Black' END"
+                }
+                Some(GameResult::Loss) => {
+                    "game.winner = CASE WHEN LOWER(game.white) = LOWER($2) THEN 'Black' ELSE 'White' END"
+                }
+                Some(GameResult::Draw) => "game.winner IS NULL",
+                None => "TRUE",
+            },
+            GAME_FILTER_CONDITION,
+        );
+        let query = sqlx::query_as(&sql)
+            .bind(Into::<&'static str>::into(search.platform_name()))
+            .bind(search.username())
+            .bind(
+                search
+                    .position()
+                    .map(|position| position_key::from_fen(&position.fen().to_string()))
+                    .transpose()?,
+            )
+            .bind(search.position().and_then(PositionFilter::next_move_uci));
+        let mut games_dto: Vec<GameDto> = Self::bind_game_filter(query, search.filter())
+            .bind(after.map(GameCursor::finished_at))
+            .bind(after.map(GameCursor::id))
+            // One more game tells whether there is a next page
+            .bind(limit as i64 + 1)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let has_next_page = games_dto.len() > limit;
+        games_dto.truncate(limit);
+
+        Ok(GamePage::new(
+            games_dto.into_iter().map(|game| game.into()).collect(),
+            has_next_page,
+        ))
+    }
+
+    async fn query_opening_report(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+        play_as: &Color,
+        filter: &GameFilter,
+        trend_period: &TrendPeriod,
+    ) -> Result<Vec<OpeningFamilyReport>, PostgresError> {
+        let (player, opponent_elo) = match play_as {
+            Color::White => ("game.white", "game.black_elo"),
+            Color::Black => ("game.black", "game.white_elo"),
+        };
+
+        // The grouping sets give a row per family and a row per family and period
+        let sql = format!(
+            "SELECT SPLIT_PART(game.opening_name, ':', 1) family,
+                    DATE_TRUNC($4, game.finished_at) period_start,
+                    COALESCE(
+                        ARRAY_AGG(DISTINCT game.eco ORDER BY game.eco) FILTER (WHERE game.eco IS NOT NULL),
+                        '{{}}'
+                    ) ecos,
+                    COUNT(*) games,
+                    SUM(case when game.winner = $1 then 1 else 0 end) wins,
+                    SUM(case when game.winner is NULL then 1 else 0 end) draws,
+                    SUM(case when game.winner <> $1 then 1 else 0 end) losses,
+                    GREATEST(AVG(NULLIF({opponent_elo}, 0)), 0)::INT avg_opponent_elo
+                FROM game
+                WHERE game.platform_name = $2
+                    AND LOWER({player}) = LOWER($3)
+                    AND {GAME_FILTER_CONDITION}
+                GROUP BY GROUPING SETS ((family), (family, period_start))
+                ORDER BY family NULLS LAST, period_start NULLS FIRST",
+        );
+        let query = sqlx::query_as(&sql)
+            .bind(Into::<&'static str>::into(play_as))
+            .bind(Into::<&'static str>::into(platform_name))
+            .bind(username)
+            .bind(Into::<&'static str>::into(trend_period));
+        let rows_dto: Vec<OpeningReportRowDto> = Self::bind_game_filter(query, filter)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(OpeningReportRowDto::into_opening_reports(rows_dto))
+    }
+
+    async fn insert_repertoire(
+        &self,
+        name: &str,
+        color: &Color,
+        moves: &[RepertoireMove],
+    ) -> Result<Repertoire, PostgresError> {
+        let mut tx = self.pool.begin().await?;
+
+        let repertoire: RepertoireDto = sqlx::query_as(
+            "INSERT INTO repertoire (name, color)
+            VALUES ($1, $2)
+            RETURNING *",
+        )
+        .bind(name)
+        .bind(Into::<&'static str>::into(color))
+        .fetch_one(&mut *tx)
+        .await?;
+        Self::insert_repertoire_moves(&repertoire.id, moves, &mut tx).await?;
+
+        tx.commit().await?;
+
+        Ok(repertoire.into_repertoire(moves.to_vec()))
+    }
+
+    /// Changes the given fields of the repertoire, replacing all its moves if moves are given
+    async fn set_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+        name: Option<&str>,
+        color: Option<&Color>,
+        moves: Option<&[RepertoireMove]>,
+    ) -> Result<Option<Repertoire>, PostgresError> {
+        let mut tx = self.pool.begin().await?;
+
+        let repertoire: Option<RepertoireDto> = sqlx::query_as(
+            "UPDATE repertoire
+            SET name = COALESCE($2, name),
+                color = COALESCE($3, color),
+                updated_at = now()
+            WHERE id = $1
+            RETURNING *",
+        )
+        .bind(repertoire_id)
+        .bind(name)
+        .bind(color.map(Into::<&'static str>::into))
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(repertoire) = repertoire else {
+            return Ok(None);
+        };
+
+        if let Some(moves) = moves {
+            sqlx::query("DELETE FROM repertoire_move WHERE repertoire_id = $1")
+                .bind(repertoire_id)
+                .execute(&mut *tx)
+                .await?;
+            Self::insert_repertoire_moves(repertoire_id, moves, &mut tx).await?;
+        }
+        let mut moves = Self::query_repertoire_moves(&[*repertoire_id], &mut tx).await?;
+
+        tx.commit().await?;
+
+        Ok(Some(repertoire.into_repertoire(
+            moves.remove(repertoire_id).unwrap_or_default(),
+        )))
+    }
+
+    async fn insert_repertoire_moves(
+        repertoire_id: &uuid::Uuid,
+        moves: &[RepertoireMove],
+        conn: &mut PgConnection,
+    ) -> Result<(), PostgresError> {
+        let (mut keys, mut fens, mut ucis, mut sans) = (vec![], vec![], vec![], vec![]);
+        for repertoire_move in moves {
+            keys.push(*repertoire_move.position_key());
+            fens.push(repertoire_move.fen().to_string());
+            ucis.push(repertoire_move.uci().as_str());
+            sans.push(repertoire_move.san().as_str());
+        }
+
+        // The moves keep the order of the PGN, so the mainline is written back first
+        sqlx::query(
+            "INSERT INTO repertoire_move (
+                repertoire_id, move_idx, position_key, fen, move_uci, move_san
+            )
+            SELECT $1, move.move_idx - 1, move.position_key, move.fen, move.move_uci, move.move_san
+                FROM UNNEST($2::BIGINT[], $3::TEXT[], $4::TEXT[], $5::TEXT[])
+                    WITH ORDINALITY AS move(position_key, fen, move_uci, move_san, move_idx)
+            ON CONFLICT DO NOTHING",
+        )
+        .bind(repertoire_id)
+        .bind(keys)
+        .bind(fens)
+        .bind(ucis)
+        .bind(sans)
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Moves of the repertoires in the order they were stored, by repertoire id
+    async fn query_repertoire_moves(
+        repertoire_ids: &[uuid::Uuid],
+        conn: &mut PgConnection,
+    ) -> Result<HashMap<uuid::Uuid, Vec<RepertoireMove>>, PostgresError> {
+        let moves_dto: Vec<RepertoireMoveDto> = sqlx::query_as(
+            "SELECT repertoire_id, position_key, fen, move_uci, move_san
+                FROM repertoire_move
+                WHERE repertoire_id = ANY($1)
+                ORDER BY repertoire_id, move_idx",
+        )
+        .bind(repertoire_ids)
+        .fetch_all(&mut *conn)
+        .await?;
+
+        let mut moves: HashMap<uuid::Uuid, Vec<RepertoireMove>> = HashMap::new();
+        for move_dto in moves_dto {
+            moves
+                .entry(move_dto.repertoire_id)
+                .or_default()
+                .push(move_dto.into());
+        }
+
+        Ok(moves)
+    }
+
+    /// Lists the repertoires with the id, or all of them by name
+    async fn query_repertoires(
+        &self,
+        repertoire_id: Option<&uuid::Uuid>,
+    ) -> Result<Vec<Repertoire>, PostgresError> {
+        let mut conn = self.pool.acquire().await?;
+
+        let repertoires: Vec<RepertoireDto> = sqlx::query_as(
+            "SELECT * FROM repertoire
+            WHERE $1::UUID IS NULL OR id = $1
+            ORDER BY name, created_at",
+        )
+        .bind(repertoire_id)
+        .fetch_all(&mut *conn)
+        .await?;
+        let repertoire_ids = repertoires
+            .iter()
+            .map(|repertoire| repertoire.id)
+            .collect::<Vec<_>>();
+        let mut moves = Self::query_repertoire_moves(&repertoire_ids, &mut conn).await?;
+
+        Ok(repertoires
+            .into_iter()
+            .map(|repertoire| {
+                let repertoire_moves = moves.remove(&repertoire.id).unwrap_or_default();
+                repertoire.into_repertoire(repertoire_moves)
+            })
+            .collect())
+    }
+
+    async fn remove_repertoire(&self, repertoire_id: &uuid::Uuid) -> Result<bool, PostgresError> {
+        let result = sqlx::query("DELETE FROM repertoire WHERE id = $1")
+            .bind(repertoire_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn query_repertoire_deviations(
+        &self,
+        repertoire: &Repertoire,
+        username: &str,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+    ) -> Result<RepertoireDeviationReport, PostgresError> {
+        let player = match repertoire.color() {
+            Color::White => "game.white",
+            Color::Black => "game.black",
+        };
+
+        // A game leaves the repertoire with its first move that is not a move of the
+        // repertoire. It deviated if the repertoire has other moves in the position,
+        // otherwise the game followed the repertoire to its end
+        let sql = format!(
+            "WITH expected_move AS (
+                SELECT position_key,
+                       (ARRAY_AGG(fen ORDER BY move_idx))[1] fen,
+                       ARRAY_AGG(move_san ORDER BY move_idx) expected_sans
+                    FROM repertoire_move
+                    WHERE repertoire_id = $1
+                    GROUP BY position_key
+            ),
+            left_move AS (
+                SELECT game.winner, game.finished_at, first_left.*
+                    FROM game
+                    LEFT JOIN LATERAL (
+                        SELECT game_position.move_idx,
+                               position.key position_key,
+                               game_position.next_move_uci
+                            FROM game_position
+                            JOIN position ON position.id = game_position.position_id
+                            WHERE game_position.game_id = game.id
+                                AND game_position.next_move_uci IS NOT NULL
+                                AND NOT EXISTS (
+                                    SELECT 1 FROM repertoire_move
+                                        WHERE repertoire_move.repertoire_id = $1
+                                            AND repertoire_move.position_key = position.key
+                                            AND repertoire_move.move_uci = game_position.next_move_uci
+                                )
+                            ORDER BY game_position.move_idx
+                            LIMIT 1
+                    ) first_left ON TRUE
+                    WHERE game.platform_name = $3
+                        AND LOWER({player}) = LOWER($4)
+                        AND {GAME_FILTER_CONDITION}
+                        -- games without indexed positions can't be walked
+                        AND EXISTS (SELECT 1 FROM game_position WHERE game_position.game_id = game.id)
+            )
+            SELECT expected_move.fen,
+                   expected_move.expected_sans,
+                   CASE WHEN expected_move.fen IS NOT NULL THEN left_move.next_move_uci END played_uci,
+                   MIN(left_move.move_idx) move_idx,
+                   COUNT(*) games,
+                   SUM(case when left_move.winner = $2 then 1 else 0 end) wins,
+                   SUM(case when left_move.winner is NULL then 1 else 0 end) draws,
+                   SUM(case when left_move.winner <> $2 then 1 else 0 end) losses,
+                   MAX(left_move.finished_at) last_played_at
+                FROM left_move
+                LEFT JOIN expected_move ON expected_move.position_key = left_move.position_key
+                GROUP BY expected_move.position_key, expected_move.fen, expected_move.expected_sans, played_uci
+                ORDER BY games DESC, last_played_at DESC",
+        );
+        let query = sqlx::query_as(&sql)
+            .bind(repertoire.id())
+            .bind(Into::<&'static str>::into(repertoire.color()))
+            .bind(Into::<&'static str>::into(platform_name))
+            .bind(username);
+        let rows_dto: Vec<RepertoireDeviationRowDto> = Self::bind_game_filter(query, filter)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(RepertoireDeviationRowDto::into_deviation_report(
+            rows_dto,
+            repertoire.color(),
+        ))
+    }
+
+    async fn query_player_profile(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Option<PlayerProfile>, PostgresError> {
+        let summary: PlayerSummaryDto = sqlx::query_as(
+            "SELECT (ARRAY_AGG(player_game.username ORDER BY player_game.finished_at DESC))[1] username,
+                    COUNT(*) FILTER (WHERE player_game.is_white) white_games,
+                    COUNT(*) FILTER (WHERE player_game.is_white AND player_game.winner = 'White') white_wins,
+                    COUNT(*) FILTER (WHERE player_game.is_white AND player_game.winner IS NULL) white_draws,
+                    COUNT(*) FILTER (WHERE player_game.is_white AND player_game.winner = 'Black') white_losses,
+                    COUNT(*) FILTER (WHERE NOT player_game.is_white) black_games,
+                    COUNT(*) FILTER (WHERE NOT player_game.is_white AND player_game.winner = 'Black') black_wins,
+                    COUNT(*) FILTER (WHERE NOT player_game.is_white AND player_game.winner IS NULL) black_draws,
+                    COUNT(*) FILTER (WHERE NOT player_game.is_white AND player_game.winner = 'White') black_losses,
+                    MIN(player_game.elo) min_elo,
+                    MAX(player_game.elo) max_elo,
+                    (ARRAY_AGG(player_game.elo ORDER BY player_game.finished_at DESC)
+                        FILTER (WHERE player_game.elo IS NOT NULL))[1] current_elo,
+                    MIN(player_game.finished_at) first_game_at,
+                    MAX(player_game.finished_at) last_game_at
+                FROM (
+                    SELECT LOWER(game.white) = LOWER($2) is_white,
+                           CASE WHEN LOWER(game.white) = LOWER($2) THEN game.white ELSE game.black END username,
+                           -- Platforms report unknown ratings as 0
+                           NULLIF(CASE WHEN LOWER(game.white) = LOWER($2) THEN game.white_elo ELSE game.black_elo END, 0) elo,
+                           game.winner,
+                           game.finished_at
+                        FROM game
+                        WHERE game.platform_name = $1
+                            AND (
+                                LOWER(game.white) = LOWER($2)
+                                OR LOWER(game.black) = LOWER($2)
+                            )
+                ) player_game",
+        )
+        .bind(Into::<&'static str>::into(platform_name))
+        .bind(username)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let first_moves_as_white = self
+            .query_first_moves(username, &Color::White, platform_name)
+            .await?;
+        let first_moves_as_black = self
+            .query_first_moves(username, &Color::Black, platform_name)
+            .await?;
+
+        Ok(summary.into_player_profile(*platform_name, first_moves_as_white, first_moves_as_black))
+    }
+
+    /// Most played first moves of the player with the given color
+    async fn query_first_moves(
+        &self,
+        username: &str,
+        play_as: &Color,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<MoveStat>, PostgresError> {
+        let (player, own_elo, opponent_elo) = match play_as {
+            Color::White => ("game.white", "game.white_elo", "game.black_elo"),
+            Color::Black => ("game.black", "game.black_elo", "game.white_elo"),
+        };
+        let move_stats_dto: Vec<MoveStatDto> = sqlx::query_as(&format!(
+            "SELECT game_position.next_move_uci,
+                    {aggregates}
+                FROM game
+                    JOIN game_position ON game_position.game_id = game.id
+                WHERE game.platform_name = $2
+                    AND LOWER({player}) = LOWER($3)
+                    AND game_position.move_idx = $4
+                    AND game_position.next_move_uci IS NOT NULL
+                    -- Games set up from a FEN are indexed from their own first position
+                    AND EXISTS (
+                        SELECT FROM game_position start_position
+                            JOIN position ON position.id = start_position.position_id
+                        WHERE start_position.game_id = game.id
+                            AND start_position.move_idx = 0
+                            AND position.key = $6
+                    )
+                GROUP BY game_position.next_move_uci
+                ORDER BY total DESC, game_position.next_move_uci
+                LIMIT $5",
+            aggregates = Self::move_stat_aggregates("$1", own_elo, opponent_elo),
+        ))
+        .bind(Into::<&'static str>::into(play_as))
+        .bind(Into::<&'static str>::into(platform_name))
+        .bind(username)
+        .bind(match play_as {
+            Color::White => 0_i16,
+            Color::Black => 1_i16,
+        })
+        .bind(FIRST_MOVE_LIMIT)
+        .bind(position_key::starting())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(move_stats_dto
+            .into_iter()
+            .map(|move_stat_dto| move_stat_dto.into())
+            .collect::<_>())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PostgresError {
+    #[error(transparent)]
+    DatabaseError(#[from] sqlx::Error),
+    #[error(transparent)]
+    JoinError(#[from] tokio::task::JoinError),
+    #[error(transparent)]
+    InvalidPgn(#[from] InvalidPgnError),
+    #[error(transparent)]
+    InvalidFen(#[from] InvalidFenError),
+    #[error(transparent)]
+    SerializationError(#[from] std::io::Error),
+    #[error(transparent)]
+    PlatformError(#[from] PlatformError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<PostgresError> for GameRepositoryError {
+    fn from(value: PostgresError) -> Self {
+        match value {
+            PostgresError::DatabaseError(err) => {
+                GameRepositoryError::DatabaseError(err.to_string())
+            }
+            err => GameRepositoryError::Unknown(anyhow::anyhow!(err)),
+        }
+    }
+}
+
+#[async_trait]
+impl GameRepository for Postgres {
+    async fn store_games(
+        &self,
+        import_job: Option<&ImportJob>,
+        game_receiver: Receiver<Result<GameBatch, PlatformError>>,
+        progress_sender: Sender<StoreProgress>,
+    ) -> Result<(), GameRepositoryError> {
+        Ok(self
+            .save_games_from_receiver(import_job, game_receiver, progress_sender)
+            .await?)
+    }
+
+    async fn get_latest_game_timestamp_seconds(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+    ) -> Result<Option<u64>, GameRepositoryError> {
+        let tracked_player = self.query_tracked_player(platform_name, username).await?;
+
+        // The games of the last synced archive may be incomplete, so it is synced again
+        Ok(tracked_player
+            .as_ref()
+            .and_then(TrackedPlayer::last_synced_archive)
+            .and_then(ArchiveMonth::start)
+            .map(|timestamp| timestamp.timestamp().max(0) as u64))
+    }
+
+    async fn get_move_stats(
+        &self,
+        position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+    ) -> Result<Vec<MoveStat>, GameRepositoryError> {
+        Ok(self
+            .query_move_stats(position_fen, scope, platform_name, filter, elo_filter)
+            .await?)
+    }
+
+    async fn get_move_tree(
+        &self,
+        position_fen: &Fen,
+        scope: &MoveStatsScope,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+        elo_filter: &EloFilter,
+        depth: u8,
+        min_games: u64,
+    ) -> Result<MoveTree, GameRepositoryError> {
+        Ok(self
+            .query_move_tree(
+                position_fen,
+                scope,
+                platform_name,
+                filter,
+                elo_filter,
+                depth,
+                min_games,
+            )
+            .await?)
+    }
+
+    async fn get_game_import_errors(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<GameImportError>, GameRepositoryError> {
+        Ok(self
+            .query_game_import_errors(username, platform_name)
+            .await?)
+    }
+
+    async fn create_import_job(
+        &self,
+        platform_name: &PlatformName,
+        username: &str,
+        full_resync: bool,
+    ) -> Result<ImportJob, GameRepositoryError> {
+        Ok(self
+            .insert_import_job(platform_name, username, full_resync)
+            .await?)
+    }
+
+    async fn update_import_job_status(
+        &self,
+        import_job_id: &uuid::Uuid,
+        status: &ImportJobStatus,
+        error: Option<&str>,
+    ) -> Result<(), GameRepositoryError> {
+        Ok(self
+            .set_import_job_status(import_job_id, status, error)
+            .await?)
+    }
+
+    async fn get_unfinished_import_jobs(&self) -> Result<Vec<ImportJob>, GameRepositoryError> {
+        Ok(self.query_unfinished_import_jobs().await?)
+    }
+
+    async fn get_import_jobs(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Vec<ImportJob>, GameRepositoryError> {
+        Ok(self.query_import_jobs(username, platform_name).await?)
+    }
+
+    async fn get_import_job(
+        &self,
+        import_job_id: &uuid::Uuid,
+    ) -> Result<Option<ImportJob>, GameRepositoryError> {
+        Ok(self.query_import_job(import_job_id).await?)
+    }
+
+    async fn delete_player_games(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<u64, GameRepositoryError> {
+        Ok(self.remove_player_games(username, platform_name).await?)
+    }
+
+    async fn get_tracked_players(
+        &self,
+        platform_name: Option<&PlatformName>,
+    ) -> Result<Vec<TrackedPlayer>, GameRepositoryError> {
+        Ok(self.query_tracked_players(platform_name).await?)
+    }
+
+    async fn get_player_profile(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+    ) -> Result<Option<PlayerProfile>, GameRepositoryError> {
+        Ok(self.query_player_profile(username, platform_name).await?)
+    }
+
+    async fn get_opening_report(
+        &self,
+        username: &str,
+        platform_name: &PlatformName,
+        play_as: &Color,
+        filter: &GameFilter,
+        trend_period: &TrendPeriod,
+    ) -> Result<Vec<OpeningFamilyReport>, GameRepositoryError> {
+        Ok(self
+            .query_opening_report(username, platform_name, play_as, filter, trend_period)
+            .await?)
+    }
+
+    async fn get_games(
+        &self,
+        search: &GameSearch,
+        after: Option<&GameCursor>,
+        limit: usize,
+    ) -> Result<GamePage, GameRepositoryError> {
+        Ok(self.query_games(search, after, limit).await?)
+    }
+
+    async fn get_game(
+        &self,
+        game_id: &uuid::Uuid,
+    ) -> Result<Option<GameDetail>, GameRepositoryError> {
+        Ok(self.query_game(game_id).await?)
+    }
+
+    async fn compare_move_stats(
+        &self,
+        position_fen: &Fen,
+        players: &[PlayerRef],
+        filter: &GameFilter,
+    ) -> Result<Vec<MoveComparison>, GameRepositoryError> {
+        Ok(self
+            .query_compared_move_stats(position_fen, players, filter)
+            .await?)
+    }
+
+    async fn create_repertoire(
+        &self,
+        name: &str,
+        color: &Color,
+        moves: &[RepertoireMove],
+    ) -> Result<Repertoire, GameRepositoryError> {
+        Ok(self.insert_repertoire(name, color, moves).await?)
+    }
+
+    async fn update_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+        name: Option<&str>,
+        color: Option<&Color>,
+        moves: Option<&[RepertoireMove]>,
+    ) -> Result<Option<Repertoire>, GameRepositoryError> {
+        Ok(self
+            .set_repertoire(repertoire_id, name, color, moves)
+            .await?)
+    }
+
+    async fn delete_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+    ) -> Result<bool, GameRepositoryError> {
+        Ok(self.remove_repertoire(repertoire_id).await?)
+    }
+
+    async fn get_repertoires(&self) -> Result<Vec<Repertoire>, GameRepositoryError> {
+        Ok(self.query_repertoires(None).await?)
+    }
+
+    async fn get_repertoire(
+        &self,
+        repertoire_id: &uuid::Uuid,
+    ) -> Result<Option<Repertoire>, GameRepositoryError> {
+        Ok(self.query_repertoires(Some(repertoire_id)).await?.pop())
+    }
+
+    async fn get_repertoire_deviations(
+        &self,
+        repertoire: &Repertoire,
+        username: &str,
+        platform_name: &PlatformName,
+        filter: &GameFilter,
+    ) -> Result<RepertoireDeviationReport, GameRepositoryError> {
+        Ok(self
+            .query_repertoire_deviations(repertoire, username, platform_name, filter)
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+    use crate::domain::game::models::new_game::NewGame;
+
+    fn new_game_dto(pgn: &str, source_id: Option<&str>) -> NewGameDto {
+        NewGame::new(
+            "A".to_string(),
+            0,
+            "B".to_string(),
+            0,
+            None,
+            PlatformName::Pgn,
+            pgn.to_string(),
+            DateTime::UNIX_EPOCH,
+            None,
+            None,
+            false,
+            Variant::Standard,
+            source_id.map(str::to_string),
+        )
+        .into()
+    }
+
+    #[test]
+    fn test_unique_games() {
+        let games = vec![
+            new_game_dto("1. e4 e5 *", Some("e4")),
+            new_game_dto("1. d4 d5 *", Some("d4")),
+            new_game_dto("1. e4 e5 *", Some("e4")),
+        ];
+
+        let actual = Postgres::unique_games(games)
+            .into_iter()
+            .map(|game_dto| game_dto.pgn)
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, vec!["1. e4 e5 *", "1. d4 d5 *"]);
+    }
+
+    async fn insert_game(pool: &Pool<sqlx::Postgres>, white: &str, black: &str) {
+        sqlx::query(
+            "INSERT INTO game
+                (white, white_elo, black, black_elo, platform_name, pgn, finished_at, rated, variant)
+            VALUES ($1, 1500, $2, 1500, 'Lichess', '1. e4 e5 *', NOW(), TRUE, 'Standard')",
+        )
+        .bind(white)
+        .bind(black)
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = "src/outbound/postgres/migrations")]
+    #[ignore = "needs the Postgres server of DATABASE_URL"]
+    async fn test_remove_player_games_keeps_games_of_tracked_opponents(pool: Pool<sqlx::Postgres>) {
+        sqlx::query(
+            "INSERT INTO tracked_player (platform_name, username)
+            VALUES ('Lichess', 'alice'), ('Lichess', 'bob')",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        insert_game(&pool, "Alice", "Bob").await;
+        insert_game(&pool, "Carol", "alice").await;
+        insert_game(&pool, "Bob", "Carol").await;
+
+        let postgres = Postgres {
+            pool: pool.clone(),
+            opening_book: Arc::new(OpeningBook::new(
+                &crate::outbound::opening_line_keyer::Keyer,
+            )),
+        };
+        let removed = postgres
+            .remove_player_games("ALICE", &PlatformName::Lichess)
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        let games: Vec<(String, String)> =
+            sqlx::query_as("SELECT white, black FROM game ORDER BY white")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            games,
+            vec![
+                ("Alice".to_string(), "Bob".to_string()),
+                ("Bob".to_string(), "Carol".to_string()),
+            ]
+        );
+        let tracked: Vec<String> = sqlx::query_scalar("SELECT username FROM tracked_player")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(tracked, vec!["bob".to_string()]);
     }
 }
@@ -1,18 +1,32 @@
 use std::str::FromStr;
 
-use crate::domain::{
-    game::models::{
-        fen::Fen,
-        game::{Color, Game},
-        move_stat::MoveStat,
-        new_game::NewGame,
-        pgn::Pgn,
-        position::Position,
+use crate::{
+    domain::{
+        game::models::{
+            fen::Fen,
+            game::{Color, Game, TimeClass, Variant},
+            game_import_error::GameImportError,
+            import_job::{ImportJob, ImportJobStatus},
+            move_stat::MoveStat,
+            new_game::NewGame,
+            opening::Opening,
+            opening_report::{OpeningFamilyReport, OpeningTrendPoint},
+            pgn::Pgn,
+            player_profile::{ColorRecord, PlayerProfile, RatingSummary},
+            position::Position,
+            repertoire::{
+                Deviator, Repertoire, RepertoireDeviation, RepertoireDeviationReport,
+                RepertoireMove,
+            },
+            tracked_player::TrackedPlayer,
+        },
+        platform::models::{ArchiveMonth, PlatformName},
     },
-    platform::models::PlatformName,
+    outbound::{move_tree::MoveTreeLine, repertoire_pgn},
 };
 
 /// DTO for game model
+#[derive(sqlx::FromRow)]
 pub struct GameDto {
     pub id: uuid::Uuid,
     pub white: String,
@@ -23,6 +37,12 @@ pub struct GameDto {
     pub platform_name: String,
     pub pgn: String,
     pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub time_class: Option<String>,
+    pub time_control: Option<String>,
+    pub rated: bool,
+    pub variant: String,
+    pub eco: Option<String>,
+    pub opening_name: Option<String>,
 }
 
 impl From<GameDto> for Game {
@@ -30,9 +50,9 @@ impl From<GameDto> for Game {
         Self::new(
             value.id,
             value.white,
-            value.white_elo as i16,
+            value.white_elo,
             value.black,
-            value.black_elo as i16,
+            value.black_elo,
             match value.winner {
                 Some(value) => Color::from_str(&value).ok(),
                 None => None,
@@ -40,6 +60,16 @@ impl From<GameDto> for Game {
             PlatformName::from_str(&value.platform_name).unwrap_or(PlatformName::ChessCom),
             Pgn::new_unchecked(&value.pgn),
             value.finished_at,
+            value
+                .time_class
+                .and_then(|time_class| TimeClass::from_str(&time_class).ok()),
+            value.time_control,
+            value.rated,
+            Variant::from_str(&value.variant).unwrap_or(Variant::Other),
+            match (value.eco, value.opening_name) {
+                (Some(eco), Some(opening_name)) => Some(Opening::new(eco, opening_name)),
+                _ => None,
+            },
         )
     }
 }
@@ -53,75 +83,457 @@ pub struct NewGameDto {
     pub platform_name: String,
     pub pgn: String,
     pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub time_class: Option<String>,
+    pub time_control: Option<String>,
+    pub rated: bool,
+    pub variant: String,
+    /// empty for sources whose games are told apart by their players and end time
+    pub source_id: String,
 }
 
 impl From<NewGame> for NewGameDto {
     fn from(value: NewGame) -> Self {
         Self {
             white: value.white().clone(),
-            white_elo: *value.white_elo() as i16,
+            white_elo: *value.white_elo(),
             black: value.black().clone(),
-            black_elo: *value.black_elo() as i16,
+            black_elo: *value.black_elo(),
             winner: value
                 .winner()
-                .map(|color| Into::<&'static str>::into(color.clone()).to_string()),
+                .map(|color| Into::<&'static str>::into(*color).to_string()),
             platform_name: <&PlatformName as Into<&'static str>>::into(value.platform_name())
                 .to_string(),
             pgn: value.pgn().to_string(),
             finished_at: *value.finished_at(),
+            time_class: value
+                .time_class()
+                .map(|time_class| Into::<&'static str>::into(*time_class).to_string()),
+            time_control: value.time_control().cloned(),
+            rated: *value.rated(),
+            variant: Into::<&'static str>::into(*value.variant()).to_string(),
+            source_id: value.source_id().cloned().unwrap_or_default(),
         }
     }
 }
 
 /// DTO for position model
+#[derive(sqlx::FromRow)]
 pub struct PositionDto {
-    pub id: uuid::Uuid,
-    pub fen: String,
+    pub id: i32,
+    pub key: i64,
 }
 
 impl From<PositionDto> for Position {
     fn from(value: PositionDto) -> Self {
-        Self::new(value.id, Fen::new_unchecked(&value.fen))
+        Self::new(value.id, value.key)
     }
 }
 
 pub struct NewPositionDto {
-    pub fen: String,
+    pub key: i64,
 }
 
 pub struct GamePositionDto {
     pub game_id: uuid::Uuid,
-    pub position_id: uuid::Uuid,
+    pub position_id: i32,
     pub move_idx: i16,
     pub next_move_uci: Option<String>,
 }
 
+/// PGN and keys of the positions in move order of a game whose opening wasn't checked yet
+#[derive(sqlx::FromRow)]
+pub struct UncheckedGameOpeningDto {
+    pub game_id: uuid::Uuid,
+    pub pgn: String,
+    pub keys: Vec<i64>,
+}
+
+/// Move statistics of one of the compared players
+#[derive(sqlx::FromRow)]
+pub struct PlayerMoveStatDto {
+    /// index of the player in the compared players
+    pub player_idx: i64,
+    #[sqlx(flatten)]
+    pub move_stat: MoveStatDto,
+}
+
+/// Move statistics of a line of a move tree, the moves being played from its root
+#[derive(sqlx::FromRow)]
+pub struct MoveTreeLineDto {
+    pub moves: Vec<String>,
+    #[sqlx(flatten)]
+    pub move_stat: MoveStatDto,
+}
+
+impl From<MoveTreeLineDto> for MoveTreeLine {
+    fn from(value: MoveTreeLineDto) -> Self {
+        MoveTreeLine {
+            moves: value.moves,
+            stat: value.move_stat.into(),
+        }
+    }
+}
+
 #[derive(sqlx::FromRow)]
 pub struct MoveStatDto {
     pub next_move_uci: String,
     pub total: i64,
     pub wins: i64,
     pub draws: i64,
+    pub losses: i64,
+    pub score: f64,
     pub avg_opponent_elo: i32,
+    pub avg_own_elo: i32,
+    pub performance_rating: i32,
+    pub first_played_at: chrono::DateTime<chrono::Utc>,
     pub last_played_at: chrono::DateTime<chrono::Utc>,
+    pub white_wins: i64,
+    pub black_wins: i64,
+    pub avg_white_elo: i32,
+    pub avg_black_elo: i32,
 }
 
-impl Into<MoveStat> for MoveStatDto {
-    fn into(self) -> MoveStat {
+impl From<MoveStatDto> for MoveStat {
+    fn from(value: MoveStatDto) -> Self {
         MoveStat::new(
-            self.next_move_uci,
-            self.total as u64,
-            self.wins as u64,
-            self.draws as u64,
-            self.avg_opponent_elo as u16,
-            self.last_played_at,
+            value.next_move_uci,
+            value.total as u64,
+            value.wins as u64,
+            value.draws as u64,
+            value.losses as u64,
+            value.score,
+            value.avg_opponent_elo as u16,
+            value.avg_own_elo as u16,
+            value.performance_rating as u16,
+            value.first_played_at,
+            value.last_played_at,
+            value.white_wins as u64,
+            value.black_wins as u64,
+            value.avg_white_elo as u16,
+            value.avg_black_elo as u16,
         )
     }
 }
 
+/// Aggregates of the games of a player, which are NULL if the player has no games
+#[derive(sqlx::FromRow)]
+pub struct PlayerSummaryDto {
+    pub username: Option<String>,
+    pub white_games: i64,
+    pub white_wins: i64,
+    pub white_draws: i64,
+    pub white_losses: i64,
+    pub black_games: i64,
+    pub black_wins: i64,
+    pub black_draws: i64,
+    pub black_losses: i64,
+    pub min_elo: Option<i16>,
+    pub max_elo: Option<i16>,
+    pub current_elo: Option<i16>,
+    pub first_game_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_game_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl PlayerSummaryDto {
+    pub fn into_player_profile(
+        self,
+        platform_name: PlatformName,
+        first_moves_as_white: Vec<MoveStat>,
+        first_moves_as_black: Vec<MoveStat>,
+    ) -> Option<PlayerProfile> {
+        Some(PlayerProfile::new(
+            self.username?,
+            platform_name,
+            ColorRecord::new(
+                self.white_games as u64,
+                self.white_wins as u64,
+                self.white_draws as u64,
+                self.white_losses as u64,
+            ),
+            ColorRecord::new(
+                self.black_games as u64,
+                self.black_wins as u64,
+                self.black_draws as u64,
+                self.black_losses as u64,
+            ),
+            match (self.min_elo, self.max_elo, self.current_elo) {
+                (Some(min), Some(max), Some(current)) => {
+                    Some(RatingSummary::new(min as u16, max as u16, current as u16))
+                }
+                _ => None,
+            },
+            self.first_game_at?,
+            self.last_game_at?,
+            first_moves_as_white,
+            first_moves_as_black,
+        ))
+    }
+}
+
+/// Results of an opening family, over all its games if `period_start` is NULL, otherwise
+/// over the games of the period
+#[derive(sqlx::FromRow)]
+pub struct OpeningReportRowDto {
+    pub family: Option<String>,
+    pub period_start: Option<chrono::DateTime<chrono::Utc>>,
+    pub ecos: Vec<String>,
+    pub games: i64,
+    pub wins: i64,
+    pub draws: i64,
+    pub losses: i64,
+    pub avg_opponent_elo: i32,
+}
+
+impl OpeningReportRowDto {
+    /// Groups the rows ordered by family, with the total of each family before its periods,
+    /// into reports, the most played family first
+    pub fn into_opening_reports(rows: Vec<Self>) -> Vec<OpeningFamilyReport> {
+        let mut reports: Vec<OpeningFamilyReport> = vec![];
+        for row in rows {
+            let record = ColorRecord::new(
+                row.games as u64,
+                row.wins as u64,
+                row.draws as u64,
+                row.losses as u64,
+            );
+            match (row.period_start, reports.last_mut()) {
+                (Some(period_start), Some(report)) => {
+                    report.push_trend_point(OpeningTrendPoint::new(period_start, record))
+                }
+                (Some(_), None) => {}
+                (None, _) => reports.push(OpeningFamilyReport::new(
+                    row.family,
+                    row.ecos,
+                    record,
+                    row.avg_opponent_elo as u16,
+                    vec![],
+                )),
+            }
+        }
+
+        reports.sort_by(|a, b| b.record().games().cmp(a.record().games()));
+        reports
+    }
+}
+
 #[derive(sqlx::FromRow, Clone)]
 pub struct InsertedGameDto {
     pub id: uuid::Uuid,
     pub pgn: String,
     pub finished_at: chrono::DateTime<chrono::Utc>,
+    pub variant: String,
+}
+
+/// Game written by a full resync, which either inserted it or refreshed the stored row
+#[derive(sqlx::FromRow)]
+pub struct UpsertedGameDto {
+    #[sqlx(flatten)]
+    pub game: InsertedGameDto,
+    pub inserted: bool,
+}
+
+impl InsertedGameDto {
+    /// Positions are only indexed for games played with standard rules
+    pub fn is_indexed(&self) -> bool {
+        self.variant == Into::<&'static str>::into(Variant::Standard)
+    }
+}
+
+pub struct NewGameImportErrorDto {
+    pub game_id: uuid::Uuid,
+    pub reason: String,
+    pub san: Option<String>,
+    pub ply: Option<i16>,
+}
+
+/// DTO for game import error model
+#[derive(sqlx::FromRow)]
+pub struct GameImportErrorDto {
+    pub game_id: uuid::Uuid,
+    pub reason: String,
+    pub san: Option<String>,
+    pub ply: Option<i16>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<GameImportErrorDto> for GameImportError {
+    fn from(value: GameImportErrorDto) -> Self {
+        GameImportError::new(
+            value.game_id,
+            value.reason,
+            value.san,
+            value.ply.map(|ply| ply as u16),
+            value.created_at,
+        )
+    }
+}
+
+/// DTO for import job model
+#[derive(sqlx::FromRow)]
+pub struct ImportJobDto {
+    pub id: uuid::Uuid,
+    pub username: String,
+    pub platform_name: String,
+    pub status: String,
+    pub full_resync: bool,
+    pub from_timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub last_archive_year: Option<i16>,
+    pub last_archive_month: Option<i16>,
+    pub games_inserted: i32,
+    pub error: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub finished_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+impl From<ImportJobDto> for ImportJob {
+    fn from(value: ImportJobDto) -> Self {
+        ImportJob::new(
+            value.id,
+            value.username,
+            PlatformName::from_str(&value.platform_name).unwrap_or(PlatformName::ChessCom),
+            ImportJobStatus::from_str(&value.status).unwrap_or(ImportJobStatus::Failed),
+            value.full_resync,
+            value.from_timestamp,
+            match (value.last_archive_year, value.last_archive_month) {
+                (Some(year), Some(month)) => Some(ArchiveMonth::new(year as i32, month as u32)),
+                _ => None,
+            },
+            value.games_inserted as usize,
+            value.error,
+            value.created_at,
+            value.updated_at,
+            value.finished_at,
+        )
+    }
+}
+
+/// DTO for tracked player model
+#[derive(sqlx::FromRow)]
+pub struct TrackedPlayerDto {
+    pub platform_name: String,
+    pub username: String,
+    pub last_synced_year: Option<i16>,
+    pub last_synced_month: Option<i16>,
+    pub last_synced_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub game_count: i32,
+}
+
+impl From<TrackedPlayerDto> for TrackedPlayer {
+    fn from(value: TrackedPlayerDto) -> Self {
+        TrackedPlayer::new(
+            PlatformName::from_str(&value.platform_name).unwrap_or(PlatformName::ChessCom),
+            value.username,
+            match (value.last_synced_year, value.last_synced_month) {
+                (Some(year), Some(month)) => Some(ArchiveMonth::new(year as i32, month as u32)),
+                _ => None,
+            },
+            value.last_synced_at,
+            value.game_count as usize,
+        )
+    }
+}
+
+/// DTO for repertoire model, the moves are queried separately
+#[derive(sqlx::FromRow)]
+pub struct RepertoireDto {
+    pub id: uuid::Uuid,
+    pub name: String,
+    pub color: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RepertoireDto {
+    pub fn into_repertoire(self, moves: Vec<RepertoireMove>) -> Repertoire {
+        Repertoire::new(
+            self.id,
+            self.name,
+            Color::from_str(&self.color).unwrap_or(Color::White),
+            moves,
+            self.created_at,
+            self.updated_at,
+        )
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub struct RepertoireMoveDto {
+    pub repertoire_id: uuid::Uuid,
+    pub position_key: i64,
+    pub fen: String,
+    pub move_uci: String,
+    pub move_san: String,
+}
+
+impl From<RepertoireMoveDto> for RepertoireMove {
+    fn from(value: RepertoireMoveDto) -> Self {
+        RepertoireMove::new(
+            Fen::new_unchecked(&value.fen),
+            value.position_key,
+            value.move_uci,
+            value.move_san,
+        )
+    }
+}
+
+/// Games that left a repertoire with the same move in the same position of it. The games
+/// that didn't leave it before it ended have no position
+#[derive(sqlx::FromRow)]
+pub struct RepertoireDeviationRowDto {
+    pub fen: Option<String>,
+    pub expected_sans: Option<Vec<String>>,
+    pub played_uci: Option<String>,
+    pub move_idx: Option<i16>,
+    pub games: i64,
+    pub wins: i64,
+    pub draws: i64,
+    pub losses: i64,
+    pub last_played_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl RepertoireDeviationRowDto {
+    /// Sorts the rows of the games of the player with the color of the repertoire into the
+    /// games that followed it and the deviations, keeping the order of the rows
+    pub fn into_deviation_report(rows: Vec<Self>, color: &Color) -> RepertoireDeviationReport {
+        let games = rows.iter().map(|row| row.games as u64).sum();
+        let mut followed_games = 0;
+        let mut deviations = vec![];
+        for row in rows {
+            let row_games = row.games as u64;
+            match row.into_deviation(color) {
+                Some(deviation) => deviations.push(deviation),
+                None => followed_games += row_games,
+            }
+        }
+
+        RepertoireDeviationReport::new(games, followed_games, deviations)
+    }
+
+    /// Deviation of the games in the repertoire of the color, `None` for the games that
+    /// followed it
+    fn into_deviation(self, color: &Color) -> Option<RepertoireDeviation> {
+        let fen = Fen::new_unchecked(&self.fen?);
+        let played_uci = self.played_uci?;
+
+        Some(RepertoireDeviation::new(
+            fen.clone(),
+            self.move_idx? as u16 + 1,
+            if fen.turn() == *color {
+                Deviator::Player
+            } else {
+                Deviator::Opponent
+            },
+            played_uci.clone(),
+            repertoire_pgn::san(&fen, &played_uci).unwrap_or_else(|| played_uci.clone()),
+            self.expected_sans.unwrap_or_default(),
+            ColorRecord::new(
+                self.games as u64,
+                self.wins as u64,
+                self.draws as u64,
+                self.losses as u64,
+            ),
+            self.last_played_at,
+        ))
+    }
 }
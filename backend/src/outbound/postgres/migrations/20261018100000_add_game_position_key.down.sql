@@ -0,0 +1,5 @@
+DROP INDEX game_position_key_nextmove_idx;
+ALTER TABLE game_position DROP COLUMN position_key;
+CREATE INDEX game_position_fen_nextmove_idx ON game_position (fen, game_id, next_move_uci)
+WHERE next_move_uci IS NOT NULL;
+DROP TABLE game_reindex;
@@ -0,0 +1,17 @@
+-- Games whose positions have to be computed again from their PGN, which is done on startup
+CREATE TABLE game_reindex (
+    game_id UUID PRIMARY KEY REFERENCES game(id) ON DELETE CASCADE
+);
+
+-- Transposition-aware position key. Keys can't be computed in SQL, so the stored positions
+-- are dropped and their games indexed again
+INSERT INTO game_reindex (game_id)
+SELECT DISTINCT game_id FROM game_position
+WHERE game_id IS NOT NULL;
+
+DELETE FROM game_position;
+
+ALTER TABLE game_position ADD COLUMN position_key BIGINT NOT NULL;
+DROP INDEX game_position_fen_nextmove_idx;
+CREATE INDEX game_position_key_nextmove_idx ON game_position (position_key, game_id, next_move_uci)
+WHERE next_move_uci IS NOT NULL;
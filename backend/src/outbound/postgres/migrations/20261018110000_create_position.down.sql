@@ -0,0 +1,18 @@
+DROP INDEX game_position_position_nextmove_idx;
+
+-- FEN strings can't be restored from the position key, so the positions are dropped and
+-- their games indexed again from the PGN on startup
+INSERT INTO game_reindex (game_id)
+SELECT DISTINCT game_id FROM game_position
+WHERE game_id IS NOT NULL
+ON CONFLICT DO NOTHING;
+
+DELETE FROM game_position;
+
+ALTER TABLE game_position
+    ADD COLUMN fen TEXT NOT NULL,
+    ADD COLUMN position_key BIGINT NOT NULL,
+    DROP COLUMN position_id;
+DROP TABLE position;
+CREATE INDEX game_position_key_nextmove_idx ON game_position (position_key, game_id, next_move_uci)
+WHERE next_move_uci IS NOT NULL;
@@ -0,0 +1,21 @@
+CREATE TABLE position (
+    id INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+    key BIGINT NOT NULL UNIQUE
+);
+
+INSERT INTO position (key)
+SELECT DISTINCT position_key FROM game_position;
+
+ALTER TABLE game_position ADD COLUMN position_id INTEGER REFERENCES position(id);
+
+UPDATE game_position SET position_id = position.id
+FROM position
+WHERE position.key = game_position.position_key;
+
+DROP INDEX game_position_key_nextmove_idx;
+ALTER TABLE game_position
+    ALTER COLUMN position_id SET NOT NULL,
+    DROP COLUMN fen,
+    DROP COLUMN position_key;
+CREATE INDEX game_position_position_nextmove_idx ON game_position (position_id, game_id, next_move_uci)
+WHERE next_move_uci IS NOT NULL;
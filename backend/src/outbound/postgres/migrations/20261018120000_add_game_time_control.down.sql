@@ -0,0 +1,3 @@
+ALTER TABLE game DROP COLUMN rated;
+ALTER TABLE game DROP COLUMN time_control;
+ALTER TABLE game DROP COLUMN time_class;
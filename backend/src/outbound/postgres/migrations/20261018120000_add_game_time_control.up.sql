@@ -0,0 +1,27 @@
+ALTER TABLE game ADD COLUMN time_class VARCHAR;
+ALTER TABLE game ADD COLUMN time_control VARCHAR;
+-- Platforms didn't report the rated flag before, so existing games count as rated
+ALTER TABLE game ADD COLUMN rated BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE game ALTER COLUMN rated DROP DEFAULT;
+
+UPDATE game
+SET time_control = substring(pgn FROM '\[TimeControl "([0-9/+:]+)"\]');
+
+-- Same estimate as TimeClass::from_time_control: base + 40 * increment
+UPDATE game
+SET time_class = CASE
+    WHEN time_control LIKE '%/%' THEN
+        CASE WHEN split_part(split_part(time_control, ':', 1), '/', 2)::INT >= 86400
+            THEN 'Daily' ELSE 'Classical' END
+    ELSE
+        CASE
+            WHEN split_part(time_control, '+', 1)::INT
+                + 40 * COALESCE(NULLIF(split_part(time_control, '+', 2), ''), '0')::INT < 180 THEN 'Bullet'
+            WHEN split_part(time_control, '+', 1)::INT
+                + 40 * COALESCE(NULLIF(split_part(time_control, '+', 2), ''), '0')::INT < 480 THEN 'Blitz'
+            WHEN split_part(time_control, '+', 1)::INT
+                + 40 * COALESCE(NULLIF(split_part(time_control, '+', 2), ''), '0')::INT < 1500 THEN 'Rapid'
+            ELSE 'Classical'
+        END
+    END
+WHERE time_control ~ '^[0-9]+(\+[0-9]+)?$' OR time_control ~ '^[0-9]+/[0-9]+';
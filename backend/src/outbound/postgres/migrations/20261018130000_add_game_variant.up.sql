@@ -0,0 +1,30 @@
+ALTER TABLE game ADD COLUMN variant VARCHAR NOT NULL DEFAULT 'Standard';
+ALTER TABLE game ALTER COLUMN variant DROP DEFAULT;
+
+-- Same mapping as Variant::from_name for the PGN Variant tag
+UPDATE game
+SET variant = CASE regexp_replace(lower(substring(pgn FROM '\[Variant "([^"]*)"\]')), '[^a-z0-9]', '', 'g')
+    WHEN 'chess960' THEN 'Chess960'
+    WHEN 'fischerandom' THEN 'Chess960'
+    WHEN 'fischerrandom' THEN 'Chess960'
+    WHEN 'crazyhouse' THEN 'Crazyhouse'
+    WHEN 'bughouse' THEN 'Bughouse'
+    WHEN 'threecheck' THEN 'ThreeCheck'
+    WHEN '3check' THEN 'ThreeCheck'
+    WHEN 'kingofthehill' THEN 'KingOfTheHill'
+    WHEN 'atomic' THEN 'Atomic'
+    WHEN 'antichess' THEN 'Antichess'
+    WHEN 'suicide' THEN 'Antichess'
+    WHEN 'giveaway' THEN 'Antichess'
+    WHEN 'horde' THEN 'Horde'
+    WHEN 'racingkings' THEN 'RacingKings'
+    ELSE 'Other'
+END
+WHERE regexp_replace(lower(substring(pgn FROM '\[Variant "([^"]*)"\]')), '[^a-z0-9]', '', 'g')
+    NOT IN ('', 'chess', 'standard', 'fromposition', 'oddschess');
+
+-- Positions of other variants were read with standard rules
+DELETE FROM game_position
+USING game
+WHERE game.id = game_position.game_id
+AND game.variant <> 'Standard';
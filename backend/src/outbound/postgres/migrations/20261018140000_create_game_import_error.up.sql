@@ -0,0 +1,7 @@
+CREATE TABLE game_import_error (
+    game_id UUID PRIMARY KEY REFERENCES game(id) ON DELETE CASCADE,
+    reason VARCHAR NOT NULL,
+    san VARCHAR,
+    ply SMALLINT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
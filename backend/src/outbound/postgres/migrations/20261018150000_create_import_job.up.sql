@@ -0,0 +1,20 @@
+CREATE TABLE import_job (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    username VARCHAR NOT NULL,
+    platform_name VARCHAR NOT NULL,
+    status VARCHAR NOT NULL DEFAULT 'Pending',
+    from_timestamp TIMESTAMP WITH TIME ZONE,
+    last_archive_year SMALLINT,
+    last_archive_month SMALLINT,
+    games_inserted INTEGER NOT NULL DEFAULT 0,
+    error VARCHAR,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    finished_at TIMESTAMP WITH TIME ZONE
+);
+
+-- A player has at most one unfinished import per platform
+CREATE UNIQUE INDEX import_job_unfinished_idx ON import_job (LOWER(username), platform_name)
+    WHERE status IN ('Pending', 'Running');
+
+CREATE INDEX import_job_player_idx ON import_job (LOWER(username), platform_name, created_at);
@@ -0,0 +1 @@
+ALTER TABLE import_job DROP COLUMN full_resync;
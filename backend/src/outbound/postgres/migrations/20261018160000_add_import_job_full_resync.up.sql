@@ -0,0 +1 @@
+ALTER TABLE import_job ADD COLUMN full_resync BOOLEAN NOT NULL DEFAULT FALSE;
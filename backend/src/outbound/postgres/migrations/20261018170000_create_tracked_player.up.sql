@@ -0,0 +1,42 @@
+CREATE TABLE tracked_player (
+    platform_name VARCHAR NOT NULL,
+    -- lowercased, platforms treat usernames case-insensitively
+    username VARCHAR NOT NULL,
+    last_synced_year SMALLINT,
+    last_synced_month SMALLINT,
+    last_synced_at TIMESTAMP WITH TIME ZONE,
+    game_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (platform_name, username)
+);
+
+-- The players imported so far continue at the last archive their own imports committed
+WITH synced_player AS (
+    SELECT platform_name,
+           LOWER(username) AS username,
+           MAX(make_date(last_archive_year, last_archive_month, 1)) AS last_archive,
+           MAX(finished_at) FILTER (WHERE status = 'Done') AS last_synced_at
+        FROM import_job
+        GROUP BY platform_name, LOWER(username)
+)
+INSERT INTO tracked_player (
+    platform_name,
+    username,
+    last_synced_year,
+    last_synced_month,
+    last_synced_at,
+    game_count
+)
+SELECT synced_player.platform_name,
+       synced_player.username,
+       EXTRACT(YEAR FROM synced_player.last_archive),
+       EXTRACT(MONTH FROM synced_player.last_archive),
+       synced_player.last_synced_at,
+       (
+           SELECT COUNT(*) FROM game
+           WHERE game.platform_name = synced_player.platform_name
+               AND (
+                   LOWER(game.white) = synced_player.username
+                   OR LOWER(game.black) = synced_player.username
+               )
+       )
+    FROM synced_player;
@@ -0,0 +1 @@
+DROP INDEX game_position_game_moveidx_idx;
@@ -0,0 +1,2 @@
+-- Looks up the moves of a game, e.g. the first moves of the games of a player
+CREATE INDEX game_position_game_moveidx_idx ON game_position (game_id, move_idx);
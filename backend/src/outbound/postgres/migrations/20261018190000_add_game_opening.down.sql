@@ -0,0 +1,2 @@
+ALTER TABLE game DROP COLUMN opening_name;
+ALTER TABLE game DROP COLUMN eco;
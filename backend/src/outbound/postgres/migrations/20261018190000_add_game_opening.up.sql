@@ -0,0 +1,4 @@
+-- Opening of the deepest position of the game found in the opening book, existing games
+-- are classified when the server starts
+ALTER TABLE game ADD COLUMN eco VARCHAR(3);
+ALTER TABLE game ADD COLUMN opening_name VARCHAR;
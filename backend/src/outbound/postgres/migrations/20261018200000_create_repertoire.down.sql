@@ -0,0 +1,2 @@
+DROP TABLE repertoire_move;
+DROP TABLE repertoire;
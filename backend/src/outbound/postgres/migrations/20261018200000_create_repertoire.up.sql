@@ -0,0 +1,18 @@
+CREATE TABLE repertoire (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR NOT NULL,
+    color CHAR(5) NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT now()
+);
+
+CREATE TABLE repertoire_move (
+    repertoire_id UUID NOT NULL REFERENCES repertoire(id) ON DELETE CASCADE,
+    -- order of the move in the PGN the repertoire was read from
+    move_idx INTEGER NOT NULL,
+    position_key BIGINT NOT NULL,
+    fen TEXT NOT NULL,
+    move_uci TEXT NOT NULL,
+    move_san TEXT NOT NULL,
+    PRIMARY KEY (repertoire_id, position_key, move_uci)
+);
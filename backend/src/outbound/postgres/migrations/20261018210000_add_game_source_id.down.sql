@@ -0,0 +1,13 @@
+-- Games that are only told apart by their PGN can't be kept
+DELETE FROM game
+USING game AS other_game
+WHERE game.white = other_game.white
+    AND game.black = other_game.black
+    AND game.finished_at = other_game.finished_at
+    AND game.platform_name = other_game.platform_name
+    AND game.id > other_game.id;
+
+ALTER TABLE game DROP CONSTRAINT game_white_black_finished_at_platform_name_source_id_key;
+ALTER TABLE game ADD CONSTRAINT game_white_black_finished_at_platform_name_key
+    UNIQUE (white, black, finished_at, platform_name);
+ALTER TABLE game DROP COLUMN source_id;
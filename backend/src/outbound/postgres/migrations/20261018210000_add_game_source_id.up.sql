@@ -0,0 +1,9 @@
+-- Tells apart games of the same players that finished at the same time. Games of uploaded
+-- PGN files often only have a date, so they are identified by a digest of their PGN
+ALTER TABLE game ADD COLUMN source_id VARCHAR NOT NULL DEFAULT '';
+
+UPDATE game SET source_id = md5(pgn) WHERE platform_name = 'Pgn';
+
+ALTER TABLE game DROP CONSTRAINT game_white_black_finished_at_platform_name_key;
+ALTER TABLE game ADD CONSTRAINT game_white_black_finished_at_platform_name_source_id_key
+    UNIQUE (white, black, finished_at, platform_name, source_id);
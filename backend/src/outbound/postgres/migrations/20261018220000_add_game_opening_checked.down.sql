@@ -0,0 +1,2 @@
+DROP INDEX game_opening_unchecked_idx;
+ALTER TABLE game DROP COLUMN opening_checked;
@@ -0,0 +1,7 @@
+-- Games are classified when they are stored. Existing games without an opening are
+-- classified once on the next start, which then marks them as checked
+ALTER TABLE game ADD COLUMN opening_checked BOOLEAN NOT NULL DEFAULT TRUE;
+
+UPDATE game SET opening_checked = FALSE WHERE eco IS NULL;
+
+CREATE INDEX game_opening_unchecked_idx ON game (id) WHERE NOT opening_checked;
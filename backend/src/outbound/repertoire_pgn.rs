@@ -0,0 +1,373 @@
+use pgn_reader::{RawTag, Reader, SanPlus, Skip, Visitor};
+use shakmaty::{CastlingMode, Chess, Color, EnPassantMode, Position as _, uci::UciMove};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    ops::ControlFlow,
+    str::FromStr,
+};
+
+use crate::{
+    domain::game::models::{
+        errors::InvalidPgnError,
+        fen::Fen,
+        repertoire::{Repertoire, RepertoireMove},
+    },
+    outbound::position_key,
+};
+
+/// Collects the moves of the mainline and of the variations of a game, unlike
+/// `PositionVisitor` which stays in the mainline
+struct RepertoireVisitor;
+
+struct RepertoireMovetext {
+    chess: Chess,
+    /// position before the last move, where a variation replacing the move starts
+    before_last: Chess,
+    /// positions to continue from after the open variations, the innermost last
+    variations: Vec<(Chess, Chess)>,
+    moves: Vec<RepertoireMove>,
+}
+
+/// Reads the moves of every game of the PGN file. A move reached by several lines, e.g.
+/// through a transposition, is kept once
+pub fn read_moves(pgn: &str) -> Result<Vec<RepertoireMove>, InvalidPgnError> {
+    let mut reader = Reader::new(io::Cursor::new(pgn));
+
+    let games = reader
+        .read_games(&mut RepertoireVisitor)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| InvalidPgnError(e.to_string()))?;
+    if games.is_empty() {
+        return Err(InvalidPgnError("no games found".to_string()));
+    }
+
+    let mut seen = HashSet::new();
+    let mut moves = vec![];
+    for game_moves in games {
+        for repertoire_move in game_moves? {
+            if seen.insert((
+                *repertoire_move.position_key(),
+                repertoire_move.uci().clone(),
+            )) {
+                moves.push(repertoire_move);
+            }
+        }
+    }
+
+    Ok(moves)
+}
+
+/// Writes the repertoire as a game starting at the position of its first move, with the
+/// alternatives of a move as variations. A position reached again, e.g. through a
+/// transposition, is only continued the first time
+pub fn write(repertoire: &Repertoire) -> String {
+    let mut pgn = format!("[Event \"{}\"]\n", escape_tag(repertoire.name()));
+    let Some(first_move) = repertoire.moves().first() else {
+        pgn.push_str("[Result \"*\"]\n\n*\n");
+        return pgn;
+    };
+
+    let root = shakmaty::fen::Fen::from_str(&first_move.fen().to_string())
+        .ok()
+        .and_then(|fen| fen.into_position::<Chess>(CastlingMode::Standard).ok())
+        .unwrap_or_default();
+    if position_key::from_chess(&root) != position_key::from_chess(&Chess::new()) {
+        pgn.push_str(&format!("[SetUp \"1\"]\n[FEN \"{}\"]\n", first_move.fen()));
+    }
+    pgn.push_str("[Result \"*\"]\n\n");
+
+    let mut moves_by_key: HashMap<i64, Vec<&RepertoireMove>> = HashMap::new();
+    for repertoire_move in repertoire.moves() {
+        moves_by_key
+            .entry(*repertoire_move.position_key())
+            .or_default()
+            .push(repertoire_move);
+    }
+
+    let mut tokens = write_moves(&root, &moves_by_key, &mut HashSet::new(), true);
+    tokens.push("*".to_string());
+    pgn.push_str(&tokens.join(" "));
+    pgn.push('\n');
+    pgn
+}
+
+/// SAN of the UCI move in the position of the FEN, `None` if the move can't be played there
+pub fn san(fen: &Fen, uci: &str) -> Option<String> {
+    let chess: Chess = shakmaty::fen::Fen::from_str(&fen.to_string())
+        .ok()?
+        .into_position(CastlingMode::Standard)
+        .ok()?;
+    let mv = UciMove::from_str(uci).ok()?.to_move(&chess).ok()?;
+
+    Some(SanPlus::from_move(chess, mv).to_string())
+}
+
+/// Movetext of the moves from the position, the first move of a position continuing the
+/// line and the others as variations
+fn write_moves(
+    chess: &Chess,
+    moves_by_key: &HashMap<i64, Vec<&RepertoireMove>>,
+    written_keys: &mut HashSet<i64>,
+    needs_number: bool,
+) -> Vec<String> {
+    let key = position_key::from_chess(chess);
+    if !written_keys.insert(key) {
+        return vec![];
+    }
+    let playable_moves = moves_by_key
+        .get(&key)
+        .into_iter()
+        .flatten()
+        .filter_map(|repertoire_move| {
+            let mv = UciMove::from_str(repertoire_move.uci())
+                .ok()?
+                .to_move(chess)
+                .ok()?;
+            let san = SanPlus::from_move(chess.clone(), mv);
+            Some((san, chess.clone().play(mv).ok()?))
+        })
+        .collect::<Vec<_>>();
+    let Some(((san, next_chess), alternatives)) = playable_moves.split_first() else {
+        return vec![];
+    };
+
+    let mut tokens = vec![numbered_san(chess, san, needs_number)];
+    for (alternative_san, alternative_chess) in alternatives {
+        let mut variation = vec![numbered_san(chess, alternative_san, true)];
+        variation.extend(write_moves(
+            alternative_chess,
+            moves_by_key,
+            written_keys,
+            false,
+        ));
+        tokens.push(format!("({})", variation.join(" ")));
+    }
+    tokens.extend(write_moves(
+        next_chess,
+        moves_by_key,
+        written_keys,
+        !alternatives.is_empty(),
+    ));
+    tokens
+}
+
+/// SAN with the move number, which black moves only need at the start of a line
+fn numbered_san(chess: &Chess, san: &SanPlus, needs_number: bool) -> String {
+    match chess.turn() {
+        Color::White => format!("{}. {}", chess.fullmoves(), san),
+        Color::Black if needs_number => format!("{}... {}", chess.fullmoves(), san),
+        Color::Black => san.to_string(),
+    }
+}
+
+fn escape_tag(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl Visitor for RepertoireVisitor {
+    /// Value of the `FEN` tag for games set up from a position
+    type Tags = Option<String>;
+    type Movetext = RepertoireMovetext;
+    type Output = Result<Vec<RepertoireMove>, InvalidPgnError>;
+
+    fn begin_tags(&mut self) -> ControlFlow<Self::Output, Self::Tags> {
+        ControlFlow::Continue(None)
+    }
+
+    fn tag(
+        &mut self,
+        tags: &mut Self::Tags,
+        name: &[u8],
+        value: RawTag<'_>,
+    ) -> ControlFlow<Self::Output> {
+        if name == b"FEN" {
+            *tags = Some(value.decode_utf8_lossy().to_string());
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn begin_movetext(&mut self, tags: Self::Tags) -> ControlFlow<Self::Output, Self::Movetext> {
+        let chess = match tags {
+            Some(fen) => match fen
+                .parse::<shakmaty::fen::Fen>()
+                .ok()
+                .and_then(|fen| fen.into_position(CastlingMode::Standard).ok())
+            {
+                Some(chess) => chess,
+                None => {
+                    return ControlFlow::Break(Err(InvalidPgnError("invalid FEN tag".to_string())));
+                }
+            },
+            None => Chess::new(),
+        };
+
+        ControlFlow::Continue(RepertoireMovetext {
+            before_last: chess.clone(),
+            chess,
+            variations: vec![],
+            moves: vec![],
+        })
+    }
+
+    fn san(
+        &mut self,
+        movetext: &mut Self::Movetext,
+        san_plus: SanPlus,
+    ) -> ControlFlow<Self::Output> {
+        let Some((mv, next_chess)) = san_plus
+            .san
+            .to_move(&movetext.chess)
+            .ok()
+            .and_then(|mv| Some((mv, movetext.chess.clone().play(mv).ok()?)))
+        else {
+            return ControlFlow::Break(Err(InvalidPgnError(format!("illegal move {}", san_plus))));
+        };
+
+        movetext.moves.push(RepertoireMove::new(
+            shakmaty::fen::Fen::from_position(&movetext.chess, EnPassantMode::Always).into(),
+            position_key::from_chess(&movetext.chess),
+            UciMove::from_standard(mv).to_string(),
+            SanPlus::from_move(movetext.chess.clone(), mv).to_string(),
+        ));
+        movetext.before_last = std::mem::replace(&mut movetext.chess, next_chess);
+        ControlFlow::Continue(())
+    }
+
+    fn begin_variation(
+        &mut self,
+        movetext: &mut Self::Movetext,
+    ) -> ControlFlow<Self::Output, Skip> {
+        // The variation replaces the last move, so it starts from the position before it
+        movetext
+            .variations
+            .push((movetext.chess.clone(), movetext.before_last.clone()));
+        movetext.chess = movetext.before_last.clone();
+        ControlFlow::Continue(Skip(false))
+    }
+
+    fn end_variation(&mut self, movetext: &mut Self::Movetext) -> ControlFlow<Self::Output> {
+        if let Some((chess, before_last)) = movetext.variations.pop() {
+            movetext.chess = chess;
+            movetext.before_last = before_last;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn end_game(&mut self, movetext: Self::Movetext) -> Self::Output {
+        Ok(movetext.moves)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn sans(moves: &[RepertoireMove]) -> Vec<&str> {
+        moves
+            .iter()
+            .map(|repertoire_move| repertoire_move.san().as_str())
+            .collect()
+    }
+
+    fn repertoire(moves: Vec<RepertoireMove>) -> Repertoire {
+        Repertoire::new(
+            uuid::Uuid::nil(),
+            "Sicilian \"main\"".to_string(),
+            crate::domain::game::models::game::Color::Black,
+            moves,
+            DateTime::UNIX_EPOCH,
+            DateTime::UNIX_EPOCH,
+        )
+    }
+
+    #[test]
+    fn test_read_variations() {
+        let moves = read_moves("1. e4 c5 (1... e5 2. Nf3 (2. Bc4) 2... Nc6) 2. Nf3 d6 *").unwrap();
+
+        assert_eq!(
+            sans(&moves),
+            vec!["e4", "c5", "e5", "Nf3", "Bc4", "Nc6", "Nf3", "d6"]
+        );
+        let bc4 = &moves[4];
+        assert_eq!(
+            bc4.fen().to_string(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2"
+        );
+        assert_eq!(bc4.uci(), "f1c4");
+        assert_eq!(
+            *bc4.position_key(),
+            position_key::from_fen(&bc4.fen().to_string()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_read_keeps_moves_once() {
+        let moves = read_moves(
+            "1. Nf3 Nf6 2. Nc3 (2. c4) *\n\n1. Nc3 Nf6 2. Nf3 Nc6 (2... d5) *\n\n1. Nf3 Nf6 2. c4 *",
+        )
+        .unwrap();
+
+        assert_eq!(
+            sans(&moves),
+            vec!["Nf3", "Nf6", "Nc3", "c4", "Nc3", "Nf6", "Nf3", "Nc6", "d5"]
+        );
+    }
+
+    #[test]
+    fn test_read_illegal_move() {
+        assert_eq!(
+            read_moves("1. e4 (1. e5) *"),
+            Err(InvalidPgnError("illegal move e5".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_san() {
+        let fen =
+            Fen::new_unchecked("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+
+        assert_eq!(san(&fen, "g1f3"), Some("Nf3".to_string()));
+        assert_eq!(san(&fen, "e4e5"), None);
+    }
+
+    #[test]
+    fn test_write_round_trip() {
+        let moves = read_moves("1. e4 c5 (1... e5 2. Nf3 (2. Bc4) 2... Nc6) 2. Nf3 d6 *").unwrap();
+
+        let pgn = write(&repertoire(moves.clone()));
+
+        assert_eq!(
+            pgn,
+            "[Event \"Sicilian \\\"main\\\"\"]\n[Result \"*\"]\n\n\
+            1. e4 c5 (1... e5 2. Nf3 (2. Bc4) 2... Nc6) 2. Nf3 d6 *\n"
+        );
+        assert_eq!(read_moves(&pgn).unwrap(), moves);
+    }
+
+    #[test]
+    fn test_write_from_position() {
+        let moves = read_moves(
+            "[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n\n12... Kd7 (12... Kf7) 13. e4 *",
+        )
+        .unwrap();
+
+        assert_eq!(
+            write(&repertoire(moves)),
+            "[Event \"Sicilian \\\"main\\\"\"]\n[SetUp \"1\"]\n\
+            [FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 12\"]\n[Result \"*\"]\n\n\
+            12... Kd7 (12... Kf7) 13. e4 *\n"
+        );
+    }
+
+    #[test]
+    fn test_write_repeated_position_once() {
+        let moves = read_moves("1. Nf3 Nf6 2. Ng1 Ng8 3. e4 *").unwrap();
+
+        assert_eq!(
+            write(&repertoire(moves)),
+            "[Event \"Sicilian \\\"main\\\"\"]\n[Result \"*\"]\n\n1. Nf3 (1. e4) 1... Nf6 2. Ng1 Ng8 *\n"
+        );
+    }
+}
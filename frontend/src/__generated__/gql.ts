@@ -14,11 +14,11 @@ import { TypedDocumentNode as DocumentNode } from '@graphql-typed-document-node/
  * Learn more about it here: https://the-guild.dev/graphql/codegen/plugins/presets/preset-client#reducing-bundle-size
  */
 type Documents = {
-    "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName)\n  }\n": typeof types.UpdateUserGamesDocument,
+    "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName) {\n      phase\n      fraction\n      failures\n      error\n    }\n  }\n": typeof types.UpdateUserGamesDocument,
     "\n  query GetMoveStats(\n    $positionFen: String!,\n    $username: String!,\n    $playAs: Color!,\n    $platformName: PlatformName!,\n    $fromTimestampSeconds: Int,\n    $toTimestampSeconds: Int\n  ) {\n    getMoveStats(\n      positionFen: $positionFen,\n      username: $username,\n      playAs: $playAs,\n      platformName: $platformName,\n      fromTimestampSeconds: $fromTimestampSeconds,\n      toTimestampSeconds: $toTimestampSeconds,\n    ) {\n      moveUci\n      avgOpponentElo\n      wins\n      total\n      draws\n      lastPlayedAt\n    }\n  }\n": typeof types.GetMoveStatsDocument,
 };
 const documents: Documents = {
-    "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName)\n  }\n": types.UpdateUserGamesDocument,
+    "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName) {\n      phase\n      fraction\n      failures\n      error\n    }\n  }\n": types.UpdateUserGamesDocument,
     "\n  query GetMoveStats(\n    $positionFen: String!,\n    $username: String!,\n    $playAs: Color!,\n    $platformName: PlatformName!,\n    $fromTimestampSeconds: Int,\n    $toTimestampSeconds: Int\n  ) {\n    getMoveStats(\n      positionFen: $positionFen,\n      username: $username,\n      playAs: $playAs,\n      platformName: $platformName,\n      fromTimestampSeconds: $fromTimestampSeconds,\n      toTimestampSeconds: $toTimestampSeconds,\n    ) {\n      moveUci\n      avgOpponentElo\n      wins\n      total\n      draws\n      lastPlayedAt\n    }\n  }\n": types.GetMoveStatsDocument,
 };
 
@@ -39,7 +39,7 @@ export function gql(source: string): unknown;
 /**
  * The gql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
  */
-export function gql(source: "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName)\n  }\n"): (typeof documents)["\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName)\n  }\n"];
+export function gql(source: "\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName) {\n      phase\n      fraction\n      failures\n      error\n    }\n  }\n"): (typeof documents)["\n  subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {\n    updateUserGames(username: $username, platformName: $platformName) {\n      phase\n      fraction\n      failures\n      error\n    }\n  }\n"];
 /**
  * The gql function is used to parse GraphQL queries into a document that can be used by GraphQL clients.
  */
@@ -14,6 +14,7 @@ export type Scalars = {
   Boolean: { input: boolean; output: boolean; }
   Int: { input: number; output: number; }
   Float: { input: number; output: number; }
+  Uuid: { input: any; output: any; }
 };
 
 export enum Color {
@@ -21,57 +22,677 @@ export enum Color {
   White = 'WHITE'
 }
 
+export type ColorRecord = {
+  __typename?: 'ColorRecord';
+  draws: Scalars['Int']['output'];
+  games: Scalars['Int']['output'];
+  losses: Scalars['Int']['output'];
+  wins: Scalars['Int']['output'];
+};
+
+export enum Deviator {
+  Opponent = 'OPPONENT',
+  Player = 'PLAYER'
+}
+
+export type Game = {
+  __typename?: 'Game';
+  black: Scalars['String']['output'];
+  blackElo: Scalars['Int']['output'];
+  finishedAt: Scalars['Int']['output'];
+  id: Scalars['Uuid']['output'];
+  /** opening of the deepest position of the game in the opening book */
+  opening?: Maybe<Opening>;
+  pgn: Scalars['String']['output'];
+  platformName: PlatformName;
+  rated: Scalars['Boolean']['output'];
+  timeClass?: Maybe<TimeClass>;
+  timeControl?: Maybe<Scalars['String']['output']>;
+  variant: Variant;
+  white: Scalars['String']['output'];
+  whiteElo: Scalars['Int']['output'];
+  winner?: Maybe<Color>;
+};
+
+export type GameConnection = {
+  __typename?: 'GameConnection';
+  edges: Array<GameEdge>;
+  pageInfo: PageInfo;
+};
+
+export type GameDetail = {
+  __typename?: 'GameDetail';
+  game: Game;
+  /** null if the moves can't be replayed, e.g. for other variants than standard chess */
+  mainline?: Maybe<Mainline>;
+};
+
+export type GameEdge = {
+  __typename?: 'GameEdge';
+  cursor: Scalars['String']['output'];
+  node: Game;
+};
+
+export type GameImportError = {
+  __typename?: 'GameImportError';
+  createdAt: Scalars['Int']['output'];
+  gameId: Scalars['Uuid']['output'];
+  /** half-move number of the offending move, starting at 1 */
+  ply?: Maybe<Scalars['Int']['output']>;
+  reason: Scalars['String']['output'];
+  /** move that could not be played */
+  san?: Maybe<Scalars['String']['output']>;
+};
+
+export type GameImportProgress = {
+  __typename?: 'GameImportProgress';
+  archivesDone: Scalars['Int']['output'];
+  archivesTotal: Scalars['Int']['output'];
+  /** month of the archive being stored, e.g. `2024/05` */
+  currentArchive?: Maybe<Scalars['String']['output']>;
+  /** fetched games that were already stored */
+  duplicatesSkipped: Scalars['Int']['output'];
+  /** reason of the failure for the `FAILED` phase */
+  error?: Maybe<Scalars['String']['output']>;
+  /** amount of stored games whose positions could not be indexed */
+  failures: Scalars['Int']['output'];
+  /** fraction of the import that is completed, between 0 and 1 */
+  fraction: Scalars['Float']['output'];
+  gamesFetched: Scalars['Int']['output'];
+  gamesInserted: Scalars['Int']['output'];
+  /** stored games whose details were refreshed by a full resync */
+  gamesUpdated: Scalars['Int']['output'];
+  /** `DONE`, `FAILED` and `CANCELLED` are sent once as the last event of an import */
+  phase: ImportPhase;
+  /** inserted games of non-standard variants, which have no positions indexed */
+  variantsSkipped: Scalars['Int']['output'];
+};
+
+export enum GameResult {
+  Draw = 'DRAW',
+  Loss = 'LOSS',
+  Win = 'WIN'
+}
+
 export type GraphQlMoveStat = {
   __typename?: 'GraphQLMoveStat';
+  avgBlackElo: Scalars['Int']['output'];
   avgOpponentElo: Scalars['Int']['output'];
+  avgOwnElo: Scalars['Int']['output'];
+  /** average of the known ratings of the players with white */
+  avgWhiteElo: Scalars['Int']['output'];
+  blackWins: Scalars['Int']['output'];
   draws: Scalars['Int']['output'];
+  firstPlayedAt: Scalars['Int']['output'];
   lastPlayedAt: Scalars['Int']['output'];
+  losses: Scalars['Int']['output'];
   moveUci: Scalars['String']['output'];
+  /**
+   * opening of the position after the move, or of the queried position if the move
+   * leaves the opening book. Only set by `getMoveStats`
+   */
+  opening?: Maybe<Opening>;
+  performanceRating: Scalars['Int']['output'];
+  /** percentage of the points scored, counting draws as half a point */
+  score: Scalars['Float']['output'];
   total: Scalars['Int']['output'];
+  whiteWins: Scalars['Int']['output'];
   wins: Scalars['Int']['output'];
 };
 
+export type ImportJob = {
+  __typename?: 'ImportJob';
+  createdAt: Scalars['Int']['output'];
+  error?: Maybe<Scalars['String']['output']>;
+  finishedAt?: Maybe<Scalars['Int']['output']>;
+  fullResync: Scalars['Boolean']['output'];
+  gamesInserted: Scalars['Int']['output'];
+  id: Scalars['Uuid']['output'];
+  /** month of the last archive whose games are stored, e.g. `2024/05` */
+  lastArchive?: Maybe<Scalars['String']['output']>;
+  platformName: PlatformName;
+  status: ImportJobStatus;
+  updatedAt: Scalars['Int']['output'];
+  username: Scalars['String']['output'];
+};
+
+export enum ImportJobStatus {
+  Cancelled = 'CANCELLED',
+  Done = 'DONE',
+  Failed = 'FAILED',
+  Pending = 'PENDING',
+  Running = 'RUNNING'
+}
+
+export enum ImportPhase {
+  Cancelled = 'CANCELLED',
+  Done = 'DONE',
+  Downloading = 'DOWNLOADING',
+  Failed = 'FAILED',
+  Indexing = 'INDEXING',
+  ResolvingArchives = 'RESOLVING_ARCHIVES'
+}
+
+export type Mainline = {
+  __typename?: 'Mainline';
+  plies: Array<Ply>;
+  startFen: Scalars['String']['output'];
+};
+
+export type MoveComparison = {
+  __typename?: 'MoveComparison';
+  moveUci: Scalars['String']['output'];
+  /**
+   * one entry per player in the order of the requested players, null if the player
+   * never played the move
+   */
+  stats: Array<Maybe<GraphQlMoveStat>>;
+};
+
+export type MoveTree = {
+  __typename?: 'MoveTree';
+  /** moves played in the position, the most played first */
+  branches: Array<MoveTreeBranch>;
+  fen: Scalars['String']['output'];
+};
+
+export type MoveTreeBranch = {
+  __typename?: 'MoveTreeBranch';
+  san: Scalars['String']['output'];
+  stat: GraphQlMoveStat;
+  /** position after the move */
+  tree: MoveTree;
+};
+
+/** The root mutation object of the schema */
+export type Mutation = {
+  __typename?: 'Mutation';
+  /**
+   * Stops the running import and returns it once it stopped. The games stored so far are
+   * kept. Null if the import with the id is not running
+   */
+  cancelImport?: Maybe<ImportJob>;
+  /**
+   * Stores a repertoire of the color. The mainlines and variations of every game of the
+   * PGN become its moves
+   */
+  createRepertoire: Repertoire;
+  /**
+   * Deletes the stored games of the player and returns how many were deleted. The next
+   * import of the player downloads the whole history again
+   */
+  deletePlayerGames: Scalars['Int']['output'];
+  /** Deletes the repertoire, false if there is no repertoire with the id */
+  deleteRepertoire: Scalars['Boolean']['output'];
+  /** Parses every game of the PGN and stores the ones that are not stored yet */
+  importPgn: PgnImportSummary;
+  /**
+   * Starts an import of the new games of the player, or of all games for a full resync,
+   * and returns it without waiting for it. Returns the running import if there is one,
+   * a full resync fails while the new games are imported. `updateUserGames` or
+   * `importJobs` report its progress
+   */
+  startImport: ImportJob;
+  /**
+   * Changes the given fields of the repertoire, a PGN replaces all its moves. Null if
+   * there is no repertoire with the id
+   */
+  updateRepertoire?: Maybe<Repertoire>;
+};
+
+
+/** The root mutation object of the schema */
+export type MutationCancelImportArgs = {
+  jobId: Scalars['Uuid']['input'];
+};
+
+
+/** The root mutation object of the schema */
+export type MutationCreateRepertoireArgs = {
+  color: Color;
+  name: Scalars['String']['input'];
+  pgn?: InputMaybe<Scalars['String']['input']>;
+};
+
+
+/** The root mutation object of the schema */
+export type MutationDeletePlayerGamesArgs = {
+  platformName: PlatformName;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root mutation object of the schema */
+export type MutationDeleteRepertoireArgs = {
+  id: Scalars['Uuid']['input'];
+};
+
+
+/** The root mutation object of the schema */
+export type MutationImportPgnArgs = {
+  pgn: Scalars['String']['input'];
+};
+
+
+/** The root mutation object of the schema */
+export type MutationStartImportArgs = {
+  fullResync?: InputMaybe<Scalars['Boolean']['input']>;
+  platformName: PlatformName;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root mutation object of the schema */
+export type MutationUpdateRepertoireArgs = {
+  color?: InputMaybe<Color>;
+  id: Scalars['Uuid']['input'];
+  name?: InputMaybe<Scalars['String']['input']>;
+  pgn?: InputMaybe<Scalars['String']['input']>;
+};
+
+export type Opening = {
+  __typename?: 'Opening';
+  eco: Scalars['String']['output'];
+  name: Scalars['String']['output'];
+};
+
+export type OpeningFamilyReport = {
+  __typename?: 'OpeningFamilyReport';
+  avgOpponentElo: Scalars['Int']['output'];
+  ecos: Array<Scalars['String']['output']>;
+  /** opening name without the variation, null for the games outside the opening book */
+  family?: Maybe<Scalars['String']['output']>;
+  record: ColorRecord;
+  /** percentage of the points scored, counting draws as half a point */
+  score: Scalars['Float']['output'];
+  /** results per period, the oldest first */
+  trend: Array<OpeningTrendPoint>;
+};
+
+export type OpeningTrendPoint = {
+  __typename?: 'OpeningTrendPoint';
+  periodStart: Scalars['Int']['output'];
+  record: ColorRecord;
+  /** percentage of the points scored, counting draws as half a point */
+  score: Scalars['Float']['output'];
+};
+
+export type PageInfo = {
+  __typename?: 'PageInfo';
+  /** cursor of the last edge, to pass as `after` for the next page */
+  endCursor?: Maybe<Scalars['String']['output']>;
+  hasNextPage: Scalars['Boolean']['output'];
+};
+
+export type PgnImportSummary = {
+  __typename?: 'PgnImportSummary';
+  /** inserted games whose positions could not be indexed */
+  failed: Scalars['Int']['output'];
+  /** games that were not stored before */
+  inserted: Scalars['Int']['output'];
+  /** games found in the PGN */
+  parsed: Scalars['Int']['output'];
+  /** inserted games of non-standard variants, which have no positions indexed */
+  skipped: Scalars['Int']['output'];
+};
+
 export enum PlatformName {
-  ChessCom = 'CHESS_COM'
+  ChessCom = 'CHESS_COM',
+  Lichess = 'LICHESS',
+  Pgn = 'PGN'
 }
 
+export type PlayerProfile = {
+  __typename?: 'PlayerProfile';
+  asBlack: ColorRecord;
+  asWhite: ColorRecord;
+  firstGameAt: Scalars['Int']['output'];
+  firstMovesAsBlack: Array<GraphQlMoveStat>;
+  /** most played first moves, the most played first */
+  firstMovesAsWhite: Array<GraphQlMoveStat>;
+  lastGameAt: Scalars['Int']['output'];
+  platformName: PlatformName;
+  /** null if no game of the player is rated */
+  rating?: Maybe<RatingSummary>;
+  totalGames: Scalars['Int']['output'];
+  /** username as spelled in the latest game of the player */
+  username: Scalars['String']['output'];
+};
+
+export type PlayerRef = {
+  platformName: PlatformName;
+  playAs: Color;
+  username: Scalars['String']['input'];
+};
+
+export type Ply = {
+  __typename?: 'Ply';
+  /** remaining time of the player who moved */
+  clockSeconds?: Maybe<Scalars['Float']['output']>;
+  /** position after the move */
+  fen: Scalars['String']['output'];
+  /** half-move number, starting at 1 */
+  ply: Scalars['Int']['output'];
+  san: Scalars['String']['output'];
+  uci: Scalars['String']['output'];
+};
+
 /** The root query object of the schema */
 export type Query = {
   __typename?: 'Query';
+  /**
+   * Move statistics of several players in the same position. Every move played by at
+   * least one of the players gets a row with an entry per player
+   */
+  compareMoveStats: Array<MoveComparison>;
+  /** Stored game with its moves, null if there is no game with the id */
+  game?: Maybe<GameDetail>;
+  /**
+   * Games of the player, the latest first. With a position, only the games that pass
+   * through it are listed, optionally only those where `nextMoveUci` was played there
+   */
+  games: GameConnection;
+  /**
+   * Games of the player whose positions could not be indexed, so their moves are
+   * missing from the statistics
+   */
+  getGameImportErrors: Array<GameImportError>;
+  /** Imports of the player, the latest first */
+  getImportJobs: Array<ImportJob>;
+  /**
+   * Statistics of the moves played in the position. With a username the games of the
+   * player with `playAs` are counted, otherwise all stored games of the platform, or only
+   * those with one of `players`, and wins count for the side to move. The rating bounds
+   * apply to the average rating of both players, the own and opponent Elo bounds to the
+   * ratings from the side of the player or of the side to move
+   */
   getMoveStats: Array<GraphQlMoveStat>;
+  /**
+   * Tree of the lines of up to `depth` moves played from the position, each move with
+   * its statistics and the position it leads to. Games are selected like for
+   * `getMoveStats`, and only moves played in at least `minGames` games are kept
+   */
+  moveTree: MoveTree;
+  /**
+   * Results of the games of the player with `playAs` per opening family, the most
+   * played family first, with a trend split into periods of `trendPeriod`
+   */
+  openingReport: Array<OpeningFamilyReport>;
+  /**
+   * Statistics over the stored games of the player, null if no game of the player is
+   * stored
+   */
+  player?: Maybe<PlayerProfile>;
+  /** Stored repertoire, null if there is no repertoire with the id */
+  repertoire?: Maybe<Repertoire>;
+  /**
+   * Where the games of the player with the color of the repertoire leave it: the first
+   * move of each game outside of the repertoire, by the player or by the opponent, with
+   * the games counted per position and move. Null if there is no repertoire with the id
+   */
+  repertoireDeviations?: Maybe<RepertoireDeviationReport>;
+  /** Stored repertoires by name */
+  repertoires: Array<Repertoire>;
+  /** Players whose games were imported, with the archive their next import continues at */
+  trackedPlayers: Array<TrackedPlayer>;
+};
+
+
+/** The root query object of the schema */
+export type QueryCompareMoveStatsArgs = {
+  fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  players: Array<PlayerRef>;
+  positionFen: Scalars['String']['input'];
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  timeClasses?: InputMaybe<Array<TimeClass>>;
+  toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+};
+
+
+/** The root query object of the schema */
+export type QueryGameArgs = {
+  id: Scalars['Uuid']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryGamesArgs = {
+  after?: InputMaybe<Scalars['String']['input']>;
+  first?: InputMaybe<Scalars['Int']['input']>;
+  fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  nextMoveUci?: InputMaybe<Scalars['String']['input']>;
+  platformName: PlatformName;
+  playAs?: InputMaybe<Color>;
+  positionFen?: InputMaybe<Scalars['String']['input']>;
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  result?: InputMaybe<GameResult>;
+  timeClasses?: InputMaybe<Array<TimeClass>>;
+  toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryGetGameImportErrorsArgs = {
+  platformName: PlatformName;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryGetImportJobsArgs = {
+  platformName: PlatformName;
+  username: Scalars['String']['input'];
 };
 
 
 /** The root query object of the schema */
 export type QueryGetMoveStatsArgs = {
   fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  maxOpponentElo?: InputMaybe<Scalars['Int']['input']>;
+  maxOwnElo?: InputMaybe<Scalars['Int']['input']>;
+  maxRating?: InputMaybe<Scalars['Int']['input']>;
+  minOpponentElo?: InputMaybe<Scalars['Int']['input']>;
+  minOwnElo?: InputMaybe<Scalars['Int']['input']>;
+  minRating?: InputMaybe<Scalars['Int']['input']>;
   platformName: PlatformName;
-  playAs: Color;
+  playAs?: InputMaybe<Color>;
+  players?: InputMaybe<Array<Scalars['String']['input']>>;
+  positionFen: Scalars['String']['input'];
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  timeClasses?: InputMaybe<Array<TimeClass>>;
+  toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  username?: InputMaybe<Scalars['String']['input']>;
+};
+
+
+/** The root query object of the schema */
+export type QueryMoveTreeArgs = {
+  depth: Scalars['Int']['input'];
+  fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  maxOpponentElo?: InputMaybe<Scalars['Int']['input']>;
+  maxOwnElo?: InputMaybe<Scalars['Int']['input']>;
+  maxRating?: InputMaybe<Scalars['Int']['input']>;
+  minGames?: InputMaybe<Scalars['Int']['input']>;
+  minOpponentElo?: InputMaybe<Scalars['Int']['input']>;
+  minOwnElo?: InputMaybe<Scalars['Int']['input']>;
+  minRating?: InputMaybe<Scalars['Int']['input']>;
+  platformName: PlatformName;
+  playAs?: InputMaybe<Color>;
+  players?: InputMaybe<Array<Scalars['String']['input']>>;
   positionFen: Scalars['String']['input'];
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  timeClasses?: InputMaybe<Array<TimeClass>>;
+  toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  username?: InputMaybe<Scalars['String']['input']>;
+};
+
+
+/** The root query object of the schema */
+export type QueryOpeningReportArgs = {
+  fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  platformName: PlatformName;
+  playAs: Color;
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  timeClasses?: InputMaybe<Array<TimeClass>>;
+  toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  trendPeriod?: InputMaybe<TrendPeriod>;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryPlayerArgs = {
+  platformName: PlatformName;
+  username: Scalars['String']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryRepertoireArgs = {
+  id: Scalars['Uuid']['input'];
+};
+
+
+/** The root query object of the schema */
+export type QueryRepertoireDeviationsArgs = {
+  fromTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
+  platformName: PlatformName;
+  ratedOnly?: InputMaybe<Scalars['Boolean']['input']>;
+  repertoireId: Scalars['Uuid']['input'];
+  timeClasses?: InputMaybe<Array<TimeClass>>;
   toTimestampSeconds?: InputMaybe<Scalars['Int']['input']>;
   username: Scalars['String']['input'];
 };
 
-/** The root subscription object of the schema */
+
+/** The root query object of the schema */
+export type QueryTrackedPlayersArgs = {
+  platformName?: InputMaybe<PlatformName>;
+};
+
+export type RatingSummary = {
+  __typename?: 'RatingSummary';
+  /** rating in the latest rated game */
+  current: Scalars['Int']['output'];
+  max: Scalars['Int']['output'];
+  min: Scalars['Int']['output'];
+};
+
+export type Repertoire = {
+  __typename?: 'Repertoire';
+  color: Color;
+  createdAt: Scalars['Int']['output'];
+  id: Scalars['Uuid']['output'];
+  /** moves in PGN order, the mainline move of a position before its alternatives */
+  moves: Array<RepertoireMove>;
+  name: Scalars['String']['output'];
+  /** the moves as a game with variations */
+  pgn: Scalars['String']['output'];
+  updatedAt: Scalars['Int']['output'];
+};
+
+export type RepertoireDeviation = {
+  __typename?: 'RepertoireDeviation';
+  deviator: Deviator;
+  /** moves of the repertoire in the position */
+  expectedSans: Array<Scalars['String']['output']>;
+  fen: Scalars['String']['output'];
+  lastPlayedAt: Scalars['Int']['output'];
+  playedSan: Scalars['String']['output'];
+  playedUci: Scalars['String']['output'];
+  /** half-move number of the deviating move, the lowest one for transpositions */
+  ply: Scalars['Int']['output'];
+  /** results of the games from the side of the player */
+  record: ColorRecord;
+  /** percentage of the points scored, counting draws as half a point */
+  score: Scalars['Float']['output'];
+};
+
+export type RepertoireDeviationReport = {
+  __typename?: 'RepertoireDeviationReport';
+  /** deviations of the other games, the most frequent first */
+  deviations: Array<RepertoireDeviation>;
+  /** games that stayed in the repertoire until it or the game ended */
+  followedGames: Scalars['Int']['output'];
+  /** games of the player with the color of the repertoire */
+  games: Scalars['Int']['output'];
+};
+
+export type RepertoireMove = {
+  __typename?: 'RepertoireMove';
+  fen: Scalars['String']['output'];
+  san: Scalars['String']['output'];
+  uci: Scalars['String']['output'];
+};
+
+/** The root subscription object of the schema. */
 export type Subscription = {
   __typename?: 'Subscription';
-  updateUserGames: Scalars['Float']['output'];
+  /**
+   * Imports new games of the player and reports the progress of the import. The stream
+   * ends after an event with the `DONE`, `FAILED` or `CANCELLED` phase. A full resync
+   * downloads every game again, inserting the missing ones and refreshing the details of
+   * the stored ones
+   */
+  updateUserGames: GameImportProgress;
 };
 
 
-/** The root subscription object of the schema */
+/** The root subscription object of the schema. */
 export type SubscriptionUpdateUserGamesArgs = {
+  fullResync?: InputMaybe<Scalars['Boolean']['input']>;
   platformName: PlatformName;
   username: Scalars['String']['input'];
 };
 
+export enum TimeClass {
+  Blitz = 'BLITZ',
+  Bullet = 'BULLET',
+  Classical = 'CLASSICAL',
+  Daily = 'DAILY',
+  Rapid = 'RAPID'
+}
+
+export type TrackedPlayer = {
+  __typename?: 'TrackedPlayer';
+  /** stored games of the player as of the last finished import */
+  gameCount: Scalars['Int']['output'];
+  /** month of the latest synced archive, e.g. `2024/05`. The next import continues there */
+  lastSyncedArchive?: Maybe<Scalars['String']['output']>;
+  lastSyncedAt?: Maybe<Scalars['Int']['output']>;
+  platformName: PlatformName;
+  /** lowercased username */
+  username: Scalars['String']['output'];
+};
+
+export enum TrendPeriod {
+  Month = 'MONTH',
+  Week = 'WEEK',
+  Year = 'YEAR'
+}
+
+export enum Variant {
+  Antichess = 'ANTICHESS',
+  Atomic = 'ATOMIC',
+  Bughouse = 'BUGHOUSE',
+  Chess960 = 'CHESS960',
+  Crazyhouse = 'CRAZYHOUSE',
+  Horde = 'HORDE',
+  KingOfTheHill = 'KING_OF_THE_HILL',
+  Other = 'OTHER',
+  RacingKings = 'RACING_KINGS',
+  Standard = 'STANDARD',
+  ThreeCheck = 'THREE_CHECK'
+}
+
 export type UpdateUserGamesSubscriptionVariables = Exact<{
   username: Scalars['String']['input'];
   platformName: PlatformName;
 }>;
 
 
-export type UpdateUserGamesSubscription = { __typename?: 'Subscription', updateUserGames: number };
+export type UpdateUserGamesSubscription = { __typename?: 'Subscription', updateUserGames: { __typename?: 'GameImportProgress', phase: ImportPhase, fraction: number, failures: number, error?: string | null } };
 
 export type GetMoveStatsQueryVariables = Exact<{
   positionFen: Scalars['String']['input'];
@@ -86,5 +707,5 @@ export type GetMoveStatsQueryVariables = Exact<{
 export type GetMoveStatsQuery = { __typename?: 'Query', getMoveStats: Array<{ __typename?: 'GraphQLMoveStat', moveUci: string, avgOpponentElo: number, wins: number, total: number, draws: number, lastPlayedAt: number }> };
 
 
-export const UpdateUserGamesDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"subscription","name":{"kind":"Name","value":"UpdateUserGames"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"username"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"String"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"PlatformName"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"updateUserGames"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"username"},"value":{"kind":"Variable","name":{"kind":"Name","value":"username"}}},{"kind":"Argument","name":{"kind":"Name","value":"platformName"},"value":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}}}]}]}}]} as unknown as DocumentNode<UpdateUserGamesSubscription, UpdateUserGamesSubscriptionVariables>;
+export const UpdateUserGamesDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"subscription","name":{"kind":"Name","value":"UpdateUserGames"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"username"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"String"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"PlatformName"}}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"updateUserGames"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"username"},"value":{"kind":"Variable","name":{"kind":"Name","value":"username"}}},{"kind":"Argument","name":{"kind":"Name","value":"platformName"},"value":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"phase"}},{"kind":"Field","name":{"kind":"Name","value":"fraction"}},{"kind":"Field","name":{"kind":"Name","value":"failures"}},{"kind":"Field","name":{"kind":"Name","value":"error"}}]}}]}}]} as unknown as DocumentNode<UpdateUserGamesSubscription, UpdateUserGamesSubscriptionVariables>;
 export const GetMoveStatsDocument = {"kind":"Document","definitions":[{"kind":"OperationDefinition","operation":"query","name":{"kind":"Name","value":"GetMoveStats"},"variableDefinitions":[{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"positionFen"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"String"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"username"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"String"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"playAs"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"Color"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}},"type":{"kind":"NonNullType","type":{"kind":"NamedType","name":{"kind":"Name","value":"PlatformName"}}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"fromTimestampSeconds"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"Int"}}},{"kind":"VariableDefinition","variable":{"kind":"Variable","name":{"kind":"Name","value":"toTimestampSeconds"}},"type":{"kind":"NamedType","name":{"kind":"Name","value":"Int"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"getMoveStats"},"arguments":[{"kind":"Argument","name":{"kind":"Name","value":"positionFen"},"value":{"kind":"Variable","name":{"kind":"Name","value":"positionFen"}}},{"kind":"Argument","name":{"kind":"Name","value":"username"},"value":{"kind":"Variable","name":{"kind":"Name","value":"username"}}},{"kind":"Argument","name":{"kind":"Name","value":"playAs"},"value":{"kind":"Variable","name":{"kind":"Name","value":"playAs"}}},{"kind":"Argument","name":{"kind":"Name","value":"platformName"},"value":{"kind":"Variable","name":{"kind":"Name","value":"platformName"}}},{"kind":"Argument","name":{"kind":"Name","value":"fromTimestampSeconds"},"value":{"kind":"Variable","name":{"kind":"Name","value":"fromTimestampSeconds"}}},{"kind":"Argument","name":{"kind":"Name","value":"toTimestampSeconds"},"value":{"kind":"Variable","name":{"kind":"Name","value":"toTimestampSeconds"}}}],"selectionSet":{"kind":"SelectionSet","selections":[{"kind":"Field","name":{"kind":"Name","value":"moveUci"}},{"kind":"Field","name":{"kind":"Name","value":"avgOpponentElo"}},{"kind":"Field","name":{"kind":"Name","value":"wins"}},{"kind":"Field","name":{"kind":"Name","value":"total"}},{"kind":"Field","name":{"kind":"Name","value":"draws"}},{"kind":"Field","name":{"kind":"Name","value":"lastPlayedAt"}}]}}]}}]} as unknown as DocumentNode<GetMoveStatsQuery, GetMoveStatsQueryVariables>;
\ No newline at end of file
@@ -10,6 +10,7 @@ import {
 import LogoTitle from '../LogoTitle';
 import { useSubscription } from '@apollo/client';
 import { gql } from '@/__generated__';
+import { ImportPhase } from '@/__generated__/graphql';
 import { useContext } from 'react';
 import { useNavigate } from 'react-router';
 import { PLATFORM_DISPLAY_NAMES } from '@/constants';
@@ -22,7 +23,12 @@ type Props = StackProps & {
 
 const UPDATE_USER_GAMES = gql(`
   subscription UpdateUserGames($username: String!, $platformName: PlatformName!) {
-    updateUserGames(username: $username, platformName: $platformName)
+    updateUserGames(username: $username, platformName: $platformName) {
+      phase
+      fraction
+      failures
+      error
+    }
   }
 `);
 
@@ -31,24 +37,31 @@ const GameLoader = ({ onComplete, ...props }: Props) => {
 
   const navigate = useNavigate();
 
+  const onFailure = () => {
+    navigate('/');
+    toaster.create({
+      title: `Failed to fetch games for @${username}`,
+      type: 'error',
+    });
+  };
+
   const { data } = useSubscription(UPDATE_USER_GAMES, {
     variables: {
       username,
       platformName,
     },
-    onError: () => {
-      navigate('/');
-      toaster.create({
-        title: `Failed to fetch games for @${username}`,
-        type: 'error',
-      });
+    onData: ({ data }) => {
+      if (data.data?.updateUserGames.phase === ImportPhase.Failed) {
+        onFailure();
+      }
     },
+    onError: onFailure,
     onComplete: () => {
       onComplete();
     },
   });
 
-  const progressValue = (data?.updateUserGames ?? 0) * 100;
+  const progressValue = (data?.updateUserGames.fraction ?? 0) * 100;
 
   return (
     <VStack flex={1} spaceY="2rem" align="center" justify="center" {...props}>
@@ -2,12 +2,15 @@ import { PlatformName } from './__generated__/graphql';
 
 export const PLATFORM_URLS: Record<PlatformName, string> = {
   [PlatformName.ChessCom]: 'chesscom',
+  [PlatformName.Lichess]: 'lichess',
 };
 
 export const PLATFORM_COLORS: Record<PlatformName, string> = {
   [PlatformName.ChessCom]: '#22c55e',
+  [PlatformName.Lichess]: '#a3a3a3',
 };
 
 export const PLATFORM_DISPLAY_NAMES: Record<PlatformName, string> = {
   [PlatformName.ChessCom]: 'Chess.com',
+  [PlatformName.Lichess]: 'Lichess',
 };